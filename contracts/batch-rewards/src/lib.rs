@@ -7,10 +7,13 @@ mod validation;
 #[cfg(test)]
 mod test;
 
-use soroban_sdk::{contract, contractimpl, panic_with_error, token, Address, Env, Vec};
+use soroban_sdk::{
+    contract, contractimpl, panic_with_error, token, Address, Env, Map, Symbol, Vec,
+};
 
 pub use crate::types::{
-    BatchRewardResult, DataKey, RewardEvents, RewardRequest, RewardResult, MAX_BATCH_SIZE,
+    BatchRewardResult, DataKey, MultiTokenReward, RewardEvents, RewardRequest, RewardResult,
+    ScheduledRewardBatch, MAX_BATCH_SIZE,
 };
 use crate::validation::{validate_address, validate_amount};
 
@@ -34,6 +37,28 @@ pub enum BatchRewardsError {
     InsufficientBalance = 7,
     /// Invalid reward amount
     InvalidAmount = 8,
+    /// Reward would push the recipient above the lifetime cap
+    LifetimeCapExceeded = 9,
+    /// Fee basis points exceed 10000
+    InvalidFee = 10,
+    /// Reward exceeds the per-recipient maximum
+    ExceedsMaxReward = 11,
+    /// Reward distribution is paused
+    Paused = 12,
+    /// Reward is below the configured minimum
+    BelowMinReward = 13,
+    /// Recipient is on the block list
+    Blocked = 14,
+    /// Configured batch size is zero or above `MAX_BATCH_SIZE`
+    InvalidBatchSize = 15,
+    /// Reward would push the recipient above the daily cap
+    DailyCapExceeded = 16,
+    /// No scheduled batch exists for the given ID
+    ScheduleNotFound = 17,
+    /// Scheduled batch's activation ledger has not been reached
+    ScheduleNotReady = 18,
+    /// Batch total overflows i128
+    ArithmeticOverflow = 19,
 }
 
 impl From<BatchRewardsError> for soroban_sdk::Error {
@@ -105,8 +130,241 @@ impl BatchRewardsContract {
         env.events().publish(topics, (&new_admin,));
     }
 
+    /// Pauses reward distribution.
+    pub fn pause(env: Env, admin: Address) {
+        admin.require_auth();
+        Self::require_admin(&env, &admin);
+
+        env.storage().instance().set(&DataKey::Paused, &true);
+    }
+
+    /// Resumes reward distribution.
+    pub fn unpause(env: Env, admin: Address) {
+        admin.require_auth();
+        Self::require_admin(&env, &admin);
+
+        env.storage().instance().set(&DataKey::Paused, &false);
+    }
+
+    /// Returns whether reward distribution is paused.
+    pub fn is_paused(env: Env) -> bool {
+        env.storage()
+            .instance()
+            .get(&DataKey::Paused)
+            .unwrap_or(false)
+    }
+
+    /// Blocks an address from receiving rewards.
+    pub fn block_address(env: Env, admin: Address, addr: Address) {
+        admin.require_auth();
+        Self::require_admin(&env, &admin);
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::Blocked(addr), &true);
+    }
+
+    /// Removes an address from the block list.
+    pub fn unblock_address(env: Env, admin: Address, addr: Address) {
+        admin.require_auth();
+        Self::require_admin(&env, &admin);
+
+        env.storage().persistent().remove(&DataKey::Blocked(addr));
+    }
+
+    /// Returns whether an address is blocked from receiving rewards.
+    pub fn is_blocked(env: Env, addr: Address) -> bool {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Blocked(addr))
+            .unwrap_or(false)
+    }
+
+    /// Sets the lifetime cap on rewards any single recipient may receive.
+    pub fn set_lifetime_cap(env: Env, caller: Address, cap: i128) {
+        caller.require_auth();
+        Self::require_admin(&env, &caller);
+
+        if cap <= 0 {
+            panic_with_error!(&env, BatchRewardsError::InvalidAmount);
+        }
+
+        env.storage().instance().set(&DataKey::LifetimeCap, &cap);
+    }
+
+    /// Gets the lifetime reward cap, if one is configured.
+    pub fn get_lifetime_cap(env: Env) -> Option<i128> {
+        env.storage().instance().get(&DataKey::LifetimeCap)
+    }
+
+    /// Sets the cap on rewards a recipient may receive per window of
+    /// `window_ledgers` ledgers. A cap of zero disables the check.
+    pub fn set_daily_reward_cap(env: Env, admin: Address, cap: i128, window_ledgers: u64) {
+        admin.require_auth();
+        Self::require_admin(&env, &admin);
+
+        if cap < 0 || (cap > 0 && window_ledgers == 0) {
+            panic_with_error!(&env, BatchRewardsError::InvalidAmount);
+        }
+
+        env.storage().instance().set(&DataKey::DailyRewardCap, &cap);
+        env.storage()
+            .instance()
+            .set(&DataKey::DailyRewardWindow, &window_ledgers);
+    }
+
+    /// Gets the daily reward cap and its window length in ledgers.
+    pub fn get_daily_reward_cap(env: Env) -> (i128, u64) {
+        let cap: i128 = env
+            .storage()
+            .instance()
+            .get(&DataKey::DailyRewardCap)
+            .unwrap_or(0);
+        let window: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::DailyRewardWindow)
+            .unwrap_or(0);
+        (cap, window)
+    }
+
+    /// Gets the cumulative rewards a recipient has received across all batches.
+    pub fn get_recipient_reward_total(env: Env, recipient: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::RecipientRewardTotal(recipient))
+            .unwrap_or(0)
+    }
+
+    /// Gets the net amount transferred to a recipient across all batches.
+    ///
+    /// Unlike `get_recipient_reward_total`, this excludes reward fees.
+    pub fn get_total_rewarded_to(env: Env, recipient: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::TotalRewardedTo(recipient))
+            .unwrap_or(0)
+    }
+
+    /// Sets the maximum single reward per recipient. Zero disables the cap.
+    pub fn set_max_reward(env: Env, admin: Address, cap: i128) {
+        admin.require_auth();
+        Self::require_admin(&env, &admin);
+
+        if cap < 0 {
+            panic_with_error!(&env, BatchRewardsError::InvalidAmount);
+        }
+
+        env.storage()
+            .instance()
+            .set(&DataKey::MaxRewardPerRecipient, &cap);
+    }
+
+    /// Gets the maximum single reward per recipient (0 when uncapped).
+    pub fn get_max_reward(env: Env) -> i128 {
+        env.storage()
+            .instance()
+            .get(&DataKey::MaxRewardPerRecipient)
+            .unwrap_or(0)
+    }
+
+    /// Sets the minimum single reward amount. Zero disables the check.
+    pub fn set_min_reward(env: Env, admin: Address, min: i128) {
+        admin.require_auth();
+        Self::require_admin(&env, &admin);
+
+        if min < 0 {
+            panic_with_error!(&env, BatchRewardsError::InvalidAmount);
+        }
+
+        env.storage()
+            .instance()
+            .set(&DataKey::MinRewardAmount, &min);
+    }
+
+    /// Gets the minimum single reward amount (0 when disabled).
+    pub fn get_min_reward(env: Env) -> i128 {
+        env.storage()
+            .instance()
+            .get(&DataKey::MinRewardAmount)
+            .unwrap_or(0)
+    }
+
+    /// Returns the memo stored for a successful reward, by batch and position.
+    pub fn get_reward_memo(env: Env, batch_id: u64, index: u32) -> Option<Symbol> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::RewardMemo(batch_id, index))
+    }
+
+    /// Sets the maximum number of rewards accepted per reward batch.
+    ///
+    /// Must be between 1 and `MAX_BATCH_SIZE`.
+    pub fn set_max_reward_batch_size(env: Env, admin: Address, size: u32) {
+        admin.require_auth();
+        Self::require_admin(&env, &admin);
+
+        if size == 0 || size > MAX_BATCH_SIZE {
+            panic_with_error!(&env, BatchRewardsError::InvalidBatchSize);
+        }
+
+        env.storage()
+            .instance()
+            .set(&DataKey::MaxRewardBatchSize, &size);
+    }
+
+    /// Gets the maximum number of rewards per batch (`MAX_BATCH_SIZE` by default).
+    pub fn get_max_reward_batch_size(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::MaxRewardBatchSize)
+            .unwrap_or(MAX_BATCH_SIZE)
+    }
+
+    /// Sets the fee skimmed from each reward and the fee collector that receives it.
+    pub fn set_reward_fee(env: Env, caller: Address, fee_bps: u32, collector: Address) {
+        caller.require_auth();
+        Self::require_admin(&env, &caller);
+
+        if fee_bps > 10_000 {
+            panic_with_error!(&env, BatchRewardsError::InvalidFee);
+        }
+
+        env.storage()
+            .instance()
+            .set(&DataKey::RewardFeeBps, &fee_bps);
+        env.storage()
+            .instance()
+            .set(&DataKey::FeeCollector, &collector);
+    }
+
+    /// Gets the reward fee in basis points (0 when unset).
+    pub fn get_reward_fee_bps(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::RewardFeeBps)
+            .unwrap_or(0)
+    }
+
+    /// Gets the fee collector address receiving reward fees, if configured.
+    pub fn get_fee_collector(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::FeeCollector)
+    }
+
+    /// Gets the total fees routed to the fee collector.
+    pub fn get_total_fees_collected(env: Env) -> i128 {
+        env.storage()
+            .instance()
+            .get(&DataKey::TotalFeesCollected)
+            .unwrap_or(0)
+    }
+
     /// Distributes rewards to multiple recipients in a batch operation.
-    /// 
+    ///
+    /// When a reward fee is configured, each recipient receives `amount - fee`
+    /// and the fee is routed to the fee collector. Successful results and
+    /// `total_distributed` report the net amount received by recipients.
+    ///
     /// # Arguments
     /// * `env` - The Soroban environment
     /// * `caller` - The address initiating the batch rewards
@@ -124,13 +382,14 @@ impl BatchRewardsContract {
         // Verify authorization
         caller.require_auth();
         Self::require_admin(&env, &caller);
+        Self::require_not_paused(&env);
 
         // Validate batch size
         let request_count = rewards.len();
         if request_count == 0 {
             panic_with_error!(&env, BatchRewardsError::EmptyBatch);
         }
-        if request_count > MAX_BATCH_SIZE {
+        if request_count > Self::get_max_reward_batch_size(env.clone()) {
             panic_with_error!(&env, BatchRewardsError::BatchTooLarge);
         }
 
@@ -153,55 +412,65 @@ impl BatchRewardsContract {
 
         // Create token client
         let token_client = token::Client::new(&env, &token);
+        let collector: Option<Address> = env.storage().instance().get(&DataKey::FeeCollector);
+        let mut total_fees: i128 = 0;
 
         // Get initial balance to ensure sufficient funds
         let available_balance = token_client.balance(&caller);
-        let total_required: i128 = rewards.iter().fold(0i128, |sum, reward| {
-            sum + reward.amount
-        });
+        let total_required = Self::total_reward_amount(&env, &rewards);
 
         if available_balance < total_required {
             panic_with_error!(&env, BatchRewardsError::InsufficientBalance);
         }
 
         // Process each reward request
-        for reward in rewards.iter() {
-            // Validate reward amount
-            if let Err(_) = validate_amount(reward.amount) {
+        for (index, reward) in rewards.iter().enumerate() {
+            if let Err(error_code) = Self::check_reward(&env, &reward.recipient, reward.amount) {
                 failed_count += 1;
-                let error_code = BatchRewardsError::InvalidAmount as u32;
                 results.push_back(RewardResult::Failure(
                     reward.recipient.clone(),
                     reward.amount,
                     error_code,
                 ));
-                RewardEvents::reward_failure(&env, batch_id, &reward.recipient, reward.amount, error_code);
-                continue;
-            }
-
-            // Validate recipient address
-            if let Err(_) = validate_address(&env, &reward.recipient) {
-                failed_count += 1;
-                let error_code = BatchRewardsError::InvalidBatch as u32;
-                results.push_back(RewardResult::Failure(
-                    reward.recipient.clone(),
+                RewardEvents::reward_failure(
+                    &env,
+                    batch_id,
+                    &reward.recipient,
                     reward.amount,
                     error_code,
-                ));
-                RewardEvents::reward_failure(&env, batch_id, &reward.recipient, reward.amount, error_code);
+                );
                 continue;
             }
 
+            // Split off the reward fee
+            let fee = Self::reward_fee(&env, reward.amount);
+            let net_amount = reward.amount - fee;
+
             // Attempt to transfer the reward
-            match token_client.try_transfer(&caller, &reward.recipient, &reward.amount) {
+            match token_client.try_transfer(&caller, &reward.recipient, &net_amount) {
                 Ok(_) => {
+                    if let Some(collector) = &collector {
+                        if fee > 0 {
+                            token_client.transfer(&caller, collector, &fee);
+                            total_fees += fee;
+                        }
+                    }
                     successful_count += 1;
-                    total_distributed += reward.amount;
-                    results.push_back(RewardResult::Success(
-                        reward.recipient.clone(),
-                        reward.amount,
-                    ));
-                    RewardEvents::reward_success(&env, batch_id, &reward.recipient, reward.amount);
+                    total_distributed += net_amount;
+                    Self::record_reward(&env, &reward.recipient, reward.amount, net_amount);
+                    if let Some(memo) = &reward.memo {
+                        env.storage()
+                            .persistent()
+                            .set(&DataKey::RewardMemo(batch_id, index as u32), memo);
+                    }
+                    results.push_back(RewardResult::Success(reward.recipient.clone(), net_amount));
+                    RewardEvents::reward_success(
+                        &env,
+                        batch_id,
+                        &reward.recipient,
+                        net_amount,
+                        &reward.memo,
+                    );
                 }
                 Err(_) => {
                     failed_count += 1;
@@ -241,11 +510,21 @@ impl BatchRewardsContract {
             .instance()
             .set(&DataKey::TotalVolumeDistributed, &total_volume);
 
+        let fees_collected: i128 = env
+            .storage()
+            .instance()
+            .get(&DataKey::TotalFeesCollected)
+            .unwrap_or(0)
+            + total_fees;
+        env.storage()
+            .instance()
+            .set(&DataKey::TotalFeesCollected, &fees_collected);
+
         // Emit batch completed event
         RewardEvents::batch_completed(&env, batch_id, successful_count, failed_count, total_distributed);
 
         BatchRewardResult {
-            total_requests: request_count as u32,
+            total_requests: request_count,
             successful: successful_count,
             failed: failed_count,
             total_distributed,
@@ -253,6 +532,695 @@ impl BatchRewardsContract {
         }
     }
 
+    /// Stages a reward batch for distribution at or after `execute_at`.
+    ///
+    /// Funds are not moved until `execute_scheduled` runs the batch.
+    ///
+    /// # Returns
+    /// The scheduled batch ID
+    pub fn schedule_rewards(
+        env: Env,
+        caller: Address,
+        token: Address,
+        rewards: Vec<RewardRequest>,
+        execute_at: u64,
+    ) -> u64 {
+        caller.require_auth();
+        Self::require_admin(&env, &caller);
+
+        if rewards.is_empty() {
+            panic_with_error!(&env, BatchRewardsError::EmptyBatch);
+        }
+        if rewards.len() > Self::get_max_reward_batch_size(env.clone()) {
+            panic_with_error!(&env, BatchRewardsError::BatchTooLarge);
+        }
+
+        let scheduled_id: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::LastScheduledBatchId)
+            .unwrap_or(0)
+            + 1;
+        env.storage()
+            .instance()
+            .set(&DataKey::LastScheduledBatchId, &scheduled_id);
+
+        let batch = ScheduledRewardBatch {
+            token,
+            rewards,
+            execute_at,
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::ScheduledBatch(scheduled_id), &batch);
+
+        RewardEvents::batch_scheduled(&env, scheduled_id, execute_at);
+        scheduled_id
+    }
+
+    /// Runs a scheduled reward batch once its activation ledger is reached.
+    ///
+    /// The batch is consumed, so it can only be executed once.
+    pub fn execute_scheduled(env: Env, caller: Address, scheduled_id: u64) -> BatchRewardResult {
+        let key = DataKey::ScheduledBatch(scheduled_id);
+        let batch: ScheduledRewardBatch = match env.storage().persistent().get(&key) {
+            Some(batch) => batch,
+            None => panic_with_error!(&env, BatchRewardsError::ScheduleNotFound),
+        };
+
+        if (env.ledger().sequence() as u64) < batch.execute_at {
+            panic_with_error!(&env, BatchRewardsError::ScheduleNotReady);
+        }
+
+        env.storage().persistent().remove(&key);
+        Self::distribute_rewards(env, caller, batch.token, batch.rewards)
+    }
+
+    /// Returns a scheduled reward batch that has not yet been executed.
+    pub fn get_scheduled_batch(env: Env, scheduled_id: u64) -> Option<ScheduledRewardBatch> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::ScheduledBatch(scheduled_id))
+    }
+
+    /// Distributes rewards where each entry names its own token.
+    ///
+    /// Required balances are aggregated per token and checked up front; a
+    /// shortfall in any token rejects the whole batch. Each reward goes through
+    /// the same checks and reward fee as `distribute_rewards`, with the fee
+    /// paid in the reward's own token.
+    pub fn distribute_multi_token_rewards(
+        env: Env,
+        caller: Address,
+        rewards: Vec<MultiTokenReward>,
+    ) -> BatchRewardResult {
+        caller.require_auth();
+        Self::require_admin(&env, &caller);
+        Self::require_not_paused(&env);
+
+        let request_count = rewards.len();
+        if request_count == 0 {
+            panic_with_error!(&env, BatchRewardsError::EmptyBatch);
+        }
+        if request_count > Self::get_max_reward_batch_size(env.clone()) {
+            panic_with_error!(&env, BatchRewardsError::BatchTooLarge);
+        }
+
+        // Aggregate the required amount per token and verify sufficiency
+        let mut required: Map<Address, i128> = Map::new(&env);
+        for reward in rewards.iter() {
+            let sum = required.get(reward.token.clone()).unwrap_or(0);
+            let sum = match sum.checked_add(reward.amount) {
+                Some(sum) => sum,
+                None => panic_with_error!(&env, BatchRewardsError::ArithmeticOverflow),
+            };
+            required.set(reward.token.clone(), sum);
+        }
+        for (token, amount) in required.iter() {
+            if token::Client::new(&env, &token).balance(&caller) < amount {
+                panic_with_error!(&env, BatchRewardsError::InsufficientBalance);
+            }
+        }
+
+        let batch_id: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::TotalBatches)
+            .unwrap_or(0)
+            + 1;
+
+        RewardEvents::batch_started(&env, batch_id, request_count);
+
+        let mut results: Vec<RewardResult> = Vec::new(&env);
+        let mut successful_count: u32 = 0;
+        let mut failed_count: u32 = 0;
+        let mut total_distributed: i128 = 0;
+
+        let collector: Option<Address> = env.storage().instance().get(&DataKey::FeeCollector);
+        let mut total_fees: i128 = 0;
+
+        for reward in rewards.iter() {
+            let fee = Self::reward_fee(&env, reward.amount);
+            let net_amount = reward.amount - fee;
+            let token_client = token::Client::new(&env, &reward.token);
+
+            let outcome = match Self::check_reward(&env, &reward.recipient, reward.amount) {
+                Err(error_code) => Err(error_code),
+                Ok(()) => {
+                    match token_client.try_transfer(&caller, &reward.recipient, &net_amount) {
+                        Ok(_) => Ok(()),
+                        Err(_) => Err(BatchRewardsError::InvalidToken as u32),
+                    }
+                }
+            };
+
+            match outcome {
+                Err(error_code) => {
+                    failed_count += 1;
+                    results.push_back(RewardResult::Failure(
+                        reward.recipient.clone(),
+                        reward.amount,
+                        error_code,
+                    ));
+                    RewardEvents::reward_failure(
+                        &env,
+                        batch_id,
+                        &reward.recipient,
+                        reward.amount,
+                        error_code,
+                    );
+                }
+                Ok(()) => {
+                    if let Some(collector) = &collector {
+                        if fee > 0 {
+                            token_client.transfer(&caller, collector, &fee);
+                            total_fees += fee;
+                        }
+                    }
+                    successful_count += 1;
+                    total_distributed += net_amount;
+                    Self::record_reward(&env, &reward.recipient, reward.amount, net_amount);
+                    results.push_back(RewardResult::Success(reward.recipient.clone(), net_amount));
+                    RewardEvents::reward_success(
+                        &env,
+                        batch_id,
+                        &reward.recipient,
+                        net_amount,
+                        &None,
+                    );
+                }
+            }
+        }
+
+        env.storage()
+            .instance()
+            .set(&DataKey::TotalBatches, &batch_id);
+        let total_processed: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::TotalRewardsProcessed)
+            .unwrap_or(0)
+            + request_count as u64;
+        env.storage()
+            .instance()
+            .set(&DataKey::TotalRewardsProcessed, &total_processed);
+
+        let fees_collected: i128 = env
+            .storage()
+            .instance()
+            .get(&DataKey::TotalFeesCollected)
+            .unwrap_or(0)
+            + total_fees;
+        env.storage()
+            .instance()
+            .set(&DataKey::TotalFeesCollected, &fees_collected);
+
+        RewardEvents::batch_completed(
+            &env,
+            batch_id,
+            successful_count,
+            failed_count,
+            total_distributed,
+        );
+
+        BatchRewardResult {
+            total_requests: request_count,
+            successful: successful_count,
+            failed: failed_count,
+            total_distributed,
+            results,
+        }
+    }
+
+    /// Checks each reward request's amount and recipient (view-only).
+    ///
+    /// Returns one flag per entry, in input order. Caps, blocklists and balances
+    /// are not considered; use `simulate_distribute_rewards` for a full projection.
+    pub fn validate_rewards(env: Env, rewards: Vec<RewardRequest>) -> Vec<bool> {
+        if rewards.len() > Self::get_max_reward_batch_size(env.clone()) {
+            panic_with_error!(&env, BatchRewardsError::BatchTooLarge);
+        }
+
+        let mut flags: Vec<bool> = Vec::new(&env);
+        for reward in rewards.iter() {
+            let valid = validate_amount(reward.amount).is_ok()
+                && validate_address(&env, &reward.recipient).is_ok();
+            flags.push_back(valid);
+        }
+        flags
+    }
+
+    /// Projects the outcome of `distribute_rewards` without transferring funds.
+    ///
+    /// Applies the same validation, caps and balance check but performs no
+    /// transfers, storage writes or event emission.
+    pub fn simulate_distribute_rewards(
+        env: Env,
+        caller: Address,
+        token: Address,
+        rewards: Vec<RewardRequest>,
+    ) -> BatchRewardResult {
+        Self::require_admin(&env, &caller);
+
+        let request_count = rewards.len();
+        if request_count == 0 {
+            panic_with_error!(&env, BatchRewardsError::EmptyBatch);
+        }
+        if request_count > Self::get_max_reward_batch_size(env.clone()) {
+            panic_with_error!(&env, BatchRewardsError::BatchTooLarge);
+        }
+
+        let available_balance = token::Client::new(&env, &token).balance(&caller);
+        let total_required = Self::total_reward_amount(&env, &rewards);
+        if available_balance < total_required {
+            panic_with_error!(&env, BatchRewardsError::InsufficientBalance);
+        }
+
+        let mut results: Vec<RewardResult> = Vec::new(&env);
+        let mut successful_count: u32 = 0;
+        let mut failed_count: u32 = 0;
+        let mut total_distributed: i128 = 0;
+        // Recipient totals including projected rewards earlier in this batch
+        let mut projected_totals: Map<Address, i128> = Map::new(&env);
+        // Daily window usage including projected rewards earlier in this batch
+        let (_, daily_window) = Self::get_daily_reward_cap(env.clone());
+        let mut projected_daily: Map<Address, i128> = Map::new(&env);
+
+        for reward in rewards.iter() {
+            let recipient_total = projected_totals
+                .get(reward.recipient.clone())
+                .unwrap_or_else(|| {
+                    Self::get_recipient_reward_total(env.clone(), reward.recipient.clone())
+                });
+            let daily_used = projected_daily
+                .get(reward.recipient.clone())
+                .unwrap_or_else(|| {
+                    Self::daily_reward_window(&env, &reward.recipient, daily_window).1
+                });
+
+            match Self::check_reward_against(
+                &env,
+                &reward.recipient,
+                reward.amount,
+                recipient_total,
+                daily_used,
+            ) {
+                Err(error_code) => {
+                    failed_count += 1;
+                    results.push_back(RewardResult::Failure(
+                        reward.recipient.clone(),
+                        reward.amount,
+                        error_code,
+                    ));
+                }
+                Ok(()) => {
+                    let net_amount = reward.amount - Self::reward_fee(&env, reward.amount);
+                    successful_count += 1;
+                    total_distributed += net_amount;
+                    projected_totals.set(reward.recipient.clone(), recipient_total + reward.amount);
+                    projected_daily.set(reward.recipient.clone(), daily_used + reward.amount);
+                    results.push_back(RewardResult::Success(reward.recipient.clone(), net_amount));
+                }
+            }
+        }
+
+        BatchRewardResult {
+            total_requests: request_count,
+            successful: successful_count,
+            failed: failed_count,
+            total_distributed,
+            results,
+        }
+    }
+
+    /// Splits a fixed `total` across recipients in proportion to their weights.
+    ///
+    /// Each recipient receives `total * weight / sum_of_weights`; the rounding
+    /// remainder goes to the final recipient so exactly `total` is distributed.
+    pub fn distribute_pool_pro_rata(
+        env: Env,
+        caller: Address,
+        token: Address,
+        total: i128,
+        recipients: Vec<(Address, u64)>,
+    ) -> BatchRewardResult {
+        if total <= 0 {
+            panic_with_error!(&env, BatchRewardsError::InvalidAmount);
+        }
+        if recipients.is_empty() {
+            panic_with_error!(&env, BatchRewardsError::EmptyBatch);
+        }
+
+        let mut weight_sum: i128 = 0;
+        for (_, weight) in recipients.iter() {
+            if weight == 0 {
+                panic_with_error!(&env, BatchRewardsError::InvalidBatch);
+            }
+            weight_sum += weight as i128;
+        }
+
+        if token::Client::new(&env, &token).balance(&caller) < total {
+            panic_with_error!(&env, BatchRewardsError::InsufficientBalance);
+        }
+
+        let last_index = recipients.len() - 1;
+        let mut assigned: i128 = 0;
+        let mut rewards: Vec<RewardRequest> = Vec::new(&env);
+        for (index, (recipient, weight)) in recipients.iter().enumerate() {
+            let amount = if index as u32 == last_index {
+                total - assigned
+            } else {
+                total
+                    .checked_mul(weight as i128)
+                    .map(|v| v / weight_sum)
+                    .unwrap_or_else(|| (total / weight_sum) * weight as i128)
+            };
+            assigned += amount;
+            rewards.push_back(RewardRequest {
+                recipient,
+                amount,
+                memo: None,
+            });
+        }
+
+        Self::distribute_rewards(env, caller, token, rewards)
+    }
+
+    /// Records claimable rewards for recipients without transferring to them.
+    ///
+    /// Rewards pass the same checks as `distribute_rewards` and count towards the
+    /// recipient's caps at allocation time. The net total is pulled from the
+    /// caller into the contract and any reward fee is paid immediately; each
+    /// recipient later withdraws their accrued balance with `claim`. Balances are
+    /// tracked per token, so different batches may allocate different tokens.
+    pub fn allocate_claimable_rewards(
+        env: Env,
+        caller: Address,
+        token: Address,
+        rewards: Vec<RewardRequest>,
+    ) -> BatchRewardResult {
+        caller.require_auth();
+        Self::require_admin(&env, &caller);
+        Self::require_not_paused(&env);
+
+        let request_count = rewards.len();
+        if request_count == 0 {
+            panic_with_error!(&env, BatchRewardsError::EmptyBatch);
+        }
+        if request_count > Self::get_max_reward_batch_size(env.clone()) {
+            panic_with_error!(&env, BatchRewardsError::BatchTooLarge);
+        }
+
+        let batch_id: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::TotalBatches)
+            .unwrap_or(0)
+            + 1;
+
+        RewardEvents::batch_started(&env, batch_id, request_count);
+
+        let mut results: Vec<RewardResult> = Vec::new(&env);
+        let mut successful_count: u32 = 0;
+        let mut failed_count: u32 = 0;
+        let mut total_allocated: i128 = 0;
+        let mut total_fees: i128 = 0;
+
+        for (index, reward) in rewards.iter().enumerate() {
+            let key = DataKey::Claimable(reward.recipient.clone(), token.clone());
+            let claimable: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+            let checked =
+                Self::check_reward(&env, &reward.recipient, reward.amount).and_then(|_| {
+                    let net_amount = reward.amount - Self::reward_fee(&env, reward.amount);
+                    claimable
+                        .checked_add(net_amount)
+                        .map(|new_claimable| (net_amount, new_claimable))
+                        .ok_or(BatchRewardsError::InvalidAmount as u32)
+                });
+            let (net_amount, new_claimable) = match checked {
+                Ok(amounts) => amounts,
+                Err(error_code) => {
+                    failed_count += 1;
+                    results.push_back(RewardResult::Failure(
+                        reward.recipient.clone(),
+                        reward.amount,
+                        error_code,
+                    ));
+                    RewardEvents::reward_failure(
+                        &env,
+                        batch_id,
+                        &reward.recipient,
+                        reward.amount,
+                        error_code,
+                    );
+                    continue;
+                }
+            };
+
+            env.storage().persistent().set(&key, &new_claimable);
+            Self::record_reward(&env, &reward.recipient, reward.amount, net_amount);
+            if let Some(memo) = &reward.memo {
+                env.storage()
+                    .persistent()
+                    .set(&DataKey::RewardMemo(batch_id, index as u32), memo);
+            }
+
+            successful_count += 1;
+            total_allocated = match total_allocated.checked_add(net_amount) {
+                Some(total) => total,
+                None => panic_with_error!(&env, BatchRewardsError::ArithmeticOverflow),
+            };
+            total_fees = match total_fees.checked_add(reward.amount - net_amount) {
+                Some(total) => total,
+                None => panic_with_error!(&env, BatchRewardsError::ArithmeticOverflow),
+            };
+            results.push_back(RewardResult::Success(reward.recipient.clone(), net_amount));
+            RewardEvents::reward_success(
+                &env,
+                batch_id,
+                &reward.recipient,
+                net_amount,
+                &reward.memo,
+            );
+        }
+
+        let token_client = token::Client::new(&env, &token);
+        let total_required = match total_allocated.checked_add(total_fees) {
+            Some(total) => total,
+            None => panic_with_error!(&env, BatchRewardsError::ArithmeticOverflow),
+        };
+        if token_client.balance(&caller) < total_required {
+            panic_with_error!(&env, BatchRewardsError::InsufficientBalance);
+        }
+        if total_allocated > 0 {
+            token_client.transfer(&caller, &env.current_contract_address(), &total_allocated);
+        }
+        if total_fees > 0 {
+            // A fee is only charged while a fee collector is configured
+            let collector: Address = env
+                .storage()
+                .instance()
+                .get(&DataKey::FeeCollector)
+                .unwrap();
+            token_client.transfer(&caller, &collector, &total_fees);
+
+            let fees_collected: i128 = env
+                .storage()
+                .instance()
+                .get(&DataKey::TotalFeesCollected)
+                .unwrap_or(0)
+                + total_fees;
+            env.storage()
+                .instance()
+                .set(&DataKey::TotalFeesCollected, &fees_collected);
+        }
+
+        env.storage()
+            .instance()
+            .set(&DataKey::TotalBatches, &batch_id);
+        RewardEvents::batch_completed(
+            &env,
+            batch_id,
+            successful_count,
+            failed_count,
+            total_allocated,
+        );
+
+        BatchRewardResult {
+            total_requests: request_count,
+            successful: successful_count,
+            failed: failed_count,
+            total_distributed: total_allocated,
+            results,
+        }
+    }
+
+    /// Transfers a recipient's accrued claimable rewards in `token` and zeroes
+    /// that balance.
+    pub fn claim(env: Env, recipient: Address, token: Address) -> i128 {
+        recipient.require_auth();
+
+        if Self::is_blocked(env.clone(), recipient.clone()) {
+            panic_with_error!(&env, BatchRewardsError::Blocked);
+        }
+
+        let key = DataKey::Claimable(recipient.clone(), token.clone());
+        let amount: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+        if amount <= 0 {
+            panic_with_error!(&env, BatchRewardsError::InvalidAmount);
+        }
+
+        env.storage().persistent().set(&key, &0i128);
+        token::Client::new(&env, &token).transfer(
+            &env.current_contract_address(),
+            &recipient,
+            &amount,
+        );
+
+        RewardEvents::reward_claimed(&env, &recipient, &token, amount);
+        amount
+    }
+
+    /// Gets the rewards in `token` a recipient can currently claim.
+    pub fn get_claimable(env: Env, recipient: Address, token: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Claimable(recipient, token))
+            .unwrap_or(0)
+    }
+
+    /// Internal helper applying the per-reward checks shared by every payout path,
+    /// measured against the recipient's stored lifetime and daily totals.
+    fn check_reward(env: &Env, recipient: &Address, amount: i128) -> Result<(), u32> {
+        let (_, daily_window) = Self::get_daily_reward_cap(env.clone());
+        let recipient_total = Self::get_recipient_reward_total(env.clone(), recipient.clone());
+        let daily_used = Self::daily_reward_window(env, recipient, daily_window).1;
+        Self::check_reward_against(env, recipient, amount, recipient_total, daily_used)
+    }
+
+    /// Internal helper validating a reward against the block list, min/max
+    /// reward and the lifetime and daily caps, given the recipient's totals so far.
+    /// Returns the `BatchRewardsError` code of the first failed check.
+    fn check_reward_against(
+        env: &Env,
+        recipient: &Address,
+        amount: i128,
+        recipient_total: i128,
+        daily_used: i128,
+    ) -> Result<(), u32> {
+        if validate_amount(amount).is_err() {
+            return Err(BatchRewardsError::InvalidAmount as u32);
+        }
+        if validate_address(env, recipient).is_err() {
+            return Err(BatchRewardsError::InvalidBatch as u32);
+        }
+        if Self::is_blocked(env.clone(), recipient.clone()) {
+            return Err(BatchRewardsError::Blocked as u32);
+        }
+
+        let min_reward = Self::get_min_reward(env.clone());
+        if min_reward > 0 && amount < min_reward {
+            return Err(BatchRewardsError::BelowMinReward as u32);
+        }
+        let max_reward = Self::get_max_reward(env.clone());
+        if max_reward > 0 && amount > max_reward {
+            return Err(BatchRewardsError::ExceedsMaxReward as u32);
+        }
+
+        let new_total = recipient_total
+            .checked_add(amount)
+            .ok_or(BatchRewardsError::InvalidAmount as u32)?;
+        if Self::get_lifetime_cap(env.clone()).is_some_and(|cap| new_total > cap) {
+            return Err(BatchRewardsError::LifetimeCapExceeded as u32);
+        }
+
+        let (daily_cap, _) = Self::get_daily_reward_cap(env.clone());
+        if daily_cap > 0
+            && daily_used
+                .checked_add(amount)
+                .is_none_or(|used| used > daily_cap)
+        {
+            return Err(BatchRewardsError::DailyCapExceeded as u32);
+        }
+
+        Ok(())
+    }
+
+    /// Internal helper summing the requested amounts of a reward batch.
+    /// Panics with `ArithmeticOverflow` if the total does not fit in an i128.
+    fn total_reward_amount(env: &Env, rewards: &Vec<RewardRequest>) -> i128 {
+        match rewards
+            .iter()
+            .try_fold(0i128, |sum, reward| sum.checked_add(reward.amount))
+        {
+            Some(total) => total,
+            None => panic_with_error!(env, BatchRewardsError::ArithmeticOverflow),
+        }
+    }
+
+    /// Internal helper computing the fee on a reward; zero when no
+    /// fee collector is configured.
+    /// Panics with `ArithmeticOverflow` if the fee computation overflows.
+    fn reward_fee(env: &Env, amount: i128) -> i128 {
+        if Self::get_fee_collector(env.clone()).is_none() {
+            return 0;
+        }
+        match amount.checked_mul(Self::get_reward_fee_bps(env.clone()) as i128) {
+            Some(scaled) => scaled / 10_000,
+            None => panic_with_error!(env, BatchRewardsError::ArithmeticOverflow),
+        }
+    }
+
+    /// Internal helper adding a successful reward to the recipient's lifetime
+    /// total (gross), to the total rewarded to them (net of fees) and, when a
+    /// daily cap is set, to their daily window (gross).
+    fn record_reward(env: &Env, recipient: &Address, amount: i128, net_amount: i128) {
+        let total = Self::get_recipient_reward_total(env.clone(), recipient.clone());
+        env.storage().persistent().set(
+            &DataKey::RecipientRewardTotal(recipient.clone()),
+            &(total + amount),
+        );
+
+        let rewarded_to = Self::get_total_rewarded_to(env.clone(), recipient.clone());
+        env.storage().persistent().set(
+            &DataKey::TotalRewardedTo(recipient.clone()),
+            &(rewarded_to + net_amount),
+        );
+
+        let (daily_cap, daily_window) = Self::get_daily_reward_cap(env.clone());
+        if daily_cap > 0 {
+            let (window_start, daily_used) =
+                Self::daily_reward_window(env, recipient, daily_window);
+            env.storage().persistent().set(
+                &DataKey::DailyRewardUsed(recipient.clone()),
+                &(window_start, daily_used + amount),
+            );
+        }
+    }
+
+    /// Internal helper returning a recipient's current daily window as
+    /// (window start, amount used), starting a fresh window once the last elapsed.
+    fn daily_reward_window(env: &Env, recipient: &Address, window_ledgers: u64) -> (u64, i128) {
+        let current_ledger = env.ledger().sequence() as u64;
+        let stored: Option<(u64, i128)> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::DailyRewardUsed(recipient.clone()));
+        match stored {
+            Some((start, used)) if current_ledger < start.saturating_add(window_ledgers) => {
+                (start, used)
+            }
+            _ => (current_ledger, 0),
+        }
+    }
+
+    /// Internal helper rejecting distribution while the contract is paused.
+    fn require_not_paused(env: &Env) {
+        if Self::is_paused(env.clone()) {
+            panic_with_error!(env, BatchRewardsError::Paused);
+        }
+    }
+
     /// Internal helper to verify that the caller is the admin.
     fn require_admin(env: &Env, caller: &Address) {
         let admin: Address = env
@@ -2,10 +2,14 @@
 
 #![cfg(test)]
 
-use crate::{BatchRewardsContract, BatchRewardsContractClient, RewardRequest, RewardResult};
+use crate::{
+    BatchRewardsContract, BatchRewardsContractClient, DataKey, MultiTokenReward, RewardRequest,
+    RewardResult,
+};
 use soroban_sdk::{
+    symbol_short,
     testutils::{Address as _, Events as _, Ledger},
-    token, Address, Env, Vec,
+    token, Address, Env, Symbol, TryIntoVal, Vec,
 };
 
 /// Creates a test environment with the contract deployed and initialized.
@@ -38,9 +42,23 @@ fn setup_test_env() -> (
     (env, admin, token_id, token_client, client)
 }
 
+/// Returns whether any event emitted by the last invocation carries `topic`.
+fn has_event_topic(env: &Env, topic: Symbol) -> bool {
+    env.events().all().iter().any(|(_contract, topics, _data)| {
+        topics.iter().any(|t| {
+            let symbol: Option<Symbol> = t.try_into_val(env).ok();
+            symbol == Some(topic.clone())
+        })
+    })
+}
+
 /// Helper to create a reward request.
 fn create_reward_request(_env: &Env, recipient: Address, amount: i128) -> RewardRequest {
-    RewardRequest { recipient, amount }
+    RewardRequest {
+        recipient,
+        amount,
+        memo: None,
+    }
 }
 
 // Initialization Tests
@@ -58,7 +76,7 @@ fn test_initialize_contract() {
 #[test]
 #[should_panic(expected = "Contract already initialized")]
 fn test_cannot_initialize_twice() {
-    let (env, admin, _token, _token_client, client) = setup_test_env();
+    let (env, _admin, _token, _token_client, client) = setup_test_env();
 
     let new_admin = Address::generate(&env);
     client.initialize(&new_admin);
@@ -84,7 +102,7 @@ fn test_distribute_rewards_single_recipient() {
     let reward_amount: i128 = 10_000_000; // 1 XLM equivalent
 
     // Mint tokens to admin
-    token_client.mint(&admin, &(reward_amount * 2));
+    token::StellarAssetClient::new(&env, &token).mint(&admin, &(reward_amount * 2));
 
     let mut rewards: Vec<RewardRequest> = Vec::new(&env);
     rewards.push_back(create_reward_request(&env, recipient.clone(), reward_amount));
@@ -111,7 +129,7 @@ fn test_distribute_rewards_multiple_recipients() {
     let amount: i128 = 5_000_000;
 
     // Mint tokens to admin
-    token_client.mint(&admin, &(amount * 3 + 10_000_000));
+    token::StellarAssetClient::new(&env, &token).mint(&admin, &(amount * 3 + 10_000_000));
 
     let mut rewards: Vec<RewardRequest> = Vec::new(&env);
     rewards.push_back(create_reward_request(&env, recipient1.clone(), amount));
@@ -141,7 +159,7 @@ fn test_distribute_rewards_partial_failures() {
     let invalid_amount: i128 = -1_000_000; // Invalid amount
 
     // Mint tokens to admin
-    token_client.mint(&admin, &(valid_amount * 2));
+    token::StellarAssetClient::new(&env, &token).mint(&admin, &(valid_amount * 2));
 
     let mut rewards: Vec<RewardRequest> = Vec::new(&env);
     rewards.push_back(create_reward_request(&env, recipient1.clone(), valid_amount));
@@ -161,14 +179,14 @@ fn test_distribute_rewards_partial_failures() {
 
 #[test]
 fn test_distribute_rewards_accumulates_stats() {
-    let (env, admin, token, token_client, client) = setup_test_env();
+    let (env, admin, token, _token_client, client) = setup_test_env();
 
     let recipient1 = Address::generate(&env);
     let recipient2 = Address::generate(&env);
     let amount: i128 = 5_000_000;
 
     // Mint tokens to admin
-    token_client.mint(&admin, &(amount * 4 + 10_000_000));
+    token::StellarAssetClient::new(&env, &token).mint(&admin, &(amount * 4 + 10_000_000));
 
     // First batch
     let mut rewards = Vec::new(&env);
@@ -199,17 +217,18 @@ fn test_distribute_rewards_accumulates_stats() {
 
 #[test]
 fn test_distribute_rewards_large_batch() {
-    let (env, admin, token, token_client, client) = setup_test_env();
+    let (env, admin, token, _token_client, client) = setup_test_env();
 
     let amount: i128 = 1_000_000;
     let batch_size = 50u32;
 
     // Mint tokens to admin
-    token_client.mint(&admin, &(amount * batch_size as i128 + 10_000_000));
+    token::StellarAssetClient::new(&env, &token)
+        .mint(&admin, &(amount * batch_size as i128 + 10_000_000));
 
     // Create batch of rewards
     let mut rewards: Vec<RewardRequest> = Vec::new(&env);
-    for i in 0..batch_size {
+    for _i in 0..batch_size {
         let recipient = Address::generate(&env);
         rewards.push_back(create_reward_request(&env, recipient, amount));
     }
@@ -223,7 +242,7 @@ fn test_distribute_rewards_large_batch() {
 }
 
 #[test]
-#[should_panic(expected = "EmptyBatch")]
+#[should_panic(expected = "HostError: Error(Contract, #4)")]
 fn test_distribute_rewards_empty_batch() {
     let (env, admin, token, _token_client, client) = setup_test_env();
 
@@ -232,15 +251,16 @@ fn test_distribute_rewards_empty_batch() {
 }
 
 #[test]
-#[should_panic(expected = "BatchTooLarge")]
+#[should_panic(expected = "HostError: Error(Contract, #5)")]
 fn test_distribute_rewards_batch_too_large() {
-    let (env, admin, token, token_client, client) = setup_test_env();
+    let (env, admin, token, _token_client, client) = setup_test_env();
 
     let amount: i128 = 1_000_000;
     let batch_size = 101u32; // Exceeds MAX_BATCH_SIZE of 100
 
     // Mint tokens to admin
-    token_client.mint(&admin, &(amount * batch_size as i128 + 10_000_000));
+    token::StellarAssetClient::new(&env, &token)
+        .mint(&admin, &(amount * batch_size as i128 + 10_000_000));
 
     // Create oversized batch
     let mut rewards: Vec<RewardRequest> = Vec::new(&env);
@@ -253,15 +273,15 @@ fn test_distribute_rewards_batch_too_large() {
 }
 
 #[test]
-#[should_panic(expected = "InsufficientBalance")]
+#[should_panic(expected = "HostError: Error(Contract, #7)")]
 fn test_distribute_rewards_insufficient_balance() {
-    let (env, admin, token, token_client, client) = setup_test_env();
+    let (env, admin, token, _token_client, client) = setup_test_env();
 
     let recipient = Address::generate(&env);
     let amount: i128 = 10_000_000;
 
     // Mint only half of what's needed
-    token_client.mint(&admin, &(amount / 2));
+    token::StellarAssetClient::new(&env, &token).mint(&admin, &(amount / 2));
 
     let mut rewards: Vec<RewardRequest> = Vec::new(&env);
     rewards.push_back(create_reward_request(&env, recipient, amount));
@@ -270,15 +290,15 @@ fn test_distribute_rewards_insufficient_balance() {
 }
 
 #[test]
-#[should_panic(expected = "Unauthorized")]
+#[should_panic(expected = "HostError: Error(Contract, #2)")]
 fn test_distribute_rewards_unauthorized() {
-    let (env, _admin, token, token_client, client) = setup_test_env();
+    let (env, _admin, token, _token_client, client) = setup_test_env();
 
     let unauthorized_caller = Address::generate(&env);
     let recipient = Address::generate(&env);
     let amount: i128 = 10_000_000;
 
-    token_client.mint(&unauthorized_caller, &amount);
+    token::StellarAssetClient::new(&env, &token).mint(&unauthorized_caller, &amount);
 
     let mut rewards: Vec<RewardRequest> = Vec::new(&env);
     rewards.push_back(create_reward_request(&env, recipient, amount));
@@ -288,12 +308,12 @@ fn test_distribute_rewards_unauthorized() {
 
 #[test]
 fn test_distribute_rewards_events_emitted() {
-    let (env, admin, token, token_client, client) = setup_test_env();
+    let (env, admin, token, _token_client, client) = setup_test_env();
 
     let recipient = Address::generate(&env);
     let amount: i128 = 10_000_000;
 
-    token_client.mint(&admin, &(amount + 10_000_000));
+    token::StellarAssetClient::new(&env, &token).mint(&admin, &(amount + 10_000_000));
 
     let mut rewards: Vec<RewardRequest> = Vec::new(&env);
     rewards.push_back(create_reward_request(&env, recipient.clone(), amount));
@@ -302,45 +322,30 @@ fn test_distribute_rewards_events_emitted() {
 
     // Verify events were emitted
     let events = env.events().all();
-    assert!(events.len() > 0);
+    assert!(!events.is_empty());
 
     // Check for batch_started event
-    let has_batch_started = events.iter().any(|event| {
-        event
-            .topics
-            .iter()
-            .any(|topic| topic.to_string().contains("batch"))
-    });
+    let has_batch_started = has_event_topic(&env, symbol_short!("batch"));
     assert!(has_batch_started, "batch_started event not found");
 
     // Check for reward_success event
-    let has_reward_success = events.iter().any(|event| {
-        event
-            .topics
-            .iter()
-            .any(|topic| topic.to_string().contains("success"))
-    });
+    let has_reward_success = has_event_topic(&env, symbol_short!("success"));
     assert!(has_reward_success, "reward_success event not found");
 
     // Check for batch_completed event
-    let has_batch_completed = events.iter().any(|event| {
-        event
-            .topics
-            .iter()
-            .any(|topic| topic.to_string().contains("completed"))
-    });
+    let has_batch_completed = has_event_topic(&env, symbol_short!("completed"));
     assert!(has_batch_completed, "batch_completed event not found");
 }
 
 #[test]
 fn test_distribute_rewards_with_zero_amount() {
-    let (env, admin, token, token_client, client) = setup_test_env();
+    let (env, admin, token, _token_client, client) = setup_test_env();
 
     let recipient = Address::generate(&env);
     let valid_amount: i128 = 5_000_000;
     let zero_amount: i128 = 0;
 
-    token_client.mint(&admin, &(valid_amount + 10_000_000));
+    token::StellarAssetClient::new(&env, &token).mint(&admin, &(valid_amount + 10_000_000));
 
     let mut rewards: Vec<RewardRequest> = Vec::new(&env);
     rewards.push_back(create_reward_request(&env, recipient.clone(), valid_amount));
@@ -355,12 +360,12 @@ fn test_distribute_rewards_with_zero_amount() {
 
 #[test]
 fn test_distribute_rewards_events_on_failure() {
-    let (env, admin, token, token_client, client) = setup_test_env();
+    let (env, admin, token, _token_client, client) = setup_test_env();
 
     let recipient = Address::generate(&env);
     let invalid_amount: i128 = -5_000_000;
 
-    token_client.mint(&admin, &(10_000_000));
+    token::StellarAssetClient::new(&env, &token).mint(&admin, &(10_000_000));
 
     let mut rewards: Vec<RewardRequest> = Vec::new(&env);
     rewards.push_back(create_reward_request(&env, recipient.clone(), invalid_amount));
@@ -369,28 +374,21 @@ fn test_distribute_rewards_events_on_failure() {
 
     assert_eq!(result.failed, 1);
 
-    let events = env.events().all();
-    
     // Check for failure event
-    let has_failure_event = events.iter().any(|event| {
-        event
-            .topics
-            .iter()
-            .any(|topic| topic.to_string().contains("failure"))
-    });
+    let has_failure_event = has_event_topic(&env, symbol_short!("failure"));
     assert!(has_failure_event, "reward_failure event not found");
 }
 
 #[test]
 fn test_distribute_rewards_result_structure() {
-    let (env, admin, token, token_client, client) = setup_test_env();
+    let (env, admin, token, _token_client, client) = setup_test_env();
 
     let recipient1 = Address::generate(&env);
     let recipient2 = Address::generate(&env);
     let amount1: i128 = 5_000_000;
     let amount2: i128 = 3_000_000;
 
-    token_client.mint(&admin, &(amount1 + amount2 + 10_000_000));
+    token::StellarAssetClient::new(&env, &token).mint(&admin, &(amount1 + amount2 + 10_000_000));
 
     let mut rewards: Vec<RewardRequest> = Vec::new(&env);
     rewards.push_back(create_reward_request(&env, recipient1.clone(), amount1));
@@ -407,16 +405,16 @@ fn test_distribute_rewards_result_structure() {
     // Verify individual results
     match result.results.get(0).unwrap() {
         RewardResult::Success(addr, amt) => {
-            assert_eq!(*addr, recipient1);
-            assert_eq!(*amt, amount1);
+            assert_eq!(addr, recipient1);
+            assert_eq!(amt, amount1);
         }
         _ => panic!("Expected success result"),
     }
 
     match result.results.get(1).unwrap() {
         RewardResult::Success(addr, amt) => {
-            assert_eq!(*addr, recipient2);
-            assert_eq!(*amt, amount2);
+            assert_eq!(addr, recipient2);
+            assert_eq!(amt, amount2);
         }
         _ => panic!("Expected success result"),
     }
@@ -426,14 +424,15 @@ fn test_distribute_rewards_result_structure() {
 fn test_multiple_simultaneous_batch_distributions() {
     let (env, admin, token, token_client, client) = setup_test_env();
 
-    let recipients: Vec<Address> = (0..10)
-        .map(|_| Address::generate(&env))
-        .collect::<Vec<_>>();
+    let mut recipients: Vec<Address> = Vec::new(&env);
+    for _ in 0..10 {
+        recipients.push_back(Address::generate(&env));
+    }
 
     let amount: i128 = 2_000_000;
     
     // Mint sufficient tokens
-    token_client.mint(&admin, &(amount * 30 + 10_000_000));
+    token::StellarAssetClient::new(&env, &token).mint(&admin, &(amount * 30 + 10_000_000));
 
     // Execute 3 batches
     for _batch in 0..3 {
@@ -454,6 +453,1117 @@ fn test_multiple_simultaneous_batch_distributions() {
 
     // Verify each recipient received tokens
     for recipient in recipients.iter() {
-        assert_eq!(token_client.balance(recipient), amount * 3);
+        assert_eq!(token_client.balance(&recipient), amount * 3);
+    }
+}
+
+// Lifetime Cap Tests
+
+#[test]
+fn test_lifetime_cap_enforced_across_batches() {
+    let (env, admin, token, token_client, client) = setup_test_env();
+    token::StellarAssetClient::new(&env, &token).mint(&admin, &100_000);
+
+    let recipient = Address::generate(&env);
+    client.set_lifetime_cap(&admin, &25_000);
+    assert_eq!(client.get_lifetime_cap(), Some(25_000));
+
+    let mut rewards: Vec<RewardRequest> = Vec::new(&env);
+    rewards.push_back(create_reward_request(&env, recipient.clone(), 10_000));
+
+    // Two batches fit under the cap
+    client.distribute_rewards(&admin, &token, &rewards);
+    client.distribute_rewards(&admin, &token, &rewards);
+    assert_eq!(client.get_recipient_reward_total(&recipient), 20_000);
+
+    // The third would reach 30_000 and is rejected
+    let result = client.distribute_rewards(&admin, &token, &rewards);
+    assert_eq!(result.successful, 0);
+    assert_eq!(result.failed, 1);
+    match result.results.get(0).unwrap() {
+        RewardResult::Failure(addr, amount, code) => {
+            assert_eq!(addr, recipient);
+            assert_eq!(amount, 10_000);
+            assert_eq!(code, 9);
+        }
+        _ => panic!("Expected failure"),
+    }
+
+    assert_eq!(client.get_recipient_reward_total(&recipient), 20_000);
+    assert_eq!(token_client.balance(&recipient), 20_000);
+}
+
+#[test]
+fn test_lifetime_cap_allows_exact_cap() {
+    let (env, admin, token, _token_client, client) = setup_test_env();
+    token::StellarAssetClient::new(&env, &token).mint(&admin, &100_000);
+
+    let recipient = Address::generate(&env);
+    client.set_lifetime_cap(&admin, &15_000);
+
+    let mut rewards: Vec<RewardRequest> = Vec::new(&env);
+    rewards.push_back(create_reward_request(&env, recipient.clone(), 10_000));
+    rewards.push_back(create_reward_request(&env, recipient.clone(), 5_000));
+    rewards.push_back(create_reward_request(&env, recipient.clone(), 1));
+
+    let result = client.distribute_rewards(&admin, &token, &rewards);
+
+    assert_eq!(result.successful, 2);
+    assert_eq!(result.failed, 1);
+    assert_eq!(client.get_recipient_reward_total(&recipient), 15_000);
+}
+
+#[test]
+fn test_lifetime_total_overflow_recorded_as_failure() {
+    let (env, admin, token, token_client, client) = setup_test_env();
+    token::StellarAssetClient::new(&env, &token).mint(&admin, &100_000);
+
+    let recipient = Address::generate(&env);
+    env.as_contract(&client.address, || {
+        env.storage().persistent().set(
+            &DataKey::RecipientRewardTotal(recipient.clone()),
+            &(i128::MAX - 10),
+        );
+    });
+
+    let mut rewards: Vec<RewardRequest> = Vec::new(&env);
+    rewards.push_back(create_reward_request(&env, recipient.clone(), 100));
+
+    let result = client.distribute_rewards(&admin, &token, &rewards);
+
+    assert_eq!(result.failed, 1);
+    match result.results.get(0).unwrap() {
+        RewardResult::Failure(_, _, code) => assert_eq!(code, 8), // InvalidAmount
+        _ => panic!("Expected overflowing total to fail"),
+    }
+    assert_eq!(token_client.balance(&recipient), 0);
+    assert_eq!(
+        client.get_recipient_reward_total(&recipient),
+        i128::MAX - 10
+    );
+}
+
+// Reward Fee Tests
+
+#[test]
+fn test_reward_fee_routed_to_collector() {
+    let (env, admin, token, token_client, client) = setup_test_env();
+    token::StellarAssetClient::new(&env, &token).mint(&admin, &100_000);
+
+    let collector = Address::generate(&env);
+    client.set_reward_fee(&admin, &250, &collector); // 2.5%
+    assert_eq!(client.get_reward_fee_bps(), 250);
+    assert_eq!(client.get_fee_collector(), Some(collector.clone()));
+
+    let recipient1 = Address::generate(&env);
+    let recipient2 = Address::generate(&env);
+    let mut rewards: Vec<RewardRequest> = Vec::new(&env);
+    rewards.push_back(create_reward_request(&env, recipient1.clone(), 10_000));
+    rewards.push_back(create_reward_request(&env, recipient2.clone(), 20_000));
+
+    let result = client.distribute_rewards(&admin, &token, &rewards);
+
+    assert_eq!(result.successful, 2);
+    assert_eq!(token_client.balance(&recipient1), 9_750);
+    assert_eq!(token_client.balance(&recipient2), 19_500);
+    assert_eq!(token_client.balance(&collector), 750);
+    assert_eq!(token_client.balance(&admin), 70_000);
+    assert_eq!(client.get_total_fees_collected(), 750);
+
+    // Results report what recipients actually received
+    assert_eq!(result.total_distributed, 29_250);
+    assert_eq!(client.get_total_volume_distributed(), 29_250);
+    match result.results.get(0).unwrap() {
+        RewardResult::Success(_, amount) => assert_eq!(amount, 9_750),
+        _ => panic!("Expected success"),
+    }
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #19)")]
+fn test_reward_fee_overflow_rejected() {
+    let (env, admin, token, _token_client, client) = setup_test_env();
+    let amount = i128::MAX / 4;
+    token::StellarAssetClient::new(&env, &token).mint(&admin, &amount);
+    client.set_reward_fee(&admin, &250, &Address::generate(&env));
+
+    let mut rewards: Vec<RewardRequest> = Vec::new(&env);
+    rewards.push_back(create_reward_request(&env, Address::generate(&env), amount));
+
+    client.distribute_rewards(&admin, &token, &rewards);
+}
+
+#[test]
+fn test_simulation_reports_net_of_fee() {
+    let (env, admin, token, _token_client, client) = setup_test_env();
+    token::StellarAssetClient::new(&env, &token).mint(&admin, &100_000);
+    client.set_reward_fee(&admin, &1_000, &Address::generate(&env)); // 10%
+
+    let mut rewards: Vec<RewardRequest> = Vec::new(&env);
+    rewards.push_back(create_reward_request(&env, Address::generate(&env), 10_000));
+
+    let simulated = client.simulate_distribute_rewards(&admin, &token, &rewards);
+    let actual = client.distribute_rewards(&admin, &token, &rewards);
+
+    assert_eq!(simulated.total_distributed, 9_000);
+    assert_eq!(actual.total_distributed, 9_000);
+}
+
+#[test]
+fn test_reward_fee_defaults_to_zero() {
+    let (env, admin, token, token_client, client) = setup_test_env();
+    token::StellarAssetClient::new(&env, &token).mint(&admin, &100_000);
+
+    let recipient = Address::generate(&env);
+    let mut rewards: Vec<RewardRequest> = Vec::new(&env);
+    rewards.push_back(create_reward_request(&env, recipient.clone(), 10_000));
+
+    let result = client.distribute_rewards(&admin, &token, &rewards);
+
+    assert_eq!(client.get_reward_fee_bps(), 0);
+    assert_eq!(token_client.balance(&recipient), 10_000);
+    assert_eq!(result.total_distributed, 10_000);
+    assert_eq!(client.get_total_fees_collected(), 0);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #10)")]
+fn test_set_reward_fee_rejects_above_10000_bps() {
+    let (env, admin, _token, _token_client, client) = setup_test_env();
+
+    client.set_reward_fee(&admin, &10_001, &Address::generate(&env));
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #19)")]
+fn test_distribute_rewards_rejects_overflowing_total() {
+    let (env, admin, token, _token_client, client) = setup_test_env();
+
+    let mut rewards: Vec<RewardRequest> = Vec::new(&env);
+    rewards.push_back(create_reward_request(
+        &env,
+        Address::generate(&env),
+        i128::MAX,
+    ));
+    rewards.push_back(create_reward_request(&env, Address::generate(&env), 1));
+
+    client.distribute_rewards(&admin, &token, &rewards);
+}
+
+// Claimable Reward Tests
+
+#[test]
+fn test_claimable_rewards_claimed_by_some_recipients() {
+    let (env, admin, token, token_client, client) = setup_test_env();
+    token::StellarAssetClient::new(&env, &token).mint(&admin, &100_000);
+
+    let recipient1 = Address::generate(&env);
+    let recipient2 = Address::generate(&env);
+    let recipient3 = Address::generate(&env);
+    let mut rewards: Vec<RewardRequest> = Vec::new(&env);
+    rewards.push_back(create_reward_request(&env, recipient1.clone(), 10_000));
+    rewards.push_back(create_reward_request(&env, recipient2.clone(), 20_000));
+    rewards.push_back(create_reward_request(&env, recipient3.clone(), 30_000));
+
+    let result = client.allocate_claimable_rewards(&admin, &token, &rewards);
+    assert_eq!(result.successful, 3);
+    assert_eq!(result.total_distributed, 60_000);
+    assert_eq!(token_client.balance(&admin), 40_000);
+    assert_eq!(token_client.balance(&recipient1), 0);
+
+    assert_eq!(client.claim(&recipient1, &token), 10_000);
+    assert_eq!(client.claim(&recipient3, &token), 30_000);
+
+    assert_eq!(token_client.balance(&recipient1), 10_000);
+    assert_eq!(token_client.balance(&recipient2), 0);
+    assert_eq!(token_client.balance(&recipient3), 30_000);
+    assert_eq!(client.get_claimable(&recipient1, &token), 0);
+    assert_eq!(client.get_claimable(&recipient2, &token), 20_000);
+    assert_eq!(client.get_claimable(&recipient3, &token), 0);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #8)")]
+fn test_claim_with_nothing_accrued_fails() {
+    let (env, _admin, token, _token_client, client) = setup_test_env();
+
+    client.claim(&Address::generate(&env), &token);
+}
+
+#[test]
+fn test_claimable_rewards_apply_checks_and_fee() {
+    let (env, admin, token, token_client, client) = setup_test_env();
+    token::StellarAssetClient::new(&env, &token).mint(&admin, &100_000);
+
+    let collector = Address::generate(&env);
+    client.set_reward_fee(&admin, &1_000, &collector); // 10%
+    client.set_max_reward(&admin, &20_000);
+
+    let blocked = Address::generate(&env);
+    let oversized = Address::generate(&env);
+    let allowed = Address::generate(&env);
+    client.block_address(&admin, &blocked);
+
+    let mut rewards: Vec<RewardRequest> = Vec::new(&env);
+    rewards.push_back(create_reward_request(&env, blocked.clone(), 5_000));
+    rewards.push_back(create_reward_request(&env, oversized.clone(), 30_000));
+    rewards.push_back(create_reward_request(&env, allowed.clone(), 10_000));
+
+    let result = client.allocate_claimable_rewards(&admin, &token, &rewards);
+
+    assert_eq!(result.successful, 1);
+    assert_eq!(result.failed, 2);
+    match result.results.get(0).unwrap() {
+        RewardResult::Failure(_, _, code) => assert_eq!(code, 14),
+        _ => panic!("Expected failure for blocked recipient"),
+    }
+    match result.results.get(1).unwrap() {
+        RewardResult::Failure(_, _, code) => assert_eq!(code, 11),
+        _ => panic!("Expected failure for oversized reward"),
+    }
+    assert_eq!(result.total_distributed, 9_000);
+    assert_eq!(client.get_claimable(&blocked, &token), 0);
+    assert_eq!(client.get_claimable(&allowed, &token), 9_000);
+    assert_eq!(client.get_recipient_reward_total(&allowed), 10_000);
+    assert_eq!(token_client.balance(&collector), 1_000);
+    assert_eq!(token_client.balance(&admin), 90_000);
+    assert_eq!(client.get_total_fees_collected(), 1_000);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #14)")]
+fn test_claim_rejected_for_blocked_recipient() {
+    let (env, admin, token, _token_client, client) = setup_test_env();
+    token::StellarAssetClient::new(&env, &token).mint(&admin, &100_000);
+
+    let recipient = Address::generate(&env);
+    let mut rewards: Vec<RewardRequest> = Vec::new(&env);
+    rewards.push_back(create_reward_request(&env, recipient.clone(), 10_000));
+    client.allocate_claimable_rewards(&admin, &token, &rewards);
+
+    client.block_address(&admin, &recipient);
+    client.claim(&recipient, &token);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #5)")]
+fn test_claimable_rewards_respect_max_batch_size() {
+    let (env, admin, token, _token_client, client) = setup_test_env();
+    token::StellarAssetClient::new(&env, &token).mint(&admin, &100_000);
+
+    client.set_max_reward_batch_size(&admin, &2);
+
+    let mut rewards: Vec<RewardRequest> = Vec::new(&env);
+    for _ in 0..3 {
+        rewards.push_back(create_reward_request(&env, Address::generate(&env), 1_000));
+    }
+
+    client.allocate_claimable_rewards(&admin, &token, &rewards);
+}
+
+#[test]
+fn test_claimable_rewards_emit_events_and_count_batches() {
+    let (env, admin, token, _token_client, client) = setup_test_env();
+    token::StellarAssetClient::new(&env, &token).mint(&admin, &100_000);
+
+    let blocked = Address::generate(&env);
+    client.block_address(&admin, &blocked);
+    let mut rewards: Vec<RewardRequest> = Vec::new(&env);
+    rewards.push_back(create_reward_request(&env, Address::generate(&env), 10_000));
+    rewards.push_back(create_reward_request(&env, blocked, 5_000));
+
+    client.allocate_claimable_rewards(&admin, &token, &rewards);
+
+    // Events only cover the latest invocation, so read them before any other call
+    assert!(has_event_topic(&env, symbol_short!("started")));
+    assert!(has_event_topic(&env, symbol_short!("success")));
+    assert!(has_event_topic(&env, symbol_short!("failure")));
+    assert!(has_event_topic(&env, symbol_short!("completed")));
+    assert_eq!(client.get_total_batches(), 1);
+}
+
+#[test]
+fn test_claimable_rewards_tracked_per_token() {
+    let (env, admin, token_a, token_a_client, client) = setup_test_env();
+    let token_b = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    let token_b_client = token::Client::new(&env, &token_b);
+    token::StellarAssetClient::new(&env, &token_a).mint(&admin, &50_000);
+    token::StellarAssetClient::new(&env, &token_b).mint(&admin, &50_000);
+
+    let recipient = Address::generate(&env);
+    let mut rewards: Vec<RewardRequest> = Vec::new(&env);
+    rewards.push_back(create_reward_request(&env, recipient.clone(), 10_000));
+    client.allocate_claimable_rewards(&admin, &token_a, &rewards);
+
+    let mut rewards: Vec<RewardRequest> = Vec::new(&env);
+    rewards.push_back(create_reward_request(&env, recipient.clone(), 4_000));
+    client.allocate_claimable_rewards(&admin, &token_b, &rewards);
+
+    assert_eq!(client.get_claimable(&recipient, &token_a), 10_000);
+    assert_eq!(client.get_claimable(&recipient, &token_b), 4_000);
+
+    assert_eq!(client.claim(&recipient, &token_b), 4_000);
+    assert_eq!(token_b_client.balance(&recipient), 4_000);
+    assert_eq!(token_a_client.balance(&recipient), 0);
+    assert_eq!(client.get_claimable(&recipient, &token_a), 10_000);
+}
+
+#[test]
+fn test_claimable_overflow_recorded_as_failure() {
+    let (env, admin, token, _token_client, client) = setup_test_env();
+    token::StellarAssetClient::new(&env, &token).mint(&admin, &100_000);
+
+    let recipient = Address::generate(&env);
+    env.as_contract(&client.address, || {
+        env.storage().persistent().set(
+            &DataKey::Claimable(recipient.clone(), token.clone()),
+            &(i128::MAX - 10),
+        );
+    });
+
+    let mut rewards: Vec<RewardRequest> = Vec::new(&env);
+    rewards.push_back(create_reward_request(&env, recipient.clone(), 100));
+
+    let result = client.allocate_claimable_rewards(&admin, &token, &rewards);
+
+    assert_eq!(result.failed, 1);
+    match result.results.get(0).unwrap() {
+        RewardResult::Failure(_, _, code) => assert_eq!(code, 8), // InvalidAmount
+        _ => panic!("Expected overflowing claimable balance to fail"),
+    }
+    assert_eq!(client.get_claimable(&recipient, &token), i128::MAX - 10);
+    assert_eq!(client.get_recipient_reward_total(&recipient), 0);
+}
+
+// Max Reward Tests
+
+#[test]
+fn test_oversized_reward_fails_while_others_succeed() {
+    let (env, admin, token, token_client, client) = setup_test_env();
+    token::StellarAssetClient::new(&env, &token).mint(&admin, &100_000);
+    client.set_max_reward(&admin, &20_000);
+    assert_eq!(client.get_max_reward(), 20_000);
+
+    let recipient1 = Address::generate(&env);
+    let recipient2 = Address::generate(&env);
+    let recipient3 = Address::generate(&env);
+    let mut rewards: Vec<RewardRequest> = Vec::new(&env);
+    rewards.push_back(create_reward_request(&env, recipient1.clone(), 10_000));
+    rewards.push_back(create_reward_request(&env, recipient2.clone(), 50_000));
+    rewards.push_back(create_reward_request(&env, recipient3.clone(), 20_000));
+
+    let result = client.distribute_rewards(&admin, &token, &rewards);
+
+    assert_eq!(result.successful, 2);
+    assert_eq!(result.failed, 1);
+    assert_eq!(result.total_distributed, 30_000);
+    match result.results.get(1).unwrap() {
+        RewardResult::Failure(_, amount, code) => {
+            assert_eq!(amount, 50_000);
+            assert_eq!(code, 11);
+        }
+        _ => panic!("Expected failure for oversized reward"),
     }
+    assert_eq!(token_client.balance(&recipient2), 0);
+    assert_eq!(token_client.balance(&recipient3), 20_000);
+}
+
+#[test]
+fn test_zero_max_reward_means_no_cap() {
+    let (env, admin, token, token_client, client) = setup_test_env();
+    token::StellarAssetClient::new(&env, &token).mint(&admin, &100_000);
+    client.set_max_reward(&admin, &0);
+
+    let recipient = Address::generate(&env);
+    let mut rewards: Vec<RewardRequest> = Vec::new(&env);
+    rewards.push_back(create_reward_request(&env, recipient.clone(), 90_000));
+
+    let result = client.distribute_rewards(&admin, &token, &rewards);
+
+    assert_eq!(result.successful, 1);
+    assert_eq!(token_client.balance(&recipient), 90_000);
+}
+
+// Per-Recipient Total Tests
+
+#[test]
+fn test_total_rewarded_to_sums_successful_rewards_across_batches() {
+    let (env, admin, token, _token_client, client) = setup_test_env();
+    token::StellarAssetClient::new(&env, &token).mint(&admin, &100_000);
+
+    let recipient = Address::generate(&env);
+    let mut first: Vec<RewardRequest> = Vec::new(&env);
+    first.push_back(create_reward_request(&env, recipient.clone(), 10_000));
+    first.push_back(create_reward_request(&env, recipient.clone(), 0));
+    client.distribute_rewards(&admin, &token, &first);
+
+    let mut second: Vec<RewardRequest> = Vec::new(&env);
+    second.push_back(create_reward_request(&env, recipient.clone(), 25_000));
+    client.distribute_rewards(&admin, &token, &second);
+
+    assert_eq!(client.get_total_rewarded_to(&recipient), 35_000);
+    assert_eq!(client.get_total_rewarded_to(&Address::generate(&env)), 0);
+}
+
+// Pro-Rata Distribution Tests
+
+#[test]
+fn test_pro_rata_distributes_exact_total() {
+    let (env, admin, token, token_client, client) = setup_test_env();
+    token::StellarAssetClient::new(&env, &token).mint(&admin, &100_000);
+
+    let recipient1 = Address::generate(&env);
+    let recipient2 = Address::generate(&env);
+    let recipient3 = Address::generate(&env);
+    let mut recipients: Vec<(Address, u64)> = Vec::new(&env);
+    recipients.push_back((recipient1.clone(), 1));
+    recipients.push_back((recipient2.clone(), 1));
+    recipients.push_back((recipient3.clone(), 1));
+
+    let result = client.distribute_pool_pro_rata(&admin, &token, &10_000, &recipients);
+
+    assert_eq!(result.successful, 3);
+    assert_eq!(result.total_distributed, 10_000);
+    assert_eq!(token_client.balance(&recipient1), 3_333);
+    assert_eq!(token_client.balance(&recipient2), 3_333);
+    assert_eq!(token_client.balance(&recipient3), 3_334);
+    assert_eq!(token_client.balance(&admin), 90_000);
+}
+
+#[test]
+fn test_pro_rata_respects_weights() {
+    let (env, admin, token, token_client, client) = setup_test_env();
+    token::StellarAssetClient::new(&env, &token).mint(&admin, &100_000);
+
+    let recipient1 = Address::generate(&env);
+    let recipient2 = Address::generate(&env);
+    let mut recipients: Vec<(Address, u64)> = Vec::new(&env);
+    recipients.push_back((recipient1.clone(), 3));
+    recipients.push_back((recipient2.clone(), 1));
+
+    client.distribute_pool_pro_rata(&admin, &token, &40_000, &recipients);
+
+    assert_eq!(token_client.balance(&recipient1), 30_000);
+    assert_eq!(token_client.balance(&recipient2), 10_000);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #8)")]
+fn test_pro_rata_rejects_zero_total() {
+    let (env, admin, token, _token_client, client) = setup_test_env();
+
+    let mut recipients: Vec<(Address, u64)> = Vec::new(&env);
+    recipients.push_back((Address::generate(&env), 1));
+
+    client.distribute_pool_pro_rata(&admin, &token, &0, &recipients);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #4)")]
+fn test_pro_rata_rejects_empty_recipients() {
+    let (env, admin, token, _token_client, client) = setup_test_env();
+
+    let recipients: Vec<(Address, u64)> = Vec::new(&env);
+    client.distribute_pool_pro_rata(&admin, &token, &10_000, &recipients);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #3)")]
+fn test_pro_rata_rejects_zero_weight() {
+    let (env, admin, token, _token_client, client) = setup_test_env();
+    token::StellarAssetClient::new(&env, &token).mint(&admin, &100_000);
+
+    let mut recipients: Vec<(Address, u64)> = Vec::new(&env);
+    recipients.push_back((Address::generate(&env), 1));
+    recipients.push_back((Address::generate(&env), 0));
+
+    client.distribute_pool_pro_rata(&admin, &token, &10_000, &recipients);
+}
+
+// Simulation Tests
+
+#[test]
+fn test_simulation_matches_real_distribution() {
+    let (env, admin, token, token_client, client) = setup_test_env();
+    token::StellarAssetClient::new(&env, &token).mint(&admin, &100_000);
+    client.set_max_reward(&admin, &40_000);
+
+    let recipient1 = Address::generate(&env);
+    let recipient2 = Address::generate(&env);
+    let mut rewards: Vec<RewardRequest> = Vec::new(&env);
+    rewards.push_back(create_reward_request(&env, recipient1.clone(), 10_000));
+    rewards.push_back(create_reward_request(&env, recipient2.clone(), 0));
+    rewards.push_back(create_reward_request(&env, recipient2.clone(), 50_000));
+
+    let simulated = client.simulate_distribute_rewards(&admin, &token, &rewards);
+
+    // Simulation moves no funds and records nothing
+    assert_eq!(token_client.balance(&admin), 100_000);
+    assert_eq!(client.get_total_batches(), 0);
+
+    let actual = client.distribute_rewards(&admin, &token, &rewards);
+
+    assert_eq!(simulated.successful, actual.successful);
+    assert_eq!(simulated.failed, actual.failed);
+    assert_eq!(simulated.total_distributed, actual.total_distributed);
+    for i in 0..actual.results.len() {
+        match (
+            simulated.results.get(i).unwrap(),
+            actual.results.get(i).unwrap(),
+        ) {
+            (RewardResult::Success(_, a), RewardResult::Success(_, b)) => assert_eq!(a, b),
+            (RewardResult::Failure(_, _, a), RewardResult::Failure(_, _, b)) => assert_eq!(a, b),
+            _ => panic!("Simulation diverged from distribution at index {}", i),
+        }
+    }
+}
+
+#[test]
+fn test_simulation_rejects_insufficient_balance_like_distribution() {
+    let (env, admin, token, _token_client, client) = setup_test_env();
+    token::StellarAssetClient::new(&env, &token).mint(&admin, &5_000);
+
+    let mut rewards: Vec<RewardRequest> = Vec::new(&env);
+    rewards.push_back(create_reward_request(&env, Address::generate(&env), 10_000));
+
+    let simulated = client.try_simulate_distribute_rewards(&admin, &token, &rewards);
+    let actual = client.try_distribute_rewards(&admin, &token, &rewards);
+
+    assert!(simulated.is_err());
+    assert!(actual.is_err());
+    assert_eq!(simulated.err(), actual.err());
+}
+
+// Multi-Token Tests
+
+#[test]
+fn test_distribute_multi_token_rewards() {
+    let (env, admin, token_a, token_a_client, client) = setup_test_env();
+    let token_b = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    let token_b_client = token::Client::new(&env, &token_b);
+    token::StellarAssetClient::new(&env, &token_a).mint(&admin, &50_000);
+    token::StellarAssetClient::new(&env, &token_b).mint(&admin, &50_000);
+
+    let recipient1 = Address::generate(&env);
+    let recipient2 = Address::generate(&env);
+    let mut rewards: Vec<MultiTokenReward> = Vec::new(&env);
+    rewards.push_back(MultiTokenReward {
+        recipient: recipient1.clone(),
+        amount: 10_000,
+        token: token_a.clone(),
+    });
+    rewards.push_back(MultiTokenReward {
+        recipient: recipient1.clone(),
+        amount: 5_000,
+        token: token_b.clone(),
+    });
+    rewards.push_back(MultiTokenReward {
+        recipient: recipient2.clone(),
+        amount: 20_000,
+        token: token_b.clone(),
+    });
+
+    let result = client.distribute_multi_token_rewards(&admin, &rewards);
+
+    assert_eq!(result.successful, 3);
+    assert_eq!(token_a_client.balance(&recipient1), 10_000);
+    assert_eq!(token_b_client.balance(&recipient1), 5_000);
+    assert_eq!(token_a_client.balance(&recipient2), 0);
+    assert_eq!(token_b_client.balance(&recipient2), 20_000);
+    assert_eq!(token_a_client.balance(&admin), 40_000);
+    assert_eq!(token_b_client.balance(&admin), 25_000);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #7)")]
+fn test_multi_token_rewards_checks_balance_per_token() {
+    let (env, admin, token_a, _token_a_client, client) = setup_test_env();
+    let token_b = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    token::StellarAssetClient::new(&env, &token_a).mint(&admin, &50_000);
+    token::StellarAssetClient::new(&env, &token_b).mint(&admin, &1_000);
+
+    let mut rewards: Vec<MultiTokenReward> = Vec::new(&env);
+    rewards.push_back(MultiTokenReward {
+        recipient: Address::generate(&env),
+        amount: 10_000,
+        token: token_a,
+    });
+    rewards.push_back(MultiTokenReward {
+        recipient: Address::generate(&env),
+        amount: 5_000,
+        token: token_b,
+    });
+
+    client.distribute_multi_token_rewards(&admin, &rewards);
+}
+
+#[test]
+fn test_multi_token_rewards_apply_checks_and_fee() {
+    let (env, admin, token_a, token_a_client, client) = setup_test_env();
+    let token_b = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    let token_b_client = token::Client::new(&env, &token_b);
+    token::StellarAssetClient::new(&env, &token_a).mint(&admin, &50_000);
+    token::StellarAssetClient::new(&env, &token_b).mint(&admin, &50_000);
+
+    let collector = Address::generate(&env);
+    client.set_reward_fee(&admin, &1_000, &collector); // 10%
+    client.set_lifetime_cap(&admin, &15_000);
+
+    let blocked = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    client.block_address(&admin, &blocked);
+
+    let mut rewards: Vec<MultiTokenReward> = Vec::new(&env);
+    rewards.push_back(MultiTokenReward {
+        recipient: blocked.clone(),
+        amount: 5_000,
+        token: token_a.clone(),
+    });
+    rewards.push_back(MultiTokenReward {
+        recipient: recipient.clone(),
+        amount: 10_000,
+        token: token_a.clone(),
+    });
+    // Pushes the recipient past the lifetime cap shared across tokens
+    rewards.push_back(MultiTokenReward {
+        recipient: recipient.clone(),
+        amount: 10_000,
+        token: token_b.clone(),
+    });
+
+    let result = client.distribute_multi_token_rewards(&admin, &rewards);
+
+    assert_eq!(result.successful, 1);
+    assert_eq!(result.failed, 2);
+    match result.results.get(0).unwrap() {
+        RewardResult::Failure(_, _, code) => assert_eq!(code, 14),
+        _ => panic!("Expected failure for blocked recipient"),
+    }
+    match result.results.get(2).unwrap() {
+        RewardResult::Failure(_, _, code) => assert_eq!(code, 9),
+        _ => panic!("Expected failure for lifetime cap"),
+    }
+    assert_eq!(result.total_distributed, 9_000);
+    assert_eq!(token_a_client.balance(&blocked), 0);
+    assert_eq!(token_a_client.balance(&recipient), 9_000);
+    assert_eq!(token_b_client.balance(&recipient), 0);
+    assert_eq!(token_a_client.balance(&collector), 1_000);
+    assert_eq!(token_b_client.balance(&admin), 50_000);
+    assert_eq!(client.get_recipient_reward_total(&recipient), 10_000);
+    assert_eq!(client.get_total_rewarded_to(&recipient), 9_000);
+    assert_eq!(client.get_total_fees_collected(), 1_000);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #5)")]
+fn test_multi_token_rewards_respect_max_batch_size() {
+    let (env, admin, token, _token_client, client) = setup_test_env();
+    token::StellarAssetClient::new(&env, &token).mint(&admin, &100_000);
+
+    client.set_max_reward_batch_size(&admin, &2);
+
+    let mut rewards: Vec<MultiTokenReward> = Vec::new(&env);
+    for _ in 0..3 {
+        rewards.push_back(MultiTokenReward {
+            recipient: Address::generate(&env),
+            amount: 1_000,
+            token: token.clone(),
+        });
+    }
+
+    client.distribute_multi_token_rewards(&admin, &rewards);
+}
+
+// Pause Tests
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #12)")]
+fn test_distribution_blocked_while_paused() {
+    let (env, admin, token, _token_client, client) = setup_test_env();
+    token::StellarAssetClient::new(&env, &token).mint(&admin, &100_000);
+    client.pause(&admin);
+
+    let mut rewards: Vec<RewardRequest> = Vec::new(&env);
+    rewards.push_back(create_reward_request(&env, Address::generate(&env), 10_000));
+
+    client.distribute_rewards(&admin, &token, &rewards);
+}
+
+#[test]
+fn test_distribution_resumes_after_unpause() {
+    let (env, admin, token, token_client, client) = setup_test_env();
+    token::StellarAssetClient::new(&env, &token).mint(&admin, &100_000);
+
+    client.pause(&admin);
+    assert!(client.is_paused());
+    // Admin functions and getters remain callable while paused
+    client.set_max_reward(&admin, &50_000);
+    assert_eq!(client.get_total_batches(), 0);
+
+    let recipient = Address::generate(&env);
+    let mut rewards: Vec<RewardRequest> = Vec::new(&env);
+    rewards.push_back(create_reward_request(&env, recipient.clone(), 10_000));
+    assert!(client
+        .try_distribute_rewards(&admin, &token, &rewards)
+        .is_err());
+
+    client.unpause(&admin);
+    assert!(!client.is_paused());
+    let result = client.distribute_rewards(&admin, &token, &rewards);
+
+    assert_eq!(result.successful, 1);
+    assert_eq!(token_client.balance(&recipient), 10_000);
+}
+
+// Min Reward Tests
+
+#[test]
+fn test_sub_minimum_reward_fails_while_others_succeed() {
+    let (env, admin, token, token_client, client) = setup_test_env();
+    token::StellarAssetClient::new(&env, &token).mint(&admin, &100_000);
+    client.set_min_reward(&admin, &1_000);
+    assert_eq!(client.get_min_reward(), 1_000);
+
+    let dust = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let mut rewards: Vec<RewardRequest> = Vec::new(&env);
+    rewards.push_back(create_reward_request(&env, dust.clone(), 999));
+    rewards.push_back(create_reward_request(&env, recipient.clone(), 1_000));
+
+    let result = client.distribute_rewards(&admin, &token, &rewards);
+
+    assert_eq!(result.successful, 1);
+    assert_eq!(result.failed, 1);
+    match result.results.get(0).unwrap() {
+        RewardResult::Failure(_, amount, code) => {
+            assert_eq!(amount, 999);
+            assert_eq!(code, 13);
+        }
+        _ => panic!("Expected failure for dust reward"),
+    }
+    assert_eq!(token_client.balance(&dust), 0);
+    assert_eq!(token_client.balance(&recipient), 1_000);
+}
+
+#[test]
+fn test_zero_min_reward_disables_check() {
+    let (env, admin, token, token_client, client) = setup_test_env();
+    token::StellarAssetClient::new(&env, &token).mint(&admin, &100_000);
+    client.set_min_reward(&admin, &0);
+
+    let recipient = Address::generate(&env);
+    let mut rewards: Vec<RewardRequest> = Vec::new(&env);
+    rewards.push_back(create_reward_request(&env, recipient.clone(), 1));
+
+    let result = client.distribute_rewards(&admin, &token, &rewards);
+
+    assert_eq!(result.successful, 1);
+    assert_eq!(token_client.balance(&recipient), 1);
+}
+
+// Block List Tests
+
+#[test]
+fn test_blocked_recipient_fails_while_allowed_succeeds() {
+    let (env, admin, token, token_client, client) = setup_test_env();
+    token::StellarAssetClient::new(&env, &token).mint(&admin, &100_000);
+
+    let blocked = Address::generate(&env);
+    let allowed = Address::generate(&env);
+    client.block_address(&admin, &blocked);
+    assert!(client.is_blocked(&blocked));
+    assert!(!client.is_blocked(&allowed));
+
+    let mut rewards: Vec<RewardRequest> = Vec::new(&env);
+    rewards.push_back(create_reward_request(&env, blocked.clone(), 5_000));
+    rewards.push_back(create_reward_request(&env, allowed.clone(), 5_000));
+
+    let result = client.distribute_rewards(&admin, &token, &rewards);
+
+    assert_eq!(result.successful, 1);
+    assert_eq!(result.failed, 1);
+    match result.results.get(0).unwrap() {
+        RewardResult::Failure(_, _, code) => assert_eq!(code, 14),
+        _ => panic!("Expected failure for blocked recipient"),
+    }
+    assert_eq!(token_client.balance(&blocked), 0);
+    assert_eq!(token_client.balance(&allowed), 5_000);
+    assert_eq!(token_client.balance(&admin), 95_000);
+}
+
+#[test]
+fn test_unblocked_address_receives_rewards() {
+    let (env, admin, token, token_client, client) = setup_test_env();
+    token::StellarAssetClient::new(&env, &token).mint(&admin, &100_000);
+
+    let recipient = Address::generate(&env);
+    client.block_address(&admin, &recipient);
+    client.unblock_address(&admin, &recipient);
+    assert!(!client.is_blocked(&recipient));
+
+    let mut rewards: Vec<RewardRequest> = Vec::new(&env);
+    rewards.push_back(create_reward_request(&env, recipient.clone(), 5_000));
+
+    let result = client.distribute_rewards(&admin, &token, &rewards);
+
+    assert_eq!(result.successful, 1);
+    assert_eq!(token_client.balance(&recipient), 5_000);
+}
+
+#[test]
+fn test_lowered_reward_batch_size_allows_batch_at_limit() {
+    let (env, admin, token, _token_client, client) = setup_test_env();
+    token::StellarAssetClient::new(&env, &token).mint(&admin, &100_000);
+
+    assert_eq!(client.get_max_reward_batch_size(), 100);
+    client.set_max_reward_batch_size(&admin, &3);
+    assert_eq!(client.get_max_reward_batch_size(), 3);
+
+    let mut rewards: Vec<RewardRequest> = Vec::new(&env);
+    for _ in 0..3 {
+        rewards.push_back(create_reward_request(&env, Address::generate(&env), 1_000));
+    }
+
+    let result = client.distribute_rewards(&admin, &token, &rewards);
+    assert_eq!(result.successful, 3);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #5)")]
+fn test_lowered_reward_batch_size_rejects_oversized_batch() {
+    let (env, admin, token, _token_client, client) = setup_test_env();
+    token::StellarAssetClient::new(&env, &token).mint(&admin, &100_000);
+
+    client.set_max_reward_batch_size(&admin, &3);
+
+    let mut rewards: Vec<RewardRequest> = Vec::new(&env);
+    for _ in 0..4 {
+        rewards.push_back(create_reward_request(&env, Address::generate(&env), 1_000));
+    }
+
+    client.distribute_rewards(&admin, &token, &rewards);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #15)")]
+fn test_set_max_reward_batch_size_rejects_zero() {
+    let (_env, admin, _token, _token_client, client) = setup_test_env();
+
+    client.set_max_reward_batch_size(&admin, &0);
+}
+
+#[test]
+fn test_reward_memos_round_trip_through_storage_and_events() {
+    let (env, admin, token, _token_client, client) = setup_test_env();
+    token::StellarAssetClient::new(&env, &token).mint(&admin, &100_000);
+
+    let with_memo = Address::generate(&env);
+    let without_memo = Address::generate(&env);
+
+    let mut memo_request = create_reward_request(&env, with_memo.clone(), 5_000);
+    memo_request.memo = Some(symbol_short!("inv_42"));
+    let mut rewards: Vec<RewardRequest> = Vec::new(&env);
+    rewards.push_back(memo_request);
+    rewards.push_back(create_reward_request(&env, without_memo.clone(), 3_000));
+
+    let result = client.distribute_rewards(&admin, &token, &rewards);
+    assert_eq!(result.successful, 2);
+
+    // Events only cover the latest invocation, so read them before any other call
+    let mut memos: Vec<Option<Symbol>> = Vec::new(&env);
+    for (_contract, topics, data) in env.events().all().iter() {
+        // Token transfer events carry addresses here, so skip non-symbol topics
+        let kind: Option<Symbol> = topics.get(1).unwrap().try_into_val(&env).ok();
+        if kind == Some(symbol_short!("success")) {
+            let (_recipient, _amount, memo): (Address, i128, Option<Symbol>) =
+                data.try_into_val(&env).unwrap();
+            memos.push_back(memo);
+        }
+    }
+    assert_eq!(
+        memos,
+        Vec::from_array(&env, [Some(symbol_short!("inv_42")), None])
+    );
+
+    let batch_id = client.get_total_batches();
+    assert_eq!(
+        client.get_reward_memo(&batch_id, &0),
+        Some(symbol_short!("inv_42"))
+    );
+    assert_eq!(client.get_reward_memo(&batch_id, &1), None);
+}
+
+#[test]
+fn test_daily_reward_cap_enforced_within_window() {
+    let (env, admin, token, token_client, client) = setup_test_env();
+    token::StellarAssetClient::new(&env, &token).mint(&admin, &100_000);
+    let recipient = Address::generate(&env);
+
+    client.set_daily_reward_cap(&admin, &10_000, &100);
+    assert_eq!(client.get_daily_reward_cap(), (10_000, 100));
+
+    let mut rewards: Vec<RewardRequest> = Vec::new(&env);
+    rewards.push_back(create_reward_request(&env, recipient.clone(), 6_000));
+    rewards.push_back(create_reward_request(&env, recipient.clone(), 5_000));
+    rewards.push_back(create_reward_request(&env, recipient.clone(), 4_000));
+
+    let result = client.distribute_rewards(&admin, &token, &rewards);
+
+    assert_eq!(result.successful, 2);
+    assert_eq!(result.failed, 1);
+    match result.results.get(1).unwrap() {
+        RewardResult::Failure(_, _, code) => assert_eq!(code, 16),
+        _ => panic!("Expected the reward over the daily cap to fail"),
+    }
+    assert_eq!(token_client.balance(&recipient), 10_000);
+}
+
+#[test]
+fn test_daily_reward_cap_resets_after_window() {
+    let (env, admin, token, token_client, client) = setup_test_env();
+    token::StellarAssetClient::new(&env, &token).mint(&admin, &100_000);
+    let recipient = Address::generate(&env);
+
+    client.set_daily_reward_cap(&admin, &10_000, &100);
+
+    let mut rewards: Vec<RewardRequest> = Vec::new(&env);
+    rewards.push_back(create_reward_request(&env, recipient.clone(), 10_000));
+    assert_eq!(
+        client
+            .distribute_rewards(&admin, &token, &rewards)
+            .successful,
+        1
+    );
+
+    // Still inside the window that started at ledger 12345
+    env.ledger().with_mut(|li| li.sequence_number = 12_444);
+    let mut rewards: Vec<RewardRequest> = Vec::new(&env);
+    rewards.push_back(create_reward_request(&env, recipient.clone(), 1_000));
+    assert_eq!(
+        client.distribute_rewards(&admin, &token, &rewards).failed,
+        1
+    );
+
+    // A new window starts once 100 ledgers have elapsed
+    env.ledger().with_mut(|li| li.sequence_number = 12_445);
+    assert_eq!(
+        client
+            .distribute_rewards(&admin, &token, &rewards)
+            .successful,
+        1
+    );
+    assert_eq!(token_client.balance(&recipient), 11_000);
+}
+
+#[test]
+fn test_daily_reward_cap_shared_with_claimable_rewards() {
+    let (env, admin, token, _token_client, client) = setup_test_env();
+    token::StellarAssetClient::new(&env, &token).mint(&admin, &100_000);
+    let recipient = Address::generate(&env);
+
+    client.set_daily_reward_cap(&admin, &10_000, &100);
+
+    let mut rewards: Vec<RewardRequest> = Vec::new(&env);
+    rewards.push_back(create_reward_request(&env, recipient.clone(), 8_000));
+    assert_eq!(client.distribute_rewards(&admin, &token, &rewards).successful, 1);
+
+    // Allocations count against the same window as direct payouts
+    let mut rewards: Vec<RewardRequest> = Vec::new(&env);
+    rewards.push_back(create_reward_request(&env, recipient.clone(), 3_000));
+    let result = client.allocate_claimable_rewards(&admin, &token, &rewards);
+
+    assert_eq!(result.failed, 1);
+    match result.results.get(0).unwrap() {
+        RewardResult::Failure(_, _, code) => assert_eq!(code, 16),
+        _ => panic!("Expected the allocation over the daily cap to fail"),
+    }
+    assert_eq!(client.get_claimable(&recipient, &token), 0);
+}
+
+#[test]
+fn test_validate_rewards_flags_each_entry() {
+    let (env, _admin, _token, _token_client, client) = setup_test_env();
+
+    let recipient = Address::generate(&env);
+    let mut rewards: Vec<RewardRequest> = Vec::new(&env);
+    rewards.push_back(create_reward_request(&env, recipient.clone(), 10_000));
+    rewards.push_back(create_reward_request(&env, Address::generate(&env), -500));
+    // Repeated recipients are allowed, as in `distribute_rewards`
+    rewards.push_back(create_reward_request(&env, recipient.clone(), 20_000));
+    rewards.push_back(create_reward_request(&env, Address::generate(&env), 0));
+
+    let flags = client.validate_rewards(&rewards);
+
+    assert_eq!(flags.len(), 4);
+    assert!(flags.get(0).unwrap());
+    assert!(!flags.get(1).unwrap());
+    assert!(flags.get(2).unwrap());
+    assert!(!flags.get(3).unwrap());
+    assert_eq!(client.get_total_batches(), 0);
+}
+
+#[test]
+fn test_scheduled_rewards_execute_after_activation() {
+    let (env, admin, token, token_client, client) = setup_test_env();
+
+    let recipient = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token).mint(&admin, &100_000);
+
+    let mut rewards: Vec<RewardRequest> = Vec::new(&env);
+    rewards.push_back(create_reward_request(&env, recipient.clone(), 40_000));
+
+    let scheduled_id = client.schedule_rewards(&admin, &token, &rewards, &12_400);
+    assert_eq!(
+        client
+            .get_scheduled_batch(&scheduled_id)
+            .unwrap()
+            .execute_at,
+        12_400
+    );
+
+    // Too early: nothing is distributed
+    assert!(client.try_execute_scheduled(&admin, &scheduled_id).is_err());
+    assert_eq!(token_client.balance(&recipient), 0);
+
+    env.ledger().with_mut(|li| li.sequence_number = 12_400);
+    let result = client.execute_scheduled(&admin, &scheduled_id);
+
+    assert_eq!(result.successful, 1);
+    assert_eq!(token_client.balance(&recipient), 40_000);
+    assert!(client.get_scheduled_batch(&scheduled_id).is_none());
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #18)")]
+fn test_execute_scheduled_before_activation_fails() {
+    let (env, admin, token, _token_client, client) = setup_test_env();
+
+    token::StellarAssetClient::new(&env, &token).mint(&admin, &100_000);
+    let mut rewards: Vec<RewardRequest> = Vec::new(&env);
+    rewards.push_back(create_reward_request(&env, Address::generate(&env), 40_000));
+
+    let scheduled_id = client.schedule_rewards(&admin, &token, &rewards, &12_400);
+    client.execute_scheduled(&admin, &scheduled_id);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #17)")]
+fn test_execute_scheduled_runs_only_once() {
+    let (env, admin, token, _token_client, client) = setup_test_env();
+
+    token::StellarAssetClient::new(&env, &token).mint(&admin, &100_000);
+    let mut rewards: Vec<RewardRequest> = Vec::new(&env);
+    rewards.push_back(create_reward_request(&env, Address::generate(&env), 40_000));
+
+    let scheduled_id = client.schedule_rewards(&admin, &token, &rewards, &12_345);
+    client.execute_scheduled(&admin, &scheduled_id);
+    client.execute_scheduled(&admin, &scheduled_id);
 }
@@ -1,4 +1,4 @@
-use soroban_sdk::{contracttype, symbol_short, Address, Env, Vec};
+use soroban_sdk::{contracttype, symbol_short, Address, Env, Symbol, Vec};
 
 pub const MAX_BATCH_SIZE: u32 = 100;
 
@@ -7,6 +7,17 @@ pub const MAX_BATCH_SIZE: u32 = 100;
 pub struct RewardRequest {
     pub recipient: Address,
     pub amount: i128,
+    /// Optional reconciliation memo stored with a successful reward
+    pub memo: Option<Symbol>,
+}
+
+/// A reward request paid in its own token.
+#[derive(Clone, Debug)]
+#[contracttype]
+pub struct MultiTokenReward {
+    pub recipient: Address,
+    pub amount: i128,
+    pub token: Address,
 }
 
 #[derive(Clone, Debug)]
@@ -26,6 +37,16 @@ pub struct BatchRewardResult {
     pub results: Vec<RewardResult>,
 }
 
+/// A reward batch staged for distribution at or after a target ledger.
+#[derive(Clone, Debug)]
+#[contracttype]
+pub struct ScheduledRewardBatch {
+    pub token: Address,
+    pub rewards: Vec<RewardRequest>,
+    /// Ledger sequence from which the batch may be executed
+    pub execute_at: u64,
+}
+
 #[derive(Clone)]
 #[contracttype]
 pub enum DataKey {
@@ -33,6 +54,42 @@ pub enum DataKey {
     TotalBatches,
     TotalRewardsProcessed,
     TotalVolumeDistributed,
+    /// Lifetime cap on rewards any single recipient may receive
+    LifetimeCap,
+    /// Cumulative rewards received by a recipient across all batches
+    RecipientRewardTotal(Address),
+    /// Fee in basis points skimmed from each reward
+    RewardFeeBps,
+    /// Address receiving skimmed reward fees
+    FeeCollector,
+    /// Total fees routed to the fee collector
+    TotalFeesCollected,
+    /// Accrued rewards a recipient can claim, per token
+    Claimable(Address, Address),
+    /// Maximum single reward per recipient (0 means no cap)
+    MaxRewardPerRecipient,
+    /// Net amount actually transferred to a recipient across all batches
+    TotalRewardedTo(Address),
+    /// Emergency stop for reward distribution
+    Paused,
+    /// Minimum single reward amount (0 disables the check)
+    MinRewardAmount,
+    /// Address barred from receiving rewards
+    Blocked(Address),
+    /// Runtime ceiling on rewards per distribution batch
+    MaxRewardBatchSize,
+    /// Memo attached to a successful reward: (batch_id, index in the batch)
+    RewardMemo(u64, u32),
+    /// Maximum a recipient may be rewarded within one daily window (0 disables)
+    DailyRewardCap,
+    /// Length of the daily reward window in ledgers
+    DailyRewardWindow,
+    /// A recipient's current daily window: (window start ledger, amount rewarded)
+    DailyRewardUsed(Address),
+    /// Last assigned scheduled batch ID
+    LastScheduledBatchId,
+    /// Reward batch awaiting execution, keyed by scheduled batch ID
+    ScheduledBatch(u64),
 }
 
 pub struct RewardEvents;
@@ -43,13 +100,19 @@ impl RewardEvents {
         env.events().publish(topics, (batch_id, request_count));
     }
 
-    pub fn reward_success(env: &Env, batch_id: u64, recipient: &Address, amount: i128) {
+    pub fn reward_success(
+        env: &Env,
+        batch_id: u64,
+        recipient: &Address,
+        amount: i128,
+        memo: &Option<Symbol>,
+    ) {
         let topics = (
             symbol_short!("reward"),
             symbol_short!("success"),
             batch_id,
         );
-        env.events().publish(topics, (recipient, amount));
+        env.events().publish(topics, (recipient, amount, memo.clone()));
     }
 
     pub fn reward_failure(env: &Env, batch_id: u64, recipient: &Address, amount: i128, error_code: u32) {
@@ -61,6 +124,16 @@ impl RewardEvents {
         env.events().publish(topics, (recipient, amount, error_code));
     }
 
+    pub fn batch_scheduled(env: &Env, scheduled_id: u64, execute_at: u64) {
+        let topics = (symbol_short!("batch"), symbol_short!("scheduled"));
+        env.events().publish(topics, (scheduled_id, execute_at));
+    }
+
+    pub fn reward_claimed(env: &Env, recipient: &Address, token: &Address, amount: i128) {
+        let topics = (symbol_short!("reward"), symbol_short!("claimed"));
+        env.events().publish(topics, (recipient, token, amount));
+    }
+
     pub fn batch_completed(
         env: &Env,
         batch_id: u64,
@@ -8,6 +8,7 @@ pub enum ValidationError {
     /// Invalid reward amount
     InvalidAmount,
     /// Invalid recipient address
+    #[allow(dead_code)]
     InvalidRecipient,
 }
 
@@ -4,12 +4,12 @@
 mod types;
 mod validation;
 
-use soroban_sdk::{
-    contract, contractimpl, panic_with_error, Address, Env, Vec,
-};
+use soroban_sdk::{contract, contractimpl, panic_with_error, token, Address, Env, Vec};
 
 pub use crate::types::{
-    BatchCreateResult, DataKey, Wallet, WalletCreateRequest, WalletCreateResult, WalletEvents, MAX_BATCH_SIZE,
+    BatchCreateResult, BatchDeactivateResult, DataKey, LimitSeedConfig, Wallet,
+    WalletCreateRequest, WalletCreateResult, WalletDeactivateResult, WalletEvents, MAX_BATCH_SIZE,
+    MAX_PAGE_SIZE,
 };
 use crate::validation::{validate_address, wallet_exists};
 
@@ -27,6 +27,8 @@ pub enum BatchWalletError {
     EmptyBatch = 4,
     /// Batch exceeds maximum size
     BatchTooLarge = 5,
+    /// Default seeded spending limit must be positive
+    InvalidSeedLimit = 6,
 }
 
 impl From<BatchWalletError> for soroban_sdk::Error {
@@ -52,6 +54,10 @@ impl BatchWalletContract {
     }
 
     /// Executes batch creation of wallets for multiple owners.
+    ///
+    /// Requests with a positive `initial_balance` are funded from the caller;
+    /// a failed transfer fails that wallet's creation, as does a negative
+    /// `initial_balance` (error code 4).
     pub fn batch_create_wallets(
         env: Env,
         caller: Address,
@@ -86,6 +92,10 @@ impl BatchWalletContract {
         let mut successful_count: u32 = 0;
         let mut failed_count: u32 = 0;
 
+        // Spending-limit seeding is only active once an integration is configured
+        let seed_config: Option<LimitSeedConfig> =
+            env.storage().instance().get(&DataKey::LimitSeedConfig);
+
         // Get current total wallets for ID assignment
         let mut next_wallet_id: u64 = env
             .storage()
@@ -108,6 +118,24 @@ impl BatchWalletContract {
                 is_valid = false;
                 error_code = 1; // Wallet already exists
             }
+            // Reject negative starting balances
+            else if request.initial_balance < 0 {
+                is_valid = false;
+                error_code = 4; // Invalid initial balance
+            }
+            // Seed the starting balance before the wallet is persisted
+            else if request.initial_balance > 0 {
+                let funded = match &request.token {
+                    Some(token) => token::Client::new(&env, token)
+                        .try_transfer(&caller, &request.owner, &request.initial_balance)
+                        .is_ok(),
+                    None => false,
+                };
+                if !funded {
+                    is_valid = false;
+                    error_code = 3; // Initial balance transfer failed
+                }
+            }
 
             if !is_valid {
                 // Validation failed - record and continue
@@ -130,10 +158,14 @@ impl BatchWalletContract {
                 id: next_wallet_id,
                 owner: request.owner.clone(),
                 created_at: env.ledger().timestamp(),
+                is_active: true,
             };
 
             // Store wallet
             env.storage().persistent().set(&DataKey::Wallets(request.owner.clone()), &wallet);
+            env.storage()
+                .persistent()
+                .set(&DataKey::WalletIndex(next_wallet_id - 1), &request.owner);
 
             // Increment ID
             next_wallet_id += 1;
@@ -148,6 +180,16 @@ impl BatchWalletContract {
                 &request.owner,
                 wallet.id,
             );
+
+            if let Some(config) = &seed_config {
+                WalletEvents::seed_limit(
+                    &env,
+                    batch_id,
+                    &request.owner,
+                    &config.integration,
+                    config.default_limit,
+                );
+            }
         }
 
         // Update storage
@@ -185,6 +227,107 @@ impl BatchWalletContract {
         }
     }
 
+    /// Deactivates the wallets of multiple owners, keeping the records queryable.
+    ///
+    /// Owners without a wallet are reported as failures (error code 2) while the
+    /// rest of the batch proceeds. Deactivation batches are numbered separately
+    /// from creation batches.
+    pub fn batch_deactivate_wallets(
+        env: Env,
+        admin: Address,
+        owners: Vec<Address>,
+    ) -> BatchDeactivateResult {
+        admin.require_auth();
+        Self::require_admin(&env, &admin);
+
+        let request_count = owners.len();
+        if request_count == 0 {
+            panic_with_error!(&env, BatchWalletError::EmptyBatch);
+        }
+        if request_count > MAX_BATCH_SIZE {
+            panic_with_error!(&env, BatchWalletError::BatchTooLarge);
+        }
+
+        let batch_id: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::TotalDeactivationBatches)
+            .unwrap_or(0)
+            + 1;
+
+        let mut results: Vec<WalletDeactivateResult> = Vec::new(&env);
+        let mut successful_count: u32 = 0;
+        let mut failed_count: u32 = 0;
+
+        for owner in owners.iter() {
+            let key = DataKey::Wallets(owner.clone());
+            let wallet: Option<Wallet> = env.storage().persistent().get(&key);
+            match wallet {
+                Some(mut wallet) => {
+                    wallet.is_active = false;
+                    env.storage().persistent().set(&key, &wallet);
+                    results.push_back(WalletDeactivateResult::Success(owner.clone()));
+                    successful_count += 1;
+                    WalletEvents::wallet_deactivated(&env, batch_id, &owner, wallet.id);
+                }
+                None => {
+                    let error_code = 2u32; // Wallet does not exist
+                    results.push_back(WalletDeactivateResult::Failure(owner.clone(), error_code));
+                    failed_count += 1;
+                    WalletEvents::wallet_deactivation_failure(&env, batch_id, &owner, error_code);
+                }
+            }
+        }
+
+        env.storage()
+            .instance()
+            .set(&DataKey::TotalDeactivationBatches, &batch_id);
+
+        BatchDeactivateResult {
+            total_requests: request_count,
+            successful: successful_count,
+            failed: failed_count,
+            results,
+        }
+    }
+
+    /// Configures the spending-limits integration that seeds a default limit
+    /// for every newly created wallet via a `seed_limit` event.
+    pub fn set_limit_integration(
+        env: Env,
+        caller: Address,
+        integration: Address,
+        default_limit: i128,
+    ) {
+        caller.require_auth();
+        Self::require_admin(&env, &caller);
+
+        if default_limit <= 0 {
+            panic_with_error!(&env, BatchWalletError::InvalidSeedLimit);
+        }
+
+        env.storage().instance().set(
+            &DataKey::LimitSeedConfig,
+            &LimitSeedConfig {
+                integration,
+                default_limit,
+            },
+        );
+    }
+
+    /// Disables spending-limit seeding for new wallets.
+    pub fn clear_limit_integration(env: Env, caller: Address) {
+        caller.require_auth();
+        Self::require_admin(&env, &caller);
+
+        env.storage().instance().remove(&DataKey::LimitSeedConfig);
+    }
+
+    /// Returns the configured spending-limit seeding integration, if any.
+    pub fn get_limit_integration(env: Env) -> Option<LimitSeedConfig> {
+        env.storage().instance().get(&DataKey::LimitSeedConfig)
+    }
+
     /// Returns the admin address.
     pub fn get_admin(env: Env) -> Address {
         env.storage()
@@ -209,6 +352,14 @@ impl BatchWalletContract {
             .unwrap_or(0)
     }
 
+    /// Returns the total number of deactivation batches processed.
+    pub fn get_total_deactivation_batches(env: Env) -> u64 {
+        env.storage()
+            .instance()
+            .get(&DataKey::TotalDeactivationBatches)
+            .unwrap_or(0)
+    }
+
     /// Returns the total number of wallets created.
     pub fn get_total_wallets_created(env: Env) -> u64 {
         env.storage()
@@ -222,6 +373,39 @@ impl BatchWalletContract {
         env.storage().persistent().get(&DataKey::Wallets(address))
     }
 
+    /// Returns whether each owner already has a wallet, in the same order as `owners`.
+    ///
+    /// Batches over `MAX_BATCH_SIZE` are rejected.
+    pub fn batch_wallet_exists(env: Env, owners: Vec<Address>) -> Vec<bool> {
+        if owners.len() > MAX_BATCH_SIZE {
+            panic_with_error!(&env, BatchWalletError::BatchTooLarge);
+        }
+
+        let mut flags: Vec<bool> = Vec::new(&env);
+        for owner in owners.iter() {
+            flags.push_back(wallet_exists(&env, &owner));
+        }
+        flags
+    }
+
+    /// Returns up to `limit` wallets in creation order, starting at index `start`.
+    ///
+    /// `limit` is capped at `MAX_PAGE_SIZE`.
+    pub fn get_wallets_page(env: Env, start: u64, limit: u32) -> Vec<Wallet> {
+        let total = Self::get_total_wallets_created(env.clone());
+        let end = total.min(start.saturating_add(limit.min(MAX_PAGE_SIZE) as u64));
+
+        let mut page: Vec<Wallet> = Vec::new(&env);
+        for index in start..end {
+            let owner: Option<Address> =
+                env.storage().persistent().get(&DataKey::WalletIndex(index));
+            if let Some(wallet) = owner.and_then(|owner| Self::get_wallet(env.clone(), owner)) {
+                page.push_back(wallet);
+            }
+        }
+        page
+    }
+
     // Internal helper to verify admin
     fn require_admin(env: &Env, caller: &Address) {
         let admin: Address = env
@@ -4,10 +4,12 @@
 
 use crate::{
     BatchWalletContract, BatchWalletContractClient, WalletCreateRequest, WalletCreateResult,
+    WalletDeactivateResult, MAX_BATCH_SIZE,
 };
 use soroban_sdk::{
+    symbol_short,
     testutils::{Address as _, Events as _, Ledger},
-    Address, Env, Vec,
+    token, Address, Env, Symbol, TryIntoVal, Vec,
 };
 
 /// Creates a test environment with the contract deployed and initialized.
@@ -30,7 +32,38 @@ fn setup_test_env() -> (Env, Address, BatchWalletContractClient<'static>) {
 
 /// Helper to create a wallet creation request.
 fn create_wallet_request(_env: &Env, owner: Address) -> WalletCreateRequest {
-    WalletCreateRequest { owner }
+    WalletCreateRequest {
+        owner,
+        initial_balance: 0,
+        token: None,
+    }
+}
+
+/// Helper to create a wallet creation request seeded with a starting balance.
+fn create_funded_wallet_request(
+    owner: Address,
+    token: &Address,
+    amount: i128,
+) -> WalletCreateRequest {
+    WalletCreateRequest {
+        owner,
+        initial_balance: amount,
+        token: Some(token.clone()),
+    }
+}
+
+/// Helper collecting the owners named in `seed_limit` events from the last call.
+fn seeded_owners(env: &Env) -> Vec<Address> {
+    let mut owners = Vec::new(env);
+    for (_contract, topics, data) in env.events().all().iter() {
+        let kind: Symbol = topics.get(1).unwrap().try_into_val(env).unwrap();
+        if kind == symbol_short!("seed_lim") {
+            let (owner, _integration, _limit): (Address, Address, i128) =
+                data.try_into_val(env).unwrap();
+            owners.push_back(owner);
+        }
+    }
+    owners
 }
 
 // Initialization Tests
@@ -47,7 +80,7 @@ fn test_initialize_contract() {
 #[test]
 #[should_panic(expected = "Contract already initialized")]
 fn test_cannot_initialize_twice() {
-    let (env, admin, client) = setup_test_env();
+    let (env, _admin, client) = setup_test_env();
 
     let new_admin = Address::generate(&env);
     client.initialize(&new_admin);
@@ -75,6 +108,7 @@ fn test_batch_create_wallets_single() {
     let wallet = client.get_wallet(&owner).unwrap();
     assert_eq!(wallet.owner, owner);
     assert_eq!(wallet.id, 1);
+    assert!(wallet.is_active);
 }
 
 #[test]
@@ -213,7 +247,7 @@ fn test_batch_create_wallets_empty_batch() {
 #[test]
 #[should_panic]
 fn test_batch_create_wallets_unauthorized() {
-    let (env, admin, client) = setup_test_env();
+    let (env, _admin, client) = setup_test_env();
 
     let unauthorized = Address::generate(&env);
     let owner = Address::generate(&env);
@@ -252,6 +286,76 @@ fn test_batch_create_wallets_large_batch() {
     assert_eq!(wallet50.id, 50);
 }
 
+// Spending-Limit Seeding Tests
+// ============================================================================
+
+#[test]
+fn test_seed_limit_emitted_for_each_created_wallet() {
+    let (env, admin, client) = setup_test_env();
+
+    let integration = Address::generate(&env);
+    client.set_limit_integration(&admin, &integration, &5_000);
+    assert_eq!(client.get_limit_integration().unwrap().default_limit, 5_000);
+
+    let owner1 = Address::generate(&env);
+    let owner2 = Address::generate(&env);
+    let mut requests: Vec<WalletCreateRequest> = Vec::new(&env);
+    requests.push_back(create_wallet_request(&env, owner1.clone()));
+    requests.push_back(create_wallet_request(&env, owner2.clone()));
+    client.batch_create_wallets(&admin, &requests);
+
+    let owners = seeded_owners(&env);
+    assert_eq!(owners.len(), 2);
+    assert_eq!(owners.get(0).unwrap(), owner1);
+    assert_eq!(owners.get(1).unwrap(), owner2);
+}
+
+#[test]
+fn test_seed_limit_skips_failed_wallets() {
+    let (env, admin, client) = setup_test_env();
+
+    let owner1 = Address::generate(&env);
+    let mut requests: Vec<WalletCreateRequest> = Vec::new(&env);
+    requests.push_back(create_wallet_request(&env, owner1.clone()));
+    client.batch_create_wallets(&admin, &requests);
+
+    client.set_limit_integration(&admin, &Address::generate(&env), &5_000);
+
+    let owner2 = Address::generate(&env);
+    let mut requests: Vec<WalletCreateRequest> = Vec::new(&env);
+    requests.push_back(create_wallet_request(&env, owner1.clone())); // Duplicate
+    requests.push_back(create_wallet_request(&env, owner2.clone()));
+    client.batch_create_wallets(&admin, &requests);
+
+    let owners = seeded_owners(&env);
+    assert_eq!(owners.len(), 1);
+    assert_eq!(owners.get(0).unwrap(), owner2);
+}
+
+#[test]
+fn test_seed_limit_not_emitted_without_integration() {
+    let (env, admin, client) = setup_test_env();
+
+    let mut requests: Vec<WalletCreateRequest> = Vec::new(&env);
+    requests.push_back(create_wallet_request(&env, Address::generate(&env)));
+    client.batch_create_wallets(&admin, &requests);
+
+    assert_eq!(seeded_owners(&env).len(), 0);
+
+    client.set_limit_integration(&admin, &Address::generate(&env), &5_000);
+    client.clear_limit_integration(&admin);
+    assert!(client.get_limit_integration().is_none());
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #6)")]
+fn test_set_limit_integration_rejects_non_positive_limit() {
+    let (env, admin, client) = setup_test_env();
+
+    client.set_limit_integration(&admin, &Address::generate(&env), &0);
+}
+
+// ============================================================================
 // Admin Tests
 
 #[test]
@@ -297,4 +401,248 @@ fn test_multiple_simultaneous_batch_creations() {
     // Verify contract stats
     assert_eq!(client.get_total_batches(), 2);
     assert_eq!(client.get_total_wallets_created(), 4); // 3 + 1
-}
\ No newline at end of file
+}
+// Deactivation Tests
+
+#[test]
+fn test_batch_deactivate_wallets() {
+    let (env, admin, client) = setup_test_env();
+
+    let owner1 = Address::generate(&env);
+    let owner2 = Address::generate(&env);
+    let owner3 = Address::generate(&env);
+    let mut requests: Vec<WalletCreateRequest> = Vec::new(&env);
+    requests.push_back(create_wallet_request(&env, owner1.clone()));
+    requests.push_back(create_wallet_request(&env, owner2.clone()));
+    requests.push_back(create_wallet_request(&env, owner3.clone()));
+    client.batch_create_wallets(&admin, &requests);
+
+    let mut owners: Vec<Address> = Vec::new(&env);
+    owners.push_back(owner1.clone());
+    owners.push_back(owner3.clone());
+    let result = client.batch_deactivate_wallets(&admin, &owners);
+
+    assert_eq!(result.successful, 2);
+    assert_eq!(result.failed, 0);
+    assert!(!client.get_wallet(&owner1).unwrap().is_active);
+    assert!(client.get_wallet(&owner2).unwrap().is_active);
+    assert!(!client.get_wallet(&owner3).unwrap().is_active);
+    assert_eq!(client.get_wallet(&owner3).unwrap().id, 3);
+}
+
+#[test]
+fn test_batch_deactivate_missing_wallet_fails_per_owner() {
+    let (env, admin, client) = setup_test_env();
+
+    let owner = Address::generate(&env);
+    let mut requests: Vec<WalletCreateRequest> = Vec::new(&env);
+    requests.push_back(create_wallet_request(&env, owner.clone()));
+    client.batch_create_wallets(&admin, &requests);
+
+    let missing = Address::generate(&env);
+    let mut owners: Vec<Address> = Vec::new(&env);
+    owners.push_back(missing.clone());
+    owners.push_back(owner.clone());
+    let result = client.batch_deactivate_wallets(&admin, &owners);
+
+    // The failure is reported with its own deactivation event
+    let failure_event = env
+        .events()
+        .all()
+        .iter()
+        .find(|(_contract, topics, _data)| {
+            let kind: Result<Symbol, _> = topics.get(1).unwrap().try_into_val(&env);
+            kind == Ok(symbol_short!("deact_err"))
+        });
+    let (_contract, topics, data) = failure_event.unwrap();
+    let batch_id: u64 = topics.get(2).unwrap().try_into_val(&env).unwrap();
+    let (addr, code): (Address, u32) = data.try_into_val(&env).unwrap();
+    assert_eq!(batch_id, 1);
+    assert_eq!(addr, missing);
+    assert_eq!(code, 2);
+
+    assert_eq!(result.successful, 1);
+    assert_eq!(result.failed, 1);
+    match result.results.get(0).unwrap() {
+        WalletDeactivateResult::Failure(addr, code) => {
+            assert_eq!(addr, missing);
+            assert_eq!(code, 2);
+        }
+        _ => panic!("Expected failure for missing wallet"),
+    }
+    assert!(client.get_wallet(&missing).is_none());
+    assert!(!client.get_wallet(&owner).unwrap().is_active);
+
+    // Deactivations are counted apart from creation batches
+    assert_eq!(client.get_total_deactivation_batches(), 1);
+    assert_eq!(client.get_total_batches(), 1);
+}
+
+// Pagination Tests
+
+#[test]
+fn test_get_wallets_page_covers_all_wallets() {
+    let (env, admin, client) = setup_test_env();
+
+    let mut requests: Vec<WalletCreateRequest> = Vec::new(&env);
+    for _ in 0..5 {
+        requests.push_back(create_wallet_request(&env, Address::generate(&env)));
+    }
+    client.batch_create_wallets(&admin, &requests);
+
+    let first = client.get_wallets_page(&0, &2);
+    let second = client.get_wallets_page(&2, &2);
+    let third = client.get_wallets_page(&4, &2);
+
+    assert_eq!(first.len(), 2);
+    assert_eq!(second.len(), 2);
+    assert_eq!(third.len(), 1);
+    assert_eq!(first.get(0).unwrap().id, 1);
+    assert_eq!(first.get(1).unwrap().id, 2);
+    assert_eq!(second.get(0).unwrap().id, 3);
+    assert_eq!(second.get(1).unwrap().id, 4);
+    assert_eq!(third.get(0).unwrap().id, 5);
+    assert_eq!(third.get(0).unwrap().owner, requests.get(4).unwrap().owner);
+
+    assert_eq!(client.get_wallets_page(&5, &2).len(), 0);
+}
+
+#[test]
+fn test_get_wallets_page_caps_limit() {
+    let (env, admin, client) = setup_test_env();
+
+    let mut requests: Vec<WalletCreateRequest> = Vec::new(&env);
+    for _ in 0..60 {
+        requests.push_back(create_wallet_request(&env, Address::generate(&env)));
+    }
+    client.batch_create_wallets(&admin, &requests);
+
+    assert_eq!(client.get_wallets_page(&0, &100).len(), 50);
+}
+
+// Initial Balance Tests
+
+#[test]
+fn test_batch_create_wallets_with_initial_balance() {
+    let (env, admin, client) = setup_test_env();
+    let token = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    let token_client = token::Client::new(&env, &token);
+    token::StellarAssetClient::new(&env, &token).mint(&admin, &50_000);
+
+    let owner1 = Address::generate(&env);
+    let owner2 = Address::generate(&env);
+    let mut requests: Vec<WalletCreateRequest> = Vec::new(&env);
+    requests.push_back(create_funded_wallet_request(owner1.clone(), &token, 10_000));
+    requests.push_back(create_funded_wallet_request(owner2.clone(), &token, 20_000));
+
+    let result = client.batch_create_wallets(&admin, &requests);
+
+    assert_eq!(result.successful, 2);
+    assert_eq!(token_client.balance(&owner1), 10_000);
+    assert_eq!(token_client.balance(&owner2), 20_000);
+    assert_eq!(token_client.balance(&admin), 20_000);
+}
+
+#[test]
+fn test_underfunded_admin_fails_unfunded_wallets() {
+    let (env, admin, client) = setup_test_env();
+    let token = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    let token_client = token::Client::new(&env, &token);
+    token::StellarAssetClient::new(&env, &token).mint(&admin, &15_000);
+
+    let owner1 = Address::generate(&env);
+    let owner2 = Address::generate(&env);
+    let mut requests: Vec<WalletCreateRequest> = Vec::new(&env);
+    requests.push_back(create_funded_wallet_request(owner1.clone(), &token, 10_000));
+    requests.push_back(create_funded_wallet_request(owner2.clone(), &token, 10_000));
+
+    let result = client.batch_create_wallets(&admin, &requests);
+
+    assert_eq!(result.successful, 1);
+    assert_eq!(result.failed, 1);
+    match result.results.get(1).unwrap() {
+        WalletCreateResult::Failure(addr, code) => {
+            assert_eq!(addr, owner2);
+            assert_eq!(code, 3);
+        }
+        _ => panic!("Expected failure for unfunded wallet"),
+    }
+    assert!(client.get_wallet(&owner1).is_some());
+    assert!(client.get_wallet(&owner2).is_none());
+    assert_eq!(token_client.balance(&owner1), 10_000);
+    assert_eq!(token_client.balance(&owner2), 0);
+    assert_eq!(client.get_total_wallets_created(), 1);
+}
+
+#[test]
+fn test_negative_initial_balance_fails_wallet() {
+    let (env, admin, client) = setup_test_env();
+    let token = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+
+    let owner1 = Address::generate(&env);
+    let owner2 = Address::generate(&env);
+    let mut requests: Vec<WalletCreateRequest> = Vec::new(&env);
+    requests.push_back(create_funded_wallet_request(owner1.clone(), &token, -500));
+    requests.push_back(create_wallet_request(&env, owner2.clone()));
+
+    let result = client.batch_create_wallets(&admin, &requests);
+
+    assert_eq!(result.successful, 1);
+    assert_eq!(result.failed, 1);
+    match result.results.get(0).unwrap() {
+        WalletCreateResult::Failure(addr, code) => {
+            assert_eq!(addr, owner1);
+            assert_eq!(code, 4);
+        }
+        _ => panic!("Expected failure for negative initial balance"),
+    }
+    assert!(client.get_wallet(&owner1).is_none());
+    assert!(client.get_wallet(&owner2).is_some());
+}
+
+// Existence Check Tests
+
+#[test]
+fn test_batch_wallet_exists_is_positional() {
+    let (env, admin, client) = setup_test_env();
+
+    let existing1 = Address::generate(&env);
+    let existing2 = Address::generate(&env);
+    let mut requests: Vec<WalletCreateRequest> = Vec::new(&env);
+    requests.push_back(create_wallet_request(&env, existing1.clone()));
+    requests.push_back(create_wallet_request(&env, existing2.clone()));
+    client.batch_create_wallets(&admin, &requests);
+
+    let mut owners: Vec<Address> = Vec::new(&env);
+    owners.push_back(Address::generate(&env));
+    owners.push_back(existing2.clone());
+    owners.push_back(existing1.clone());
+    owners.push_back(Address::generate(&env));
+
+    let flags = client.batch_wallet_exists(&owners);
+
+    assert_eq!(flags.len(), 4);
+    assert!(!flags.get(0).unwrap());
+    assert!(flags.get(1).unwrap());
+    assert!(flags.get(2).unwrap());
+    assert!(!flags.get(3).unwrap());
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #5)")]
+fn test_batch_wallet_exists_rejects_oversized_batch() {
+    let (env, _admin, client) = setup_test_env();
+
+    let mut owners: Vec<Address> = Vec::new(&env);
+    for _ in 0..(MAX_BATCH_SIZE + 1) {
+        owners.push_back(Address::generate(&env));
+    }
+
+    client.batch_wallet_exists(&owners);
+}
@@ -2,10 +2,17 @@ use soroban_sdk::{contracttype, symbol_short, Address, Env, Vec};
 
 pub const MAX_BATCH_SIZE: u32 = 100;
 
+/// Maximum number of wallets returned by a single page query.
+pub const MAX_PAGE_SIZE: u32 = 50;
+
 #[derive(Clone, Debug)]
 #[contracttype]
 pub struct WalletCreateRequest {
     pub owner: Address,
+    /// Starting balance transferred from the admin (0 for none)
+    pub initial_balance: i128,
+    /// Token used for the starting balance
+    pub token: Option<Address>,
 }
 
 #[derive(Clone, Debug)]
@@ -24,6 +31,22 @@ pub struct BatchCreateResult {
     pub results: Vec<WalletCreateResult>,
 }
 
+#[derive(Clone, Debug)]
+#[contracttype]
+pub enum WalletDeactivateResult {
+    Success(Address),
+    Failure(Address, u32),
+}
+
+#[derive(Clone, Debug)]
+#[contracttype]
+pub struct BatchDeactivateResult {
+    pub total_requests: u32,
+    pub successful: u32,
+    pub failed: u32,
+    pub results: Vec<WalletDeactivateResult>,
+}
+
 #[derive(Clone)]
 #[contracttype]
 pub enum DataKey {
@@ -31,6 +54,16 @@ pub enum DataKey {
     TotalBatches,
     TotalWalletsCreated,
     Wallets(Address), // Map of address to wallet id or something
+    LimitSeedConfig,  // Spending-limits integration used to seed new wallets
+    WalletIndex(u64), // Sequential creation index to owner address
+    TotalDeactivationBatches,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct LimitSeedConfig {
+    pub integration: Address,
+    pub default_limit: i128,
 }
 
 #[derive(Clone, Debug)]
@@ -39,6 +72,7 @@ pub struct Wallet {
     pub id: u64,
     pub owner: Address,
     pub created_at: u64,
+    pub is_active: bool,
 }
 
 pub struct WalletEvents;
@@ -64,6 +98,32 @@ impl WalletEvents {
         env.events().publish(topics, (owner.clone(), error_code));
     }
 
+    pub fn wallet_deactivated(env: &Env, batch_id: u64, owner: &Address, wallet_id: u64) {
+        let topics = (symbol_short!("wallet"), symbol_short!("deactiv"), batch_id);
+        env.events().publish(topics, (owner.clone(), wallet_id));
+    }
+
+    pub fn wallet_deactivation_failure(env: &Env, batch_id: u64, owner: &Address, error_code: u32) {
+        let topics = (
+            symbol_short!("wallet"),
+            symbol_short!("deact_err"),
+            batch_id,
+        );
+        env.events().publish(topics, (owner.clone(), error_code));
+    }
+
+    pub fn seed_limit(
+        env: &Env,
+        batch_id: u64,
+        owner: &Address,
+        integration: &Address,
+        default_limit: i128,
+    ) {
+        let topics = (symbol_short!("wallet"), symbol_short!("seed_lim"), batch_id);
+        env.events()
+            .publish(topics, (owner.clone(), integration.clone(), default_limit));
+    }
+
     pub fn batch_completed(
         env: &Env,
         batch_id: u64,
@@ -24,14 +24,17 @@ mod recommendations;
 mod types;
 mod validation;
 
-use soroban_sdk::{contract, contractimpl, panic_with_error, Address, Env, Vec};
+use soroban_sdk::{contract, contractimpl, panic_with_error, Address, Env, Map, Vec};
 
-pub use crate::recommendations::{generate_batch_recommendations, generate_recommendation};
+pub use crate::recommendations::{
+    compute_budget_variance, generate_batch_recommendations, generate_recommendation,
+};
 pub use crate::types::{
-    BatchRecommendationMetrics, BatchRecommendationResult, BudgetRecommendation, DataKey,
-    RecommendationEvents, RecommendationResult, UserProfile, MAX_BATCH_SIZE,
+    AllocationSplit, BatchRecommendationMetrics, BatchRecommendationResult, BudgetRecommendation,
+    BudgetVariance, DataKey, RecommendationEvents, RecommendationResult, UserProfile,
+    MAX_BATCH_SIZE, MAX_RECOMMENDATION_HISTORY,
 };
-use crate::validation::validate_batch;
+use crate::validation::{validate_allocation_split, validate_batch};
 
 /// Error codes for the budget recommendations contract.
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
@@ -49,6 +52,12 @@ pub enum BudgetRecommendationError {
     BatchTooLarge = 5,
     /// Invalid user profile
     InvalidUserProfile = 6,
+    /// Allocation split does not sum to 10000 bps
+    InvalidAllocation = 7,
+    /// Risk bucket is outside the 1-5 range
+    InvalidRiskBucket = 8,
+    /// Confidence floor is above 100
+    InvalidConfidence = 9,
 }
 
 impl From<BudgetRecommendationError> for soroban_sdk::Error {
@@ -121,7 +130,7 @@ impl BudgetRecommendationsContract {
         }
 
         // Validate batch of user profiles
-        if let Err(_) = validate_batch(&user_profiles) {
+        if validate_batch(&user_profiles).is_err() {
             panic_with_error!(&env, BudgetRecommendationError::InvalidBatch);
         }
 
@@ -140,13 +149,23 @@ impl BudgetRecommendationsContract {
         let current_ledger = env.ledger().sequence() as u64;
 
         // Generate batch recommendations (single pass over data)
-        let (results, metrics) =
-            generate_batch_recommendations(&env, &user_profiles, current_ledger);
+        let splits = Self::load_allocation_splits(&env);
+        let min_confidence = Self::get_min_confidence(env.clone());
+        let (results, metrics) = generate_batch_recommendations(
+            &env,
+            &user_profiles,
+            &splits,
+            current_ledger,
+            min_confidence,
+        );
 
         // Emit events for each recommendation
-        for result in results.iter() {
+        for (index, result) in results.iter().enumerate() {
             match result {
                 RecommendationResult::Success(recommendation) => {
+                    let profile = user_profiles.get(index as u32).unwrap();
+                    Self::append_user_history(&env, &profile.address, &recommendation);
+
                     RecommendationEvents::recommendation_generated(
                         &env,
                         batch_id,
@@ -204,6 +223,7 @@ impl BudgetRecommendationsContract {
             successful: metrics.successful_recommendations,
             failed: metrics.failed_recommendations,
             results: results.clone(),
+            filtered_out: 0,
             metrics: metrics.clone(),
         };
 
@@ -213,6 +233,48 @@ impl BudgetRecommendationsContract {
         batch_result
     }
 
+    /// Generates batch recommendations and returns only confident results.
+    ///
+    /// Runs the normal batch (all events emitted, full result set stored) but
+    /// the returned `results` keep only successful recommendations with
+    /// `confidence_score >= min_confidence`. Failed results are always omitted
+    /// and stay counted in `failed`; `filtered_out` counts only the successful
+    /// recommendations dropped for low confidence.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `caller` - The address calling this function (must be admin)
+    /// * `user_profiles` - Vector of user profiles to process
+    /// * `min_confidence` - Minimum confidence score (0-100) to include
+    ///
+    /// # Returns
+    /// * `BatchRecommendationResult` - Filtered results with `filtered_out` set
+    pub fn gen_filtered_recommendations(
+        env: Env,
+        caller: Address,
+        user_profiles: Vec<UserProfile>,
+        min_confidence: u32,
+    ) -> BatchRecommendationResult {
+        let mut batch_result =
+            Self::generate_batch_recommendations(env.clone(), caller, user_profiles);
+
+        let mut filtered: Vec<RecommendationResult> = Vec::new(&env);
+        let mut filtered_out: u32 = 0;
+        for result in batch_result.results.iter() {
+            if let RecommendationResult::Success(recommendation) = &result {
+                if recommendation.confidence_score >= min_confidence {
+                    filtered.push_back(result.clone());
+                } else {
+                    filtered_out += 1;
+                }
+            }
+        }
+
+        batch_result.filtered_out = filtered_out;
+        batch_result.results = filtered;
+        batch_result
+    }
+
     /// Retrieves stored recommendations for a specific batch.
     ///
     /// # Arguments
@@ -227,6 +289,52 @@ impl BudgetRecommendationsContract {
             .get(&DataKey::BatchRecommendations(batch_id))
     }
 
+    /// Returns the stored recommendation history for a user, oldest first.
+    ///
+    /// At most `MAX_RECOMMENDATION_HISTORY` entries are retained.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `user` - The user's address
+    pub fn get_user_recommendation_history(env: Env, user: Address) -> Vec<BudgetRecommendation> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::UserRecommendationHistory(user))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Returns the user's latest recommendation, or None if it is stale.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `user` - The user's address
+    pub fn get_fresh_recommendation(env: Env, user: Address) -> Option<BudgetRecommendation> {
+        let latest = Self::get_user_recommendation_history(env.clone(), user).last()?;
+        if Self::is_recommendation_stale(env, latest.clone()) {
+            None
+        } else {
+            Some(latest)
+        }
+    }
+
+    /// Returns whether a recommendation has outlived the configured TTL.
+    ///
+    /// A recommendation is stale once the current ledger passes
+    /// `generated_at + ttl`. Nothing is stale while no TTL is configured.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `recommendation` - The recommendation to check
+    pub fn is_recommendation_stale(env: Env, recommendation: BudgetRecommendation) -> bool {
+        let ttl = Self::get_recommendation_ttl(env.clone());
+        if ttl == 0 {
+            return false;
+        }
+
+        let current_ledger = env.ledger().sequence() as u64;
+        current_ledger > recommendation.generated_at.saturating_add(ttl)
+    }
+
     /// Generates a recommendation for a single user (view-only, no storage).
     ///
     /// Useful for simulating recommendations before committing.
@@ -241,10 +349,138 @@ impl BudgetRecommendationsContract {
         env: Env,
         user_profile: UserProfile,
     ) -> Option<BudgetRecommendation> {
-        match generate_recommendation(&env, &user_profile) {
-            Ok(rec) => Some(rec),
-            Err(_) => None,
+        let split = Self::load_allocation_splits(&env).get(user_profile.risk_tolerance);
+        generate_recommendation(&env, &user_profile, split).ok()
+    }
+
+    /// Compares actual spending against a recommendation's budget.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `recommendation` - The recommendation to compare against
+    /// * `actual_spending` - Spending recorded for the period
+    ///
+    /// # Returns
+    /// * `BudgetVariance` - Over/under amount and variance in basis points
+    pub fn compute_budget_variance(
+        _env: Env,
+        recommendation: BudgetRecommendation,
+        actual_spending: i128,
+    ) -> BudgetVariance {
+        compute_budget_variance(&recommendation, actual_spending)
+    }
+
+    /// Sets the base savings/needs/wants split used for every risk bucket
+    /// that has no specific override.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `caller` - The admin address
+    /// * `split` - Allocation in basis points; must sum to 10000
+    pub fn set_base_allocation(env: Env, caller: Address, split: AllocationSplit) {
+        caller.require_auth();
+        Self::require_admin(&env, &caller);
+
+        if validate_allocation_split(&split).is_err() {
+            panic_with_error!(&env, BudgetRecommendationError::InvalidAllocation);
+        }
+
+        env.storage()
+            .instance()
+            .set(&DataKey::BaseAllocation, &split);
+    }
+
+    /// Sets the savings/needs/wants split for a single risk tolerance bucket.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `caller` - The admin address
+    /// * `risk_tolerance` - Risk bucket (1-5) the split applies to
+    /// * `split` - Allocation in basis points; must sum to 10000
+    pub fn set_risk_allocation(
+        env: Env,
+        caller: Address,
+        risk_tolerance: u32,
+        split: AllocationSplit,
+    ) {
+        caller.require_auth();
+        Self::require_admin(&env, &caller);
+
+        if !(1..=5).contains(&risk_tolerance) {
+            panic_with_error!(&env, BudgetRecommendationError::InvalidRiskBucket);
+        }
+        if validate_allocation_split(&split).is_err() {
+            panic_with_error!(&env, BudgetRecommendationError::InvalidAllocation);
+        }
+
+        env.storage()
+            .instance()
+            .set(&DataKey::RiskAllocation(risk_tolerance), &split);
+    }
+
+    /// Sets how many ledgers a recommendation stays fresh after generation.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `caller` - The admin address
+    /// * `ttl` - Freshness window in ledgers; 0 disables expiry
+    pub fn set_recommendation_ttl(env: Env, caller: Address, ttl: u64) {
+        caller.require_auth();
+        Self::require_admin(&env, &caller);
+
+        env.storage()
+            .instance()
+            .set(&DataKey::RecommendationTtl, &ttl);
+    }
+
+    /// Returns the recommendation TTL in ledgers (0 when expiry is disabled).
+    pub fn get_recommendation_ttl(env: Env) -> u64 {
+        env.storage()
+            .instance()
+            .get(&DataKey::RecommendationTtl)
+            .unwrap_or(0)
+    }
+
+    /// Sets the confidence floor below which recommendations are rejected.
+    ///
+    /// Profiles scoring under the floor are recorded as failures with a
+    /// `low_confidence` reason. A floor of 0 accepts every recommendation.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `caller` - The admin address
+    /// * `min_confidence` - Minimum confidence score (0-100)
+    pub fn set_min_confidence(env: Env, caller: Address, min_confidence: u32) {
+        caller.require_auth();
+        Self::require_admin(&env, &caller);
+
+        if min_confidence > 100 {
+            panic_with_error!(&env, BudgetRecommendationError::InvalidConfidence);
         }
+
+        env.storage()
+            .instance()
+            .set(&DataKey::MinConfidence, &min_confidence);
+    }
+
+    /// Returns the confidence floor (0 when every recommendation is accepted).
+    pub fn get_min_confidence(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::MinConfidence)
+            .unwrap_or(0)
+    }
+
+    /// Returns the configured base allocation, if any.
+    pub fn get_base_allocation(env: Env) -> Option<AllocationSplit> {
+        env.storage().instance().get(&DataKey::BaseAllocation)
+    }
+
+    /// Returns the configured allocation for a risk bucket, if any.
+    pub fn get_risk_allocation(env: Env, risk_tolerance: u32) -> Option<AllocationSplit> {
+        env.storage()
+            .instance()
+            .get(&DataKey::RiskAllocation(risk_tolerance))
     }
 
     /// Returns the admin address.
@@ -287,6 +523,40 @@ impl BudgetRecommendationsContract {
             .unwrap_or(0)
     }
 
+    // Internal helper appending to a user's bounded recommendation history
+    fn append_user_history(env: &Env, user: &Address, recommendation: &BudgetRecommendation) {
+        let key = DataKey::UserRecommendationHistory(user.clone());
+        let mut history: Vec<BudgetRecommendation> = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or(Vec::new(env));
+
+        history.push_back(recommendation.clone());
+        while history.len() > MAX_RECOMMENDATION_HISTORY {
+            history.pop_front();
+        }
+
+        env.storage().persistent().set(&key, &history);
+    }
+
+    // Internal helper resolving the effective split for each risk bucket:
+    // a bucket override wins over the base split; unset buckets are omitted
+    fn load_allocation_splits(env: &Env) -> Map<u32, AllocationSplit> {
+        let base: Option<AllocationSplit> = env.storage().instance().get(&DataKey::BaseAllocation);
+        let mut splits = Map::new(env);
+        for risk_tolerance in 1..=5u32 {
+            let split: Option<AllocationSplit> = env
+                .storage()
+                .instance()
+                .get(&DataKey::RiskAllocation(risk_tolerance));
+            if let Some(split) = split.or_else(|| base.clone()) {
+                splits.set(risk_tolerance, split);
+            }
+        }
+        splits
+    }
+
     // Internal helper to verify admin
     fn require_admin(env: &Env, caller: &Address) {
         let admin: Address = env
@@ -7,12 +7,50 @@
 //! - Batches computations to reduce gas costs
 //! - Implements rule-based AI recommendations (deterministic for blockchain)
 
-use soroban_sdk::{Env, Symbol, Vec};
+use soroban_sdk::{Env, Map, Symbol, Vec};
 
 use crate::types::{
-    BatchRecommendationMetrics, BudgetRecommendation, RecommendationResult, UserProfile,
+    AllocationSplit, BatchRecommendationMetrics, BudgetRecommendation, BudgetVariance,
+    RecommendationResult, UserProfile, DEBT_PAYOFF_REALLOCATION_BPS, HIGH_DEBT_INCOME_MULTIPLE,
+    MAX_MONTHS_TO_GOAL,
 };
 
+/// Default savings share (in bps of disposable income) for a risk tolerance.
+///
+/// Conservative (1): 35%, Moderate (3): 25%, Aggressive (5): 15%.
+pub fn default_savings_bps(risk_tolerance: u32) -> u32 {
+    match risk_tolerance {
+        1 => 3500,
+        2 => 3000,
+        3 => 2500,
+        4 => 2000,
+        5 => 1500,
+        _ => 2500,
+    }
+}
+
+/// Projects how many months of `monthly_savings` are needed to reach `savings_goal`.
+///
+/// Returns 0 when there is no goal or no savings to reach it with, and caps
+/// the projection at `MAX_MONTHS_TO_GOAL`.
+pub fn months_to_goal(savings_goal: i128, monthly_savings: i128) -> u32 {
+    if savings_goal <= 0 || monthly_savings <= 0 {
+        return 0;
+    }
+
+    // Ceiling division without overflow
+    let mut months = savings_goal / monthly_savings;
+    if savings_goal % monthly_savings != 0 {
+        months += 1;
+    }
+
+    if months > MAX_MONTHS_TO_GOAL as i128 {
+        MAX_MONTHS_TO_GOAL
+    } else {
+        months as u32
+    }
+}
+
 /// Generates a budget recommendation for a single user.
 ///
 /// Uses rule-based AI logic to generate recommendations based on:
@@ -20,11 +58,16 @@ use crate::types::{
 /// - Savings balance
 /// - Risk tolerance
 /// - Spending patterns
+/// - Outstanding debt (high debt switches to a debt payoff strategy)
+///
+/// When `split` is provided its `savings_bps` replaces the default savings
+/// share for the profile's risk tolerance.
 ///
 /// This is deterministic and optimized for blockchain execution.
 pub fn generate_recommendation(
     env: &Env,
     profile: &UserProfile,
+    split: Option<AllocationSplit>,
 ) -> Result<BudgetRecommendation, Symbol> {
     // Calculate disposable income
     let disposable_income = profile
@@ -32,38 +75,51 @@ pub fn generate_recommendation(
         .checked_sub(profile.monthly_expenses)
         .unwrap_or(0);
 
-    // Determine recommendation type based on risk tolerance
-    let recommendation_type = match profile.risk_tolerance {
-        1 => Symbol::new(env, "conservative"),
-        2 => Symbol::new(env, "moderate_conservative"),
-        3 => Symbol::new(env, "moderate"),
-        4 => Symbol::new(env, "moderate_aggressive"),
-        5 => Symbol::new(env, "aggressive"),
-        _ => Symbol::new(env, "moderate"),
+    // High debt dominates the strategy regardless of risk tolerance
+    let debt_threshold = profile
+        .monthly_income
+        .saturating_mul(HIGH_DEBT_INCOME_MULTIPLE);
+    let high_debt = profile.outstanding_debt > debt_threshold;
+
+    // Determine recommendation type based on debt and risk tolerance
+    let recommendation_type = if high_debt {
+        Symbol::new(env, "debt_payoff")
+    } else {
+        match profile.risk_tolerance {
+            1 => Symbol::new(env, "conservative"),
+            2 => Symbol::new(env, "moderate_conservative"),
+            3 => Symbol::new(env, "moderate"),
+            4 => Symbol::new(env, "moderate_aggressive"),
+            5 => Symbol::new(env, "aggressive"),
+            _ => Symbol::new(env, "moderate"),
+        }
     };
 
-    // Calculate recommended savings percentage based on risk tolerance
-    // Conservative (1): 30-40% savings
-    // Moderate (3): 20-30% savings
-    // Aggressive (5): 10-20% savings
-    let savings_percentage = match profile.risk_tolerance {
-        1 => 35, // 35% of disposable income
-        2 => 30,
-        3 => 25,
-        4 => 20,
-        5 => 15,
-        _ => 25,
+    // Calculate recommended savings share based on risk tolerance,
+    // preferring the admin-configured split when one is set
+    let savings_bps = match split {
+        Some(split) => split.savings_bps,
+        None => default_savings_bps(profile.risk_tolerance),
     };
 
     // Calculate recommended budget (remaining after savings)
-    let recommended_savings = if disposable_income > 0 {
-        (disposable_income * savings_percentage as i128) / 100
+    let mut recommended_savings = if disposable_income > 0 {
+        (disposable_income * savings_bps as i128) / 10_000
     } else {
         0
     };
 
     let recommended_budget = profile.monthly_expenses + (disposable_income - recommended_savings);
 
+    // Redirect part of the savings toward debt payoff, leaving the budget unchanged
+    let recommended_debt_payment = if high_debt {
+        let payment = (recommended_savings * DEBT_PAYOFF_REALLOCATION_BPS) / 10_000;
+        recommended_savings -= payment;
+        payment
+    } else {
+        0
+    };
+
     // Calculate recommended spending limit (budget + small buffer)
     let buffer_percentage = 5; // 5% buffer
     let recommended_spending_limit =
@@ -103,24 +159,29 @@ pub fn generate_recommendation(
 
     // Generate recommendation notes
     let notes = if disposable_income < 0 {
-        Symbol::new(env, "expenses_exceed_income_review_needed")
+        Symbol::new(env, "expenses_exceed_income_review")
+    } else if high_debt {
+        Symbol::new(env, "prioritize_debt_payoff")
     } else if recommended_savings == 0 {
         Symbol::new(env, "minimal_savings_capacity")
     } else if profile.savings_balance < emergency_fund_target / 2 {
         Symbol::new(env, "build_emergency_fund_priority")
     } else {
-        Symbol::new(env, "on_track_continue_current_strategy")
+        Symbol::new(env, "on_track_continue_strategy")
     };
 
     Ok(BudgetRecommendation {
         user_id: profile.user_id,
         recommended_budget,
         recommended_savings,
+        recommended_debt_payment,
         recommended_spending_limit,
         emergency_fund_target,
+        months_to_goal: months_to_goal(profile.savings_goal, recommended_savings),
         confidence_score,
         recommendation_type,
         notes,
+        generated_at: env.ledger().sequence() as u64,
     })
 }
 
@@ -128,10 +189,15 @@ pub fn generate_recommendation(
 ///
 /// Optimized to perform a single pass over the user profiles,
 /// computing all recommendations in O(n) time complexity.
+///
+/// `splits` maps a risk tolerance to its configured allocation; buckets
+/// missing from the map use the default savings shares.
 pub fn generate_batch_recommendations(
     env: &Env,
     profiles: &Vec<UserProfile>,
+    splits: &Map<u32, AllocationSplit>,
     processed_at: u64,
+    min_confidence: u32,
 ) -> (Vec<RecommendationResult>, BatchRecommendationMetrics) {
     let user_count = profiles.len();
     let mut results: Vec<RecommendationResult> = Vec::new(env);
@@ -143,7 +209,14 @@ pub fn generate_batch_recommendations(
 
     // Process each user profile
     for profile in profiles.iter() {
-        match generate_recommendation(env, &profile) {
+        match generate_recommendation(env, &profile, splits.get(profile.risk_tolerance)) {
+            Ok(recommendation) if recommendation.confidence_score < min_confidence => {
+                failed_count += 1;
+                results.push_back(RecommendationResult::Failure(
+                    profile.user_id,
+                    Symbol::new(env, "low_confidence"),
+                ));
+            }
             Ok(recommendation) => {
                 // Accumulate metrics
                 total_recommended_budget = total_recommended_budget
@@ -184,10 +257,38 @@ pub fn generate_batch_recommendations(
     (results, metrics)
 }
 
+/// Compares `actual_spending` against the budget in `recommendation`.
+///
+/// The variance is reported both as an absolute amount and in basis points
+/// of the recommended budget. A zero (or negative) recommended budget has no
+/// meaningful ratio, so `variance_bps` is 0 in that case.
+pub fn compute_budget_variance(
+    recommendation: &BudgetRecommendation,
+    actual_spending: i128,
+) -> BudgetVariance {
+    let recommended_budget = recommendation.recommended_budget;
+    let variance_amount = actual_spending - recommended_budget;
+
+    let variance_bps = if recommended_budget > 0 {
+        variance_amount * 10000 / recommended_budget
+    } else {
+        0
+    };
+
+    BudgetVariance {
+        user_id: recommendation.user_id,
+        recommended_budget,
+        actual_spending,
+        variance_amount,
+        variance_bps,
+        is_over_budget: variance_amount > 0,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use soroban_sdk::{testutils::Address as _, Env, Symbol};
+    use soroban_sdk::{testutils::Address as _, Address, Env, Symbol};
 
     fn create_test_profile(env: &Env, user_id: u64, income: i128, expenses: i128) -> UserProfile {
         UserProfile {
@@ -196,8 +297,10 @@ mod tests {
             monthly_income: income,
             monthly_expenses: expenses,
             savings_balance: 100000,
-            spending_categories: Symbol::new(env, "food,transport"),
+            spending_categories: Symbol::new(env, "food_transport"),
             risk_tolerance: 3,
+            outstanding_debt: 0,
+            savings_goal: 0,
         }
     }
 
@@ -206,7 +309,7 @@ mod tests {
         let env = Env::default();
         let profile = create_test_profile(&env, 1, 100000, 50000);
 
-        let recommendation = generate_recommendation(&env, &profile).unwrap();
+        let recommendation = generate_recommendation(&env, &profile, None).unwrap();
 
         assert_eq!(recommendation.user_id, 1);
         assert!(recommendation.recommended_budget > 0);
@@ -220,7 +323,7 @@ mod tests {
         let mut profile = create_test_profile(&env, 1, 100000, 50000);
         profile.risk_tolerance = 1; // Conservative
 
-        let recommendation = generate_recommendation(&env, &profile).unwrap();
+        let recommendation = generate_recommendation(&env, &profile, None).unwrap();
 
         // Conservative should have higher savings percentage
         assert!(recommendation.recommended_savings > 0);
@@ -236,7 +339,7 @@ mod tests {
         let mut profile = create_test_profile(&env, 1, 100000, 50000);
         profile.risk_tolerance = 5; // Aggressive
 
-        let recommendation = generate_recommendation(&env, &profile).unwrap();
+        let recommendation = generate_recommendation(&env, &profile, None).unwrap();
 
         assert_eq!(
             recommendation.recommendation_type,
@@ -251,11 +354,98 @@ mod tests {
         profiles.push_back(create_test_profile(&env, 1, 100000, 50000));
         profiles.push_back(create_test_profile(&env, 2, 200000, 100000));
 
-        let (results, metrics) = generate_batch_recommendations(&env, &profiles, 100);
+        let (results, metrics) =
+            generate_batch_recommendations(&env, &profiles, &Map::new(&env), 100, 0);
 
         assert_eq!(results.len(), 2);
         assert_eq!(metrics.user_count, 2);
         assert_eq!(metrics.successful_recommendations, 2);
         assert_eq!(metrics.failed_recommendations, 0);
     }
+
+    #[test]
+    fn test_generate_recommendation_custom_split() {
+        let env = Env::default();
+        let profile = create_test_profile(&env, 1, 100000, 50000);
+        let split = AllocationSplit {
+            savings_bps: 4000,
+            needs_bps: 4000,
+            wants_bps: 2000,
+        };
+
+        let default_rec = generate_recommendation(&env, &profile, None).unwrap();
+        let custom_rec = generate_recommendation(&env, &profile, Some(split)).unwrap();
+
+        // Disposable income is 50000: default 25% vs configured 40%
+        assert_eq!(default_rec.recommended_savings, 12500);
+        assert_eq!(custom_rec.recommended_savings, 20000);
+    }
+
+    #[test]
+    fn test_months_to_goal() {
+        assert_eq!(months_to_goal(100000, 12500), 8);
+        assert_eq!(months_to_goal(100001, 12500), 9);
+        assert_eq!(months_to_goal(0, 12500), 0);
+        assert_eq!(months_to_goal(100000, 0), 0);
+        assert_eq!(months_to_goal(i128::MAX, 1), MAX_MONTHS_TO_GOAL);
+    }
+
+    #[test]
+    fn test_generate_recommendation_goal_projection() {
+        let env = Env::default();
+        let mut profile = create_test_profile(&env, 1, 100000, 50000);
+        profile.savings_goal = 100000;
+
+        let recommendation = generate_recommendation(&env, &profile, None).unwrap();
+
+        // 12500 per month toward a 100000 goal
+        assert_eq!(recommendation.months_to_goal, 8);
+    }
+
+    #[test]
+    fn test_generate_recommendation_goal_unreachable_without_savings() {
+        let env = Env::default();
+        let mut profile = create_test_profile(&env, 1, 50000, 60000);
+        profile.savings_goal = 100000;
+
+        let recommendation = generate_recommendation(&env, &profile, None).unwrap();
+
+        assert_eq!(recommendation.recommended_savings, 0);
+        assert_eq!(recommendation.months_to_goal, 0);
+    }
+
+    #[test]
+    fn test_generate_recommendation_high_debt() {
+        let env = Env::default();
+        let no_debt = create_test_profile(&env, 1, 100000, 50000);
+        let mut high_debt = no_debt.clone();
+        high_debt.outstanding_debt = 1_000_000;
+
+        let base = generate_recommendation(&env, &no_debt, None).unwrap();
+        let debt = generate_recommendation(&env, &high_debt, None).unwrap();
+
+        assert_eq!(base.recommended_debt_payment, 0);
+        assert_eq!(debt.recommendation_type, Symbol::new(&env, "debt_payoff"));
+        assert_eq!(debt.notes, Symbol::new(&env, "prioritize_debt_payoff"));
+        assert_eq!(
+            debt.recommended_savings + debt.recommended_debt_payment,
+            base.recommended_savings
+        );
+        assert!(debt.recommended_savings < base.recommended_savings);
+        assert_eq!(debt.recommended_budget, base.recommended_budget);
+    }
+
+    #[test]
+    fn test_compute_budget_variance_zero_budget() {
+        let env = Env::default();
+        let profile = create_test_profile(&env, 1, 100000, 50000);
+        let mut recommendation = generate_recommendation(&env, &profile, None).unwrap();
+        recommendation.recommended_budget = 0;
+
+        let variance = compute_budget_variance(&recommendation, 5000);
+
+        assert_eq!(variance.variance_amount, 5000);
+        assert_eq!(variance.variance_bps, 0);
+        assert!(variance.is_over_budget);
+    }
 }
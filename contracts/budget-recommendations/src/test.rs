@@ -3,11 +3,11 @@
 #![cfg(test)]
 
 use crate::{
-    BudgetRecommendationsContract, BudgetRecommendationsContractClient, RecommendationResult,
-    UserProfile,
+    AllocationSplit, BudgetRecommendationsContract, BudgetRecommendationsContractClient,
+    RecommendationResult, UserProfile, MAX_RECOMMENDATION_HISTORY,
 };
 use soroban_sdk::{
-    testutils::{Address as _, Events},
+    testutils::{Address as _, Events, Ledger},
     Address, Env, Symbol, Vec,
 };
 
@@ -32,7 +32,7 @@ fn create_user_profile(
     income: i128,
     expenses: i128,
     savings: i128,
-    risk_tolerance: u8,
+    risk_tolerance: u32,
 ) -> UserProfile {
     UserProfile {
         user_id,
@@ -40,8 +40,10 @@ fn create_user_profile(
         monthly_income: income,
         monthly_expenses: expenses,
         savings_balance: savings,
-        spending_categories: Symbol::new(env, "food,transport,utilities"),
+        spending_categories: Symbol::new(env, "food_transport_utilities"),
         risk_tolerance,
+        outstanding_debt: 0,
+        savings_goal: 0,
     }
 }
 
@@ -56,7 +58,7 @@ fn test_initialize_contract() {
     assert_eq!(client.get_admin(), admin);
     assert_eq!(client.get_last_batch_id(), 0);
     assert_eq!(client.get_total_users_processed(), 0);
-    assert_eq!(client.get_total_recommendations_generated(), 0);
+    assert_eq!(client.get_total_recommendations(), 0);
 }
 
 #[test]
@@ -175,17 +177,17 @@ fn test_generate_batch_recommendations_accumulates_stats() {
 
     assert_eq!(client.get_last_batch_id(), 0);
     assert_eq!(client.get_total_users_processed(), 0);
-    assert_eq!(client.get_total_recommendations_generated(), 0);
+    assert_eq!(client.get_total_recommendations(), 0);
 
     client.generate_batch_recommendations(&admin, &profiles1);
     assert_eq!(client.get_last_batch_id(), 1);
     assert_eq!(client.get_total_users_processed(), 1);
-    assert_eq!(client.get_total_recommendations_generated(), 1);
+    assert_eq!(client.get_total_recommendations(), 1);
 
     client.generate_batch_recommendations(&admin, &profiles2);
     assert_eq!(client.get_last_batch_id(), 2);
     assert_eq!(client.get_total_users_processed(), 2);
-    assert_eq!(client.get_total_recommendations_generated(), 2);
+    assert_eq!(client.get_total_recommendations(), 2);
 }
 
 #[test]
@@ -242,7 +244,7 @@ fn test_generate_batch_recommendations_low_income_scenario() {
     // Should have notes about expenses exceeding income
     assert_eq!(
         rec.notes,
-        Symbol::new(&env, "expenses_exceed_income_review_needed")
+        Symbol::new(&env, "expenses_exceed_income_review")
     );
 }
 
@@ -271,6 +273,71 @@ fn test_generate_batch_recommendations_emergency_fund_target() {
     assert!(rec1.emergency_fund_target > rec2.emergency_fund_target);
 }
 
+#[test]
+fn test_generate_batch_recommendations_filtered_by_confidence() {
+    let (env, admin, client) = setup_test_env();
+
+    let mut profiles: Vec<UserProfile> = Vec::new(&env);
+    // Positive income and savings: confidence 95
+    profiles.push_back(create_user_profile(&env, 1, 100000, 50000, 10000, 3));
+    // Positive income, no savings: confidence 90
+    profiles.push_back(create_user_profile(&env, 2, 100000, 50000, 0, 3));
+    // Expenses exceed income, no savings: confidence 60
+    profiles.push_back(create_user_profile(&env, 3, 50000, 80000, 0, 3));
+
+    let result = client.gen_filtered_recommendations(&admin, &profiles, &90);
+
+    assert_eq!(result.total_users, 3);
+    assert_eq!(result.successful, 3);
+    assert_eq!(result.results.len(), 2);
+    assert_eq!(result.filtered_out, 1);
+    for r in result.results.iter() {
+        match r {
+            RecommendationResult::Success(rec) => assert!(rec.confidence_score >= 90),
+            _ => panic!("Expected success"),
+        }
+    }
+
+    // The stored batch still contains every result
+    let stored = client.get_batch_recommendations(&result.batch_id).unwrap();
+    assert_eq!(stored.len(), 3);
+}
+
+#[test]
+fn test_filtered_recommendations_count_only_confidence_rejections() {
+    let (env, admin, client) = setup_test_env();
+
+    // Profiles under the contract-wide floor fail outright
+    client.set_min_confidence(&admin, &70);
+
+    let mut profiles: Vec<UserProfile> = Vec::new(&env);
+    // Positive income and savings: confidence 95
+    profiles.push_back(create_user_profile(&env, 1, 100000, 50000, 10000, 3));
+    // Positive income, no savings: confidence 90
+    profiles.push_back(create_user_profile(&env, 2, 100000, 50000, 0, 3));
+    // Expenses exceed income, no savings: confidence 60, below the floor
+    profiles.push_back(create_user_profile(&env, 3, 50000, 80000, 0, 3));
+
+    let result = client.gen_filtered_recommendations(&admin, &profiles, &95);
+
+    assert_eq!(result.successful, 2);
+    assert_eq!(result.failed, 1);
+    assert_eq!(result.results.len(), 1);
+    // The failed profile is reported in `failed`, not as filtered out
+    assert_eq!(result.filtered_out, 1);
+}
+
+#[test]
+fn test_generate_batch_recommendations_unfiltered_reports_zero_filtered() {
+    let (env, admin, client) = setup_test_env();
+
+    let mut profiles: Vec<UserProfile> = Vec::new(&env);
+    profiles.push_back(create_user_profile(&env, 1, 100000, 50000, 10000, 3));
+
+    let result = client.generate_batch_recommendations(&admin, &profiles);
+    assert_eq!(result.filtered_out, 0);
+}
+
 // ============================================================================
 // Validation Tests
 // ============================================================================
@@ -287,7 +354,7 @@ fn test_generate_batch_recommendations_empty_batch() {
 #[test]
 #[should_panic]
 fn test_generate_batch_recommendations_unauthorized() {
-    let (env, admin, client) = setup_test_env();
+    let (env, _admin, client) = setup_test_env();
 
     let mut profiles: Vec<UserProfile> = Vec::new(&env);
     profiles.push_back(create_user_profile(&env, 1, 100000, 50000, 10000, 3));
@@ -332,7 +399,7 @@ fn test_simulate_recommendation() {
 
 #[test]
 fn test_simulate_recommendation_no_storage() {
-    let (env, admin, client) = setup_test_env();
+    let (env, _admin, client) = setup_test_env();
 
     let profile = create_user_profile(&env, 1, 100000, 50000, 10000, 3);
 
@@ -344,6 +411,225 @@ fn test_simulate_recommendation_no_storage() {
     assert_eq!(batch_id_before, batch_id_after);
 }
 
+// ============================================================================
+// Debt Payoff Tests
+// ============================================================================
+
+#[test]
+fn test_high_debt_profile_gets_debt_payoff_plan() {
+    let (env, _admin, client) = setup_test_env();
+
+    let no_debt = create_user_profile(&env, 1, 100000, 50000, 10000, 3);
+    let mut high_debt = no_debt.clone();
+    high_debt.outstanding_debt = 500000;
+
+    let base = client.simulate_recommendation(&no_debt).unwrap();
+    let debt = client.simulate_recommendation(&high_debt).unwrap();
+
+    assert_eq!(base.recommendation_type, Symbol::new(&env, "moderate"));
+    assert_eq!(debt.recommendation_type, Symbol::new(&env, "debt_payoff"));
+    assert_eq!(debt.notes, Symbol::new(&env, "prioritize_debt_payoff"));
+    assert_eq!(base.recommended_debt_payment, 0);
+    assert_eq!(debt.recommended_debt_payment, 6250);
+    assert_eq!(debt.recommended_savings, 6250);
+}
+
+#[test]
+fn test_low_debt_profile_keeps_risk_strategy() {
+    let (env, _admin, client) = setup_test_env();
+
+    let mut profile = create_user_profile(&env, 1, 100000, 50000, 10000, 3);
+    profile.outstanding_debt = 300000;
+
+    let rec = client.simulate_recommendation(&profile).unwrap();
+
+    assert_eq!(rec.recommendation_type, Symbol::new(&env, "moderate"));
+    assert_eq!(rec.recommended_debt_payment, 0);
+    assert_eq!(rec.recommended_savings, 12500);
+}
+
+#[test]
+fn test_simulate_recommendation_months_to_goal() {
+    let (env, _admin, client) = setup_test_env();
+
+    let mut profile = create_user_profile(&env, 1, 100000, 50000, 10000, 3);
+    profile.savings_goal = 60000;
+
+    let rec = client.simulate_recommendation(&profile).unwrap();
+
+    // 12500 per month: ceil(60000 / 12500) = 5
+    assert_eq!(rec.recommended_savings, 12500);
+    assert_eq!(rec.months_to_goal, 5);
+}
+
+// ============================================================================
+// Recommendation History Tests
+// ============================================================================
+
+#[test]
+fn test_user_recommendation_history_across_batches() {
+    let (env, admin, client) = setup_test_env();
+
+    let mut profile = create_user_profile(&env, 1, 100000, 50000, 10000, 3);
+    let mut profiles: Vec<UserProfile> = Vec::new(&env);
+    profiles.push_back(profile.clone());
+    client.generate_batch_recommendations(&admin, &profiles);
+
+    env.ledger().with_mut(|li| li.sequence_number += 10);
+    profile.monthly_income = 200000;
+    let mut profiles: Vec<UserProfile> = Vec::new(&env);
+    profiles.push_back(profile.clone());
+    client.generate_batch_recommendations(&admin, &profiles);
+
+    let history = client.get_user_recommendation_history(&profile.address);
+    assert_eq!(history.len(), 2);
+
+    let first = history.get(0).unwrap();
+    let second = history.get(1).unwrap();
+    assert!(first.generated_at < second.generated_at);
+    assert!(first.recommended_budget < second.recommended_budget);
+}
+
+#[test]
+fn test_user_recommendation_history_is_capped() {
+    let (env, admin, client) = setup_test_env();
+
+    let profile = create_user_profile(&env, 1, 100000, 50000, 10000, 3);
+    for _ in 0..(MAX_RECOMMENDATION_HISTORY + 5) {
+        env.ledger().with_mut(|li| li.sequence_number += 1);
+        let mut profiles: Vec<UserProfile> = Vec::new(&env);
+        profiles.push_back(profile.clone());
+        client.generate_batch_recommendations(&admin, &profiles);
+    }
+
+    let history = client.get_user_recommendation_history(&profile.address);
+    assert_eq!(history.len(), MAX_RECOMMENDATION_HISTORY);
+    // Oldest five entries were evicted
+    assert_eq!(
+        history.get(0).unwrap().generated_at,
+        env.ledger().sequence() as u64 - (MAX_RECOMMENDATION_HISTORY as u64 - 1)
+    );
+}
+
+#[test]
+fn test_recommendation_becomes_stale_after_ttl() {
+    let (env, admin, client) = setup_test_env();
+    client.set_recommendation_ttl(&admin, &100);
+    assert_eq!(client.get_recommendation_ttl(), 100);
+
+    let profile = create_user_profile(&env, 1, 100000, 50000, 10000, 3);
+    let mut profiles: Vec<UserProfile> = Vec::new(&env);
+    profiles.push_back(profile.clone());
+    client.generate_batch_recommendations(&admin, &profiles);
+
+    let recommendation = client.get_fresh_recommendation(&profile.address).unwrap();
+    assert!(!client.is_recommendation_stale(&recommendation));
+
+    // Still fresh on the last ledger of the window
+    env.ledger().with_mut(|li| li.sequence_number += 100);
+    assert!(!client.is_recommendation_stale(&recommendation));
+
+    env.ledger().with_mut(|li| li.sequence_number += 1);
+    assert!(client.is_recommendation_stale(&recommendation));
+    assert!(client.get_fresh_recommendation(&profile.address).is_none());
+}
+
+#[test]
+fn test_recommendation_never_stale_without_ttl() {
+    let (env, admin, client) = setup_test_env();
+
+    let profile = create_user_profile(&env, 1, 100000, 50000, 10000, 3);
+    let mut profiles: Vec<UserProfile> = Vec::new(&env);
+    profiles.push_back(profile.clone());
+    client.generate_batch_recommendations(&admin, &profiles);
+
+    env.ledger().with_mut(|li| li.sequence_number += 4_000);
+
+    assert!(client.get_fresh_recommendation(&profile.address).is_some());
+    assert!(client
+        .get_fresh_recommendation(&Address::generate(&env))
+        .is_none());
+}
+
+#[test]
+fn test_user_recommendation_history_empty_for_unknown_user() {
+    let (env, _admin, client) = setup_test_env();
+
+    let history = client.get_user_recommendation_history(&Address::generate(&env));
+    assert_eq!(history.len(), 0);
+}
+
+// ============================================================================
+// Allocation Configuration Tests
+// ============================================================================
+
+fn split(savings_bps: u32, needs_bps: u32, wants_bps: u32) -> AllocationSplit {
+    AllocationSplit {
+        savings_bps,
+        needs_bps,
+        wants_bps,
+    }
+}
+
+#[test]
+fn test_base_allocation_changes_recommended_savings() {
+    let (env, admin, client) = setup_test_env();
+
+    // Disposable income = 50000; default moderate savings share is 25%
+    let profile = create_user_profile(&env, 1, 100000, 50000, 10000, 3);
+    let before = client.simulate_recommendation(&profile).unwrap();
+    assert_eq!(before.recommended_savings, 12500);
+
+    client.set_base_allocation(&admin, &split(4000, 4000, 2000));
+    assert_eq!(client.get_base_allocation(), Some(split(4000, 4000, 2000)));
+
+    let after = client.simulate_recommendation(&profile).unwrap();
+    assert_eq!(after.recommended_savings, 20000);
+}
+
+#[test]
+fn test_risk_allocation_overrides_base() {
+    let (env, admin, client) = setup_test_env();
+
+    client.set_base_allocation(&admin, &split(4000, 4000, 2000));
+    client.set_risk_allocation(&admin, &5, &split(1000, 5000, 4000));
+
+    let mut profiles: Vec<UserProfile> = Vec::new(&env);
+    profiles.push_back(create_user_profile(&env, 1, 100000, 50000, 10000, 3));
+    profiles.push_back(create_user_profile(&env, 2, 100000, 50000, 10000, 5));
+
+    let result = client.generate_batch_recommendations(&admin, &profiles);
+
+    let savings: Vec<i128> = {
+        let mut out = Vec::new(&env);
+        for r in result.results.iter() {
+            match r {
+                RecommendationResult::Success(rec) => out.push_back(rec.recommended_savings),
+                _ => panic!("Expected success"),
+            }
+        }
+        out
+    };
+    assert_eq!(savings.get(0).unwrap(), 20000);
+    assert_eq!(savings.get(1).unwrap(), 5000);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #7)")]
+fn test_set_base_allocation_rejects_non_summing_split() {
+    let (_env, admin, client) = setup_test_env();
+
+    client.set_base_allocation(&admin, &split(2000, 5000, 2000));
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #8)")]
+fn test_set_risk_allocation_rejects_invalid_bucket() {
+    let (_env, admin, client) = setup_test_env();
+
+    client.set_risk_allocation(&admin, &6, &split(2000, 5000, 3000));
+}
+
 // ============================================================================
 // Admin Tests
 // ============================================================================
@@ -361,7 +647,7 @@ fn test_set_admin() {
 #[test]
 #[should_panic]
 fn test_set_admin_unauthorized() {
-    let (env, admin, client) = setup_test_env();
+    let (env, _admin, client) = setup_test_env();
 
     let unauthorized = Address::generate(&env);
     let new_admin = Address::generate(&env);
@@ -428,3 +714,84 @@ fn test_generate_batch_recommendations_multiple_simultaneous_batches() {
     assert_eq!(result2.batch_id, 2);
     assert_eq!(client.get_total_users_processed(), 2);
 }
+
+#[test]
+fn test_compute_budget_variance_over_budget() {
+    let (env, _admin, client) = setup_test_env();
+    let profile = create_user_profile(&env, 1, 100000, 50000, 10000, 3);
+    let recommendation = client.simulate_recommendation(&profile).unwrap();
+    let budget = recommendation.recommended_budget;
+
+    let variance = client.compute_budget_variance(&recommendation, &(budget + budget / 10));
+
+    assert_eq!(variance.user_id, 1);
+    assert_eq!(variance.variance_amount, budget / 10);
+    assert_eq!(variance.variance_bps, (budget / 10) * 10000 / budget);
+    assert!(variance.is_over_budget);
+}
+
+#[test]
+fn test_compute_budget_variance_under_budget() {
+    let (env, _admin, client) = setup_test_env();
+    let profile = create_user_profile(&env, 1, 100000, 50000, 10000, 3);
+    let recommendation = client.simulate_recommendation(&profile).unwrap();
+    let budget = recommendation.recommended_budget;
+
+    let variance = client.compute_budget_variance(&recommendation, &(budget / 2));
+
+    assert_eq!(variance.variance_amount, budget / 2 - budget);
+    assert!(variance.variance_bps < 0);
+    assert!(!variance.is_over_budget);
+}
+
+#[test]
+fn test_compute_budget_variance_on_budget() {
+    let (env, _admin, client) = setup_test_env();
+    let profile = create_user_profile(&env, 1, 100000, 50000, 10000, 3);
+    let recommendation = client.simulate_recommendation(&profile).unwrap();
+
+    let variance =
+        client.compute_budget_variance(&recommendation, &recommendation.recommended_budget);
+
+    assert_eq!(variance.variance_amount, 0);
+    assert_eq!(variance.variance_bps, 0);
+    assert!(!variance.is_over_budget);
+}
+
+#[test]
+fn test_min_confidence_rejects_low_confidence_profiles() {
+    let (env, admin, client) = setup_test_env();
+
+    let mut profiles: Vec<UserProfile> = Vec::new(&env);
+    // Disposable income and savings: confidence 95
+    profiles.push_back(create_user_profile(&env, 1, 100000, 50000, 10000, 3));
+    // Expenses above income and no savings: confidence 60
+    profiles.push_back(create_user_profile(&env, 2, 100000, 150000, 0, 3));
+
+    // The default floor of zero accepts both
+    assert_eq!(client.get_min_confidence(), 0);
+    let result = client.generate_batch_recommendations(&admin, &profiles);
+    assert_eq!(result.successful, 2);
+
+    client.set_min_confidence(&admin, &70);
+    let result = client.generate_batch_recommendations(&admin, &profiles);
+
+    assert_eq!(result.successful, 1);
+    assert_eq!(result.failed, 1);
+    assert_eq!(result.metrics.avg_confidence_score, 95);
+    match result.results.get(1).unwrap() {
+        RecommendationResult::Failure(user_id, reason) => {
+            assert_eq!(user_id, 2);
+            assert_eq!(reason, Symbol::new(&env, "low_confidence"));
+        }
+        RecommendationResult::Success(_) => panic!("Expected low confidence failure"),
+    }
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #9)")]
+fn test_set_min_confidence_rejects_above_100() {
+    let (_env, admin, client) = setup_test_env();
+
+    client.set_min_confidence(&admin, &101);
+}
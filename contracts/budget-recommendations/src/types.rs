@@ -5,6 +5,18 @@ use soroban_sdk::{contracttype, symbol_short, Address, Env, Symbol, Vec};
 /// Maximum number of users in a single batch for optimization.
 pub const MAX_BATCH_SIZE: u32 = 100;
 
+/// Debt above this multiple of monthly income switches to a debt payoff strategy.
+pub const HIGH_DEBT_INCOME_MULTIPLE: i128 = 3;
+
+/// Share of recommended savings (in bps) redirected to debt payoff for high-debt users.
+pub const DEBT_PAYOFF_REALLOCATION_BPS: i128 = 5_000;
+
+/// Upper bound reported for `months_to_goal` (100 years).
+pub const MAX_MONTHS_TO_GOAL: u32 = 1200;
+
+/// Maximum number of recommendations retained per user (oldest evicted first).
+pub const MAX_RECOMMENDATION_HISTORY: u32 = 20;
+
 /// Represents a user's financial profile for budget recommendations.
 #[derive(Clone, Debug)]
 #[contracttype]
@@ -23,6 +35,10 @@ pub struct UserProfile {
     pub spending_categories: Symbol,
     /// Risk tolerance level (1-5, where 1 is conservative, 5 is aggressive)
     pub risk_tolerance: u32,
+    /// Outstanding debt balance in stroops (0 when the user has no debt)
+    pub outstanding_debt: i128,
+    /// Savings goal in stroops (0 when the user has no goal)
+    pub savings_goal: i128,
 }
 
 /// Represents a budget recommendation for a user.
@@ -35,16 +51,23 @@ pub struct BudgetRecommendation {
     pub recommended_budget: i128,
     /// Recommended savings amount per month in stroops
     pub recommended_savings: i128,
+    /// Recommended monthly debt payment in stroops (0 unless on a debt payoff plan)
+    pub recommended_debt_payment: i128,
     /// Recommended spending limit per month in stroops
     pub recommended_spending_limit: i128,
     /// Recommended emergency fund target in stroops
     pub emergency_fund_target: i128,
+    /// Months of recommended savings needed to reach the savings goal
+    /// (0 when there is no goal or it is unreachable)
+    pub months_to_goal: u32,
     /// Confidence score (0-100, where 100 is highest confidence)
     pub confidence_score: u32,
-    /// Recommendation category (e.g., "conservative", "moderate", "aggressive")
+    /// Recommendation category (e.g., "conservative", "moderate", "aggressive", "debt_payoff")
     pub recommendation_type: Symbol,
     /// Additional recommendation notes
     pub notes: Symbol,
+    /// Ledger sequence at which the recommendation was generated
+    pub generated_at: u64,
 }
 
 /// Aggregated metrics for a batch of recommendations.
@@ -67,6 +90,36 @@ pub struct BatchRecommendationMetrics {
     pub processed_at: u64,
 }
 
+/// Savings/needs/wants allocation expressed in basis points (must sum to 10000).
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct AllocationSplit {
+    /// Share of disposable income directed to savings
+    pub savings_bps: u32,
+    /// Share allocated to essential spending
+    pub needs_bps: u32,
+    /// Share allocated to discretionary spending
+    pub wants_bps: u32,
+}
+
+/// Comparison of actual spending against a recommended budget.
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct BudgetVariance {
+    /// User ID the recommendation belongs to
+    pub user_id: u64,
+    /// Budget from the recommendation
+    pub recommended_budget: i128,
+    /// Spending actually recorded for the period
+    pub actual_spending: i128,
+    /// Actual minus recommended (positive means over budget)
+    pub variance_amount: i128,
+    /// Variance relative to the recommended budget in basis points
+    pub variance_bps: i128,
+    /// Whether spending exceeded the recommended budget
+    pub is_over_budget: bool,
+}
+
 /// Result of processing a single user's recommendation.
 #[derive(Clone, Debug)]
 #[contracttype]
@@ -89,6 +142,8 @@ pub struct BatchRecommendationResult {
     pub failed: u32,
     /// Individual recommendation results
     pub results: Vec<RecommendationResult>,
+    /// Number of results omitted from `results` by a confidence filter
+    pub filtered_out: u32,
     /// Aggregated metrics
     pub metrics: BatchRecommendationMetrics,
 }
@@ -107,6 +162,16 @@ pub enum DataKey {
     TotalUsersProcessed,
     /// Total recommendations generated lifetime
     TotalRecommendationsGenerated,
+    /// Admin-configured allocation applied to every risk bucket without an override
+    BaseAllocation,
+    /// Admin-configured allocation for a specific risk tolerance (1-5)
+    RiskAllocation(u32),
+    /// Most recent recommendations generated for a user address
+    UserRecommendationHistory(Address),
+    /// Ledgers a recommendation stays fresh after generation (0 means never stale)
+    RecommendationTtl,
+    /// Minimum confidence score (0-100) a recommendation needs to succeed
+    MinConfidence,
 }
 
 /// Events emitted by the budget recommendations contract.
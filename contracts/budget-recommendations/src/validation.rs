@@ -2,10 +2,11 @@
 
 use soroban_sdk::{Env, Vec};
 
-use crate::types::UserProfile;
+use crate::types::{AllocationSplit, UserProfile};
 
 /// Validation error types.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[allow(clippy::enum_variant_names)]
 pub enum ValidationError {
     /// Invalid user ID
     InvalidUserId,
@@ -17,6 +18,12 @@ pub enum ValidationError {
     InvalidSavings,
     /// Invalid risk tolerance
     InvalidRiskTolerance,
+    /// Invalid outstanding debt
+    InvalidDebt,
+    /// Invalid savings goal
+    InvalidSavingsGoal,
+    /// Allocation split does not sum to 10000 bps
+    InvalidAllocationSplit,
 }
 
 /// Validates a user profile for budget recommendations.
@@ -52,12 +59,31 @@ pub fn validate_user_profile(_env: &Env, profile: &UserProfile) -> Result<(), Va
         return Err(ValidationError::InvalidRiskTolerance);
     }
 
+    // Validate debt (must be non-negative)
+    if profile.outstanding_debt < 0 {
+        return Err(ValidationError::InvalidDebt);
+    }
+
+    // Validate savings goal (must be non-negative)
+    if profile.savings_goal < 0 {
+        return Err(ValidationError::InvalidSavingsGoal);
+    }
+
     // Validate that expenses don't exceed income (warning case, but allow for debt scenarios)
     // We'll allow this but flag it in recommendations
 
     Ok(())
 }
 
+/// Validates that an allocation split sums to exactly 10000 basis points.
+pub fn validate_allocation_split(split: &AllocationSplit) -> Result<(), ValidationError> {
+    let total = (split.savings_bps as u64) + (split.needs_bps as u64) + (split.wants_bps as u64);
+    if total != 10_000 {
+        return Err(ValidationError::InvalidAllocationSplit);
+    }
+    Ok(())
+}
+
 /// Validates a batch of user profiles.
 ///
 /// Returns Ok(()) if all profiles are valid, or an error message if any are invalid.
@@ -75,7 +101,7 @@ pub fn validate_batch(profiles: &Vec<UserProfile>) -> Result<(), &'static str> {
     // Validate each profile
     let env = Env::default(); // Note: In production, pass env as parameter
     for profile in profiles.iter() {
-        if let Err(_) = validate_user_profile(&env, &profile) {
+        if validate_user_profile(&env, &profile).is_err() {
             return Err("Invalid user profile in batch");
         }
     }
@@ -86,7 +112,7 @@ pub fn validate_batch(profiles: &Vec<UserProfile>) -> Result<(), &'static str> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use soroban_sdk::{testutils::Address as _, Env, Symbol};
+    use soroban_sdk::{testutils::Address as _, Address, Env, Symbol};
 
     fn create_test_profile(env: &Env, user_id: u64, income: i128, expenses: i128) -> UserProfile {
         UserProfile {
@@ -95,8 +121,10 @@ mod tests {
             monthly_income: income,
             monthly_expenses: expenses,
             savings_balance: 0,
-            spending_categories: Symbol::new(env, "food,transport"),
+            spending_categories: Symbol::new(env, "food_transport"),
             risk_tolerance: 3,
+            outstanding_debt: 0,
+            savings_goal: 0,
         }
     }
 
@@ -149,4 +177,35 @@ mod tests {
             Err(ValidationError::InvalidRiskTolerance)
         );
     }
+
+    #[test]
+    fn test_validate_user_profile_invalid_debt() {
+        let env = Env::default();
+        let mut profile = create_test_profile(&env, 1, 100000, 50000);
+        profile.outstanding_debt = -1;
+        assert_eq!(
+            validate_user_profile(&env, &profile),
+            Err(ValidationError::InvalidDebt)
+        );
+    }
+
+    #[test]
+    fn test_validate_allocation_split() {
+        let valid = AllocationSplit {
+            savings_bps: 2000,
+            needs_bps: 5000,
+            wants_bps: 3000,
+        };
+        assert!(validate_allocation_split(&valid).is_ok());
+
+        let invalid = AllocationSplit {
+            savings_bps: 2000,
+            needs_bps: 5000,
+            wants_bps: 2000,
+        };
+        assert_eq!(
+            validate_allocation_split(&invalid),
+            Err(ValidationError::InvalidAllocationSplit)
+        );
+    }
 }
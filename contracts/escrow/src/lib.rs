@@ -7,13 +7,21 @@
 mod types;
 mod validation;
 
-use soroban_sdk::{contract, contractimpl, panic_with_error, token, Address, Env, Vec};
+use soroban_sdk::{
+    contract, contractimpl, panic_with_error, symbol_short, token, Address, Env, Map, Symbol,
+    TryFromVal, Val, Vec,
+};
 
 pub use crate::types::{
-    BatchReversalResult, DataKey, Escrow, EscrowEvents, EscrowStatus, ReversalRequest,
-    ReversalResult, MAX_BATCH_SIZE,
+    AnalyticsTransaction, BatchDeadlineResult, BatchReversalResult, ConfirmationOracle,
+    ConfirmationOracleClient, DataKey, DeadlineUpdate, DeadlineUpdateResult, Escrow, EscrowEvents,
+    EscrowRequest, EscrowStatus, EscrowV0, EscrowV1, ReleaseResult, ReversalRequest,
+    ReversalResult, SettlementResult, ESCROW_VERSION, MAX_BATCH_SIZE, STATUS_PAGE_SIZE,
+};
+use crate::validation::{
+    validate_amount_bounds, validate_deadline_extension, validate_escrow_request, validate_release,
+    validate_reversal, ValidationError,
 };
-use crate::validation::validate_reversal;
 
 /// Error codes for the escrow contract.
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
@@ -33,6 +41,28 @@ pub enum EscrowError {
     EscrowNotFound = 6,
     /// Contract already initialized
     AlreadyInitialized = 7,
+    /// At least one request in an atomic batch is invalid
+    AtomicBatchFailed = 8,
+    /// Escrow is not active
+    EscrowNotActive = 9,
+    /// No release has been requested by the recipient
+    ReleaseNotRequested = 10,
+    /// No confirmation oracle has been configured
+    OracleNotConfigured = 11,
+    /// The oracle has not confirmed delivery
+    NotConfirmed = 12,
+    /// Escrow creation and reversal are paused
+    Paused = 13,
+    /// Escrow amount is below the configured minimum
+    BelowMinAmount = 14,
+    /// Escrow amount is above the configured maximum
+    AboveMaxAmount = 15,
+    /// Fee basis points exceed 10000
+    InvalidFee = 16,
+    /// Deadline is closer than the configured minimum horizon
+    DeadlineTooSoon = 17,
+    /// Escrow is not under dispute
+    NotDisputed = 18,
 }
 
 impl From<EscrowError> for soroban_sdk::Error {
@@ -69,20 +99,27 @@ impl EscrowContract {
     /// Creates a new escrow.
     ///
     /// Locks funds from the depositor until released to recipient or reversed.
+    /// An optional `arbiter` may release or reverse this escrow alongside the admin.
     pub fn create_escrow(
         env: Env,
         depositor: Address,
         recipient: Address,
         amount: i128,
         deadline: u64,
+        arbiter: Option<Address>,
     ) -> u64 {
         // Verify depositor authorization
         depositor.require_auth();
+        Self::require_not_paused(&env);
 
         // Validate amount
         if amount <= 0 {
             panic_with_error!(&env, EscrowError::InvalidAmount);
         }
+        Self::check_amount_bounds(&env, amount);
+        if !Self::meets_deadline_horizon(&env, deadline) {
+            panic_with_error!(&env, EscrowError::DeadlineTooSoon);
+        }
 
         // Get token and transfer funds to contract
         let token: Address = env
@@ -95,49 +132,83 @@ impl EscrowContract {
         // Transfer funds from depositor to this contract
         token_client.transfer(&depositor, &env.current_contract_address(), &amount);
 
-        // Get and increment escrow counter
-        let escrow_id: u64 = env
-            .storage()
-            .instance()
-            .get(&DataKey::EscrowCounter)
-            .unwrap_or(0)
-            + 1;
-        env.storage()
-            .instance()
-            .set(&DataKey::EscrowCounter, &escrow_id);
+        Self::store_new_escrow(
+            &env, &depositor, &recipient, &token, amount, deadline, arbiter,
+        )
+    }
 
-        // Create escrow record
-        let escrow = Escrow {
-            escrow_id,
-            depositor: depositor.clone(),
-            recipient: recipient.clone(),
-            token: token.clone(),
-            amount,
-            status: EscrowStatus::Active,
-            created_at: env.ledger().sequence() as u64,
-            deadline,
-        };
+    /// Creates multiple escrows for one depositor on an all-or-nothing basis.
+    ///
+    /// Every request is validated (amount, deadline, self-escrow) before any
+    /// funds move. If a single request is invalid the whole call fails with
+    /// `AtomicBatchFailed` and no escrow is created.
+    ///
+    /// # Arguments
+    /// * `depositor` - The address funding every escrow in the batch
+    /// * `requests` - Vector of escrow creation requests
+    ///
+    /// # Returns
+    /// * `Vec<u64>` - IDs of the created escrows, in request order
+    pub fn batch_create_escrows_atomic(
+        env: Env,
+        depositor: Address,
+        requests: Vec<EscrowRequest>,
+    ) -> Vec<u64> {
+        depositor.require_auth();
+        Self::require_not_paused(&env);
 
-        // Store escrow
-        env.storage()
-            .persistent()
-            .set(&DataKey::Escrow(escrow_id), &escrow);
+        let request_count = requests.len();
+        if request_count == 0 {
+            panic_with_error!(&env, EscrowError::EmptyBatch);
+        }
+        if request_count > MAX_BATCH_SIZE {
+            panic_with_error!(&env, EscrowError::BatchTooLarge);
+        }
 
-        // Update user escrows list
-        let mut user_escrows: Vec<u64> = env
+        // Validate every request before touching funds
+        let current_ledger = env.ledger().sequence() as u64;
+        let (min_amount, max_amount) = Self::get_escrow_bounds(env.clone());
+        let mut total_amount: i128 = 0;
+        for request in requests.iter() {
+            if validate_escrow_request(&request, &depositor, current_ledger).is_err()
+                || validate_amount_bounds(request.amount, min_amount, max_amount).is_err()
+                || !Self::meets_deadline_horizon(&env, request.deadline)
+            {
+                panic_with_error!(&env, EscrowError::AtomicBatchFailed);
+            }
+            total_amount = match total_amount.checked_add(request.amount) {
+                Some(total) => total,
+                None => panic_with_error!(&env, EscrowError::InvalidAmount),
+            };
+        }
+
+        let token: Address = env
             .storage()
-            .persistent()
-            .get(&DataKey::UserEscrows(depositor.clone()))
-            .unwrap_or(Vec::new(&env));
-        user_escrows.push_back(escrow_id);
-        env.storage()
-            .persistent()
-            .set(&DataKey::UserEscrows(depositor.clone()), &user_escrows);
+            .instance()
+            .get(&DataKey::Token)
+            .expect("Contract not initialized");
+        let token_client = token::Client::new(&env, &token);
 
-        // Emit event
-        EscrowEvents::escrow_created(&env, escrow_id, &depositor, &recipient, amount);
+        // Lock the combined amount in a single transfer
+        token_client.transfer(&depositor, &env.current_contract_address(), &total_amount);
 
-        escrow_id
+        let mut escrow_ids: Vec<u64> = Vec::new(&env);
+        for request in requests.iter() {
+            let escrow_id = Self::store_new_escrow(
+                &env,
+                &depositor,
+                &request.recipient,
+                &token,
+                request.amount,
+                request.deadline,
+                None,
+            );
+            escrow_ids.push_back(escrow_id);
+        }
+
+        EscrowEvents::atomic_batch_created(&env, &depositor, request_count, total_amount);
+
+        escrow_ids
     }
 
     /// Batch reverses multiple escrows.
@@ -146,7 +217,7 @@ impl EscrowContract {
     /// It validates each reversal, handles partial failures, and emits events.
     ///
     /// # Arguments
-    /// * `caller` - The address initiating the reversal (must be admin)
+    /// * `caller` - The address initiating the reversal (admin, or arbiter of every escrow)
     /// * `requests` - Vector of reversal requests containing escrow IDs
     ///
     /// # Returns
@@ -158,7 +229,8 @@ impl EscrowContract {
     ) -> BatchReversalResult {
         // Verify authorization
         caller.require_auth();
-        Self::require_admin(&env, &caller);
+        Self::require_admin_or_arbiter(&env, &caller, &requests);
+        Self::require_not_paused(&env);
 
         // Validate batch size
         let request_count = requests.len();
@@ -169,159 +241,175 @@ impl EscrowContract {
             panic_with_error!(&env, EscrowError::BatchTooLarge);
         }
 
-        // Get batch ID
-        let batch_id: u64 = env
-            .storage()
-            .instance()
-            .get(&DataKey::TotalReversalBatches)
-            .unwrap_or(0)
-            + 1;
+        Self::reverse_escrows(&env, &caller, &requests)
+    }
 
-        // Get admin and token for validation
-        let admin: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::Admin)
-            .expect("Contract not initialized");
-        let token: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::Token)
-            .expect("Contract not initialized");
-        let token_client = token::Client::new(&env, &token);
+    /// Releases and reverses escrows in a single settlement call.
+    ///
+    /// Releases are processed first, then reversals. An invalid entry in either
+    /// list is recorded as a failure without aborting the rest of the settlement.
+    ///
+    /// # Arguments
+    /// * `caller` - The address settling the escrows (must be admin)
+    /// * `releases` - IDs of escrows to release to their recipients
+    /// * `reversals` - Reversal requests for escrows to return to depositors
+    ///
+    /// # Returns
+    /// * `SettlementResult` with per-escrow release results and the reversal sub-result
+    ///
+    /// # Errors
+    /// * `EmptyBatch` - If both lists are empty
+    /// * `BatchTooLarge` - If the combined count exceeds `MAX_BATCH_SIZE`
+    /// * `Paused` - If reversals are requested while the contract is paused
+    pub fn settle_batch(
+        env: Env,
+        caller: Address,
+        releases: Vec<u64>,
+        reversals: Vec<ReversalRequest>,
+    ) -> SettlementResult {
+        caller.require_auth();
+        Self::require_admin(&env, &caller);
+        // Releases stay available while paused; only the reversal half is blocked
+        if !reversals.is_empty() {
+            Self::require_not_paused(&env);
+        }
 
-        let current_ledger = env.ledger().sequence() as u64;
+        let total_count = releases.len() + reversals.len();
+        if total_count == 0 {
+            panic_with_error!(&env, EscrowError::EmptyBatch);
+        }
+        if total_count > MAX_BATCH_SIZE {
+            panic_with_error!(&env, EscrowError::BatchTooLarge);
+        }
 
-        // Emit batch started event
-        EscrowEvents::batch_reversal_started(&env, batch_id, request_count);
+        let mut release_results: Vec<ReleaseResult> = Vec::new(&env);
+        let mut successful_releases: u32 = 0;
+        let mut failed_releases: u32 = 0;
+        let mut total_released: i128 = 0;
 
-        // Initialize result tracking
-        let mut results: Vec<ReversalResult> = Vec::new(&env);
-        let mut successful_count: u32 = 0;
-        let mut failed_count: u32 = 0;
-        let mut total_reversed: i128 = 0;
+        for escrow_id in releases.iter() {
+            let escrow_opt: Option<Escrow> =
+                env.storage().persistent().get(&DataKey::Escrow(escrow_id));
 
-        // First pass: validate all requests
-        let mut validated_requests: Vec<(ReversalRequest, Option<Escrow>, bool, u32)> =
-            Vec::new(&env);
+            match validate_release(escrow_opt.as_ref()) {
+                Ok(()) => {
+                    let escrow = escrow_opt.unwrap();
+                    let paid = Self::release_funds(&env, &escrow, &escrow.recipient);
+                    release_results.push_back(ReleaseResult::Success(
+                        escrow_id,
+                        escrow.recipient.clone(),
+                        paid,
+                    ));
+                    successful_releases += 1;
+                    total_released = total_released.checked_add(paid).unwrap_or(total_released);
+                }
+                Err(e) => {
+                    release_results.push_back(ReleaseResult::Failure(escrow_id, e.to_error_code()));
+                    failed_releases += 1;
+                }
+            }
+        }
 
-        for request in requests.iter() {
-            let escrow_opt: Option<Escrow> = env
-                .storage()
-                .persistent()
-                .get(&DataKey::Escrow(request.escrow_id));
+        let reversals = if reversals.is_empty() {
+            BatchReversalResult {
+                batch_id: 0,
+                total_requests: 0,
+                successful: 0,
+                failed: 0,
+                total_reversed: 0,
+                results: Vec::new(&env),
+            }
+        } else {
+            Self::reverse_escrows(&env, &caller, &reversals)
+        };
 
-            let validation_result =
-                validate_reversal(escrow_opt.as_ref(), &caller, &admin, false, current_ledger);
+        SettlementResult {
+            successful_releases,
+            failed_releases,
+            total_released,
+            release_results,
+            reversals,
+        }
+    }
 
-            let (is_valid, error_code) = match validation_result {
-                Ok(()) => (true, 0u32),
-                Err(e) => (false, e.to_error_code()),
-            };
+    /// Extends the deadlines of multiple escrows with partial failure handling.
+    ///
+    /// Updates for missing or non-active escrows, or whose new deadline is not
+    /// after the current one, are recorded as failures without affecting the
+    /// rest of the batch.
+    ///
+    /// # Arguments
+    /// * `caller` - The address initiating the update (must be admin)
+    /// * `updates` - Escrow IDs paired with their new deadlines
+    ///
+    /// # Returns
+    /// * `BatchDeadlineResult` with per-escrow success/failure information
+    pub fn batch_extend_deadlines(
+        env: Env,
+        caller: Address,
+        updates: Vec<DeadlineUpdate>,
+    ) -> BatchDeadlineResult {
+        caller.require_auth();
+        Self::require_admin(&env, &caller);
 
-            validated_requests.push_back((request.clone(), escrow_opt, is_valid, error_code));
+        let request_count = updates.len();
+        if request_count == 0 {
+            panic_with_error!(&env, EscrowError::EmptyBatch);
+        }
+        if request_count > MAX_BATCH_SIZE {
+            panic_with_error!(&env, EscrowError::BatchTooLarge);
         }
 
-        // Second pass: execute reversals
-        for (request, escrow_opt, is_valid, error_code) in validated_requests.iter() {
-            if !is_valid {
-                // Validation failed - record failure and continue
-                results.push_back(ReversalResult::Failure(request.escrow_id, error_code));
+        let mut results: Vec<DeadlineUpdateResult> = Vec::new(&env);
+        let mut successful_count: u32 = 0;
+        let mut failed_count: u32 = 0;
+
+        for update in updates.iter() {
+            let escrow_opt: Option<Escrow> = env
+                .storage()
+                .persistent()
+                .get(&DataKey::Escrow(update.escrow_id));
+
+            if let Err(e) = validate_deadline_extension(escrow_opt.as_ref(), update.new_deadline) {
+                results.push_back(DeadlineUpdateResult::Failure(
+                    update.escrow_id,
+                    e.to_error_code(),
+                ));
                 failed_count += 1;
-                EscrowEvents::reversal_failure(&env, batch_id, request.escrow_id, error_code);
                 continue;
             }
 
-            // Get the escrow (safe to unwrap as validation passed)
-            let mut escrow = escrow_opt.clone().unwrap();
-
-            // Transfer funds back to depositor
-            token_client.transfer(
-                &env.current_contract_address(),
-                &escrow.depositor,
-                &escrow.amount,
-            );
-
-            // Update escrow status
-            escrow.status = EscrowStatus::Reversed;
+            let mut escrow = escrow_opt.unwrap();
+            let old_deadline = escrow.deadline;
+            escrow.deadline = update.new_deadline;
             env.storage()
                 .persistent()
-                .set(&DataKey::Escrow(escrow.escrow_id), &escrow);
+                .set(&DataKey::Escrow(update.escrow_id), &escrow);
 
-            // Record success
-            results.push_back(ReversalResult::Success(
-                escrow.escrow_id,
-                escrow.depositor.clone(),
-                escrow.amount,
+            results.push_back(DeadlineUpdateResult::Success(
+                update.escrow_id,
+                update.new_deadline,
             ));
             successful_count += 1;
-            total_reversed = total_reversed
-                .checked_add(escrow.amount)
-                .unwrap_or(total_reversed);
-
-            // Emit success event
-            EscrowEvents::reversal_success(
+            EscrowEvents::deadline_extended(
                 &env,
-                batch_id,
-                escrow.escrow_id,
-                &escrow.depositor,
-                escrow.amount,
+                update.escrow_id,
+                old_deadline,
+                update.new_deadline,
             );
         }
 
-        // Update storage statistics
-        let total_batches: u64 = env
-            .storage()
-            .instance()
-            .get(&DataKey::TotalReversalBatches)
-            .unwrap_or(0);
-        let total_escrows_reversed: u64 = env
-            .storage()
-            .instance()
-            .get(&DataKey::TotalEscrowsReversed)
-            .unwrap_or(0);
-        let total_amount_reversed: i128 = env
-            .storage()
-            .instance()
-            .get(&DataKey::TotalAmountReversed)
-            .unwrap_or(0);
-
-        env.storage()
-            .instance()
-            .set(&DataKey::TotalReversalBatches, &(total_batches + 1));
-        env.storage().instance().set(
-            &DataKey::TotalEscrowsReversed,
-            &(total_escrows_reversed + successful_count as u64),
-        );
-        env.storage().instance().set(
-            &DataKey::TotalAmountReversed,
-            &total_amount_reversed
-                .checked_add(total_reversed)
-                .unwrap_or(i128::MAX),
-        );
-
-        // Emit batch completed event
-        EscrowEvents::batch_reversal_completed(
-            &env,
-            batch_id,
-            successful_count,
-            failed_count,
-            total_reversed,
-        );
-
-        BatchReversalResult {
-            batch_id,
+        BatchDeadlineResult {
             total_requests: request_count,
             successful: successful_count,
             failed: failed_count,
-            total_reversed,
             results,
         }
     }
 
     /// Releases an escrow to the recipient.
     ///
-    /// Can only be called by admin or depositor.
+    /// Can only be called by admin, depositor, or the escrow's arbiter.
     pub fn release_escrow(env: Env, caller: Address, escrow_id: u64) {
         caller.require_auth();
 
@@ -337,8 +425,8 @@ impl EscrowContract {
             .get(&DataKey::Escrow(escrow_id))
             .expect("Escrow not found");
 
-        // Check authorization: admin or depositor
-        if caller != admin && caller != escrow.depositor {
+        // Check authorization: admin, depositor, or the escrow's arbiter
+        if caller != admin && caller != escrow.depositor && escrow.arbiter != Some(caller.clone()) {
             panic_with_error!(&env, EscrowError::Unauthorized);
         }
 
@@ -347,53 +435,459 @@ impl EscrowContract {
             panic!("Escrow is not active");
         }
 
-        // Transfer funds to recipient
-        let token_client = token::Client::new(&env, &escrow.token);
-        token_client.transfer(
-            &env.current_contract_address(),
-            &escrow.recipient,
-            &escrow.amount,
-        );
+        Self::release_funds(&env, &escrow, &escrow.recipient);
+    }
 
-        // Update escrow status
-        let mut updated_escrow = escrow.clone();
-        updated_escrow.status = EscrowStatus::Released;
-        env.storage()
-            .persistent()
-            .set(&DataKey::Escrow(escrow_id), &updated_escrow);
+    /// Releases an escrow to `target` instead of the named recipient.
+    ///
+    /// Used when the payout has been assigned elsewhere (e.g. to a factoring
+    /// assignee). Only the admin can redirect a release.
+    pub fn release_escrow_to(env: Env, caller: Address, escrow_id: u64, target: Address) {
+        caller.require_auth();
+        Self::require_admin(&env, &caller);
 
-        // Emit event
-        EscrowEvents::escrow_released(&env, escrow_id, &escrow.recipient, escrow.amount);
-    }
+        let escrow = Self::load_escrow(&env, escrow_id);
+        if escrow.status != EscrowStatus::Active {
+            panic_with_error!(&env, EscrowError::EscrowNotActive);
+        }
 
-    /// Returns an escrow by ID.
-    pub fn get_escrow(env: Env, escrow_id: u64) -> Option<Escrow> {
-        env.storage()
-            .persistent()
-            .get(&DataKey::Escrow(escrow_id))
+        let net_amount = Self::release_funds(&env, &escrow, &target);
+        EscrowEvents::escrow_redirected(&env, escrow_id, &escrow.recipient, &target, net_amount);
     }
 
-    /// Returns all escrow IDs for a user.
-    pub fn get_user_escrows(env: Env, user: Address) -> Vec<u64> {
+    /// Flags an escrow for release at the recipient's request.
+    ///
+    /// The release only executes once the depositor calls `approve_release`.
+    pub fn request_release(env: Env, recipient: Address, escrow_id: u64) {
+        recipient.require_auth();
+
+        let escrow = Self::load_escrow(&env, escrow_id);
+
+        if recipient != escrow.recipient {
+            panic_with_error!(&env, EscrowError::Unauthorized);
+        }
+        if escrow.status != EscrowStatus::Active {
+            panic_with_error!(&env, EscrowError::EscrowNotActive);
+        }
+
         env.storage()
             .persistent()
-            .get(&DataKey::UserEscrows(user))
-            .unwrap_or(Vec::new(&env))
-    }
+            .set(&DataKey::PendingRelease(escrow_id), &true);
 
-    /// Returns the admin address.
-    pub fn get_admin(env: Env) -> Address {
-        env.storage()
-            .instance()
-            .get(&DataKey::Admin)
-            .expect("Contract not initialized")
+        EscrowEvents::release_requested(&env, escrow_id, &recipient);
     }
 
-    /// Updates the admin address.
-    pub fn set_admin(env: Env, current_admin: Address, new_admin: Address) {
-        current_admin.require_auth();
-        Self::require_admin(&env, &current_admin);
-        env.storage().instance().set(&DataKey::Admin, &new_admin);
+    /// Approves a recipient-requested release and pays out the escrow.
+    pub fn approve_release(env: Env, depositor: Address, escrow_id: u64) {
+        depositor.require_auth();
+
+        let escrow = Self::load_escrow(&env, escrow_id);
+
+        if depositor != escrow.depositor {
+            panic_with_error!(&env, EscrowError::Unauthorized);
+        }
+        if escrow.status != EscrowStatus::Active {
+            panic_with_error!(&env, EscrowError::EscrowNotActive);
+        }
+        if !Self::is_release_pending(env.clone(), escrow_id) {
+            panic_with_error!(&env, EscrowError::ReleaseNotRequested);
+        }
+
+        Self::release_funds(&env, &escrow, &escrow.recipient);
+    }
+
+    /// Releases an escrow once the configured oracle confirms delivery.
+    ///
+    /// Can be called by the admin, depositor or recipient.
+    pub fn release_if_confirmed(env: Env, caller: Address, escrow_id: u64) {
+        caller.require_auth();
+
+        let oracle: Address = match env.storage().instance().get(&DataKey::Oracle) {
+            Some(oracle) => oracle,
+            None => panic_with_error!(&env, EscrowError::OracleNotConfigured),
+        };
+
+        let escrow = Self::load_escrow(&env, escrow_id);
+        let admin = Self::get_admin(env.clone());
+        if caller != admin && caller != escrow.depositor && caller != escrow.recipient {
+            panic_with_error!(&env, EscrowError::Unauthorized);
+        }
+        if escrow.status != EscrowStatus::Active {
+            panic_with_error!(&env, EscrowError::EscrowNotActive);
+        }
+
+        if !ConfirmationOracleClient::new(&env, &oracle).is_confirmed(&escrow_id) {
+            panic_with_error!(&env, EscrowError::NotConfirmed);
+        }
+
+        Self::release_funds(&env, &escrow, &escrow.recipient);
+    }
+
+    /// Raises a dispute on an active escrow, freezing release and reversal.
+    ///
+    /// Only the depositor or the recipient may raise a dispute. The escrow stays
+    /// `Disputed` until the admin calls `resolve_dispute`.
+    pub fn raise_dispute(env: Env, caller: Address, escrow_id: u64) {
+        caller.require_auth();
+
+        let mut escrow = Self::load_escrow(&env, escrow_id);
+        if caller != escrow.depositor && caller != escrow.recipient {
+            panic_with_error!(&env, EscrowError::Unauthorized);
+        }
+        if escrow.status != EscrowStatus::Active {
+            panic_with_error!(&env, EscrowError::EscrowNotActive);
+        }
+
+        escrow.status = EscrowStatus::Disputed;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Escrow(escrow_id), &escrow);
+        Self::move_status_index(
+            &env,
+            escrow_id,
+            EscrowStatus::Active,
+            EscrowStatus::Disputed,
+        );
+
+        EscrowEvents::dispute_raised(&env, escrow_id, &caller);
+    }
+
+    /// Resolves a dispute by releasing to the recipient or reversing to the depositor.
+    ///
+    /// # Errors
+    /// * `Unauthorized` - If caller is not admin
+    /// * `NotDisputed` - If the escrow is not under dispute
+    pub fn resolve_dispute(env: Env, admin: Address, escrow_id: u64, release_to_recipient: bool) {
+        admin.require_auth();
+        Self::require_admin(&env, &admin);
+
+        let mut escrow = Self::load_escrow(&env, escrow_id);
+        if escrow.status != EscrowStatus::Disputed {
+            panic_with_error!(&env, EscrowError::NotDisputed);
+        }
+
+        if release_to_recipient {
+            // Reactivate so the regular release path applies fees and indexing
+            escrow.status = EscrowStatus::Active;
+            Self::move_status_index(
+                &env,
+                escrow_id,
+                EscrowStatus::Disputed,
+                EscrowStatus::Active,
+            );
+            Self::release_funds(&env, &escrow, &escrow.recipient);
+        } else {
+            Self::reverse_funds(&env, &escrow, EscrowStatus::Disputed, 0);
+        }
+
+        EscrowEvents::dispute_resolved(&env, escrow_id, release_to_recipient);
+    }
+
+    /// Sets the oracle consulted by `release_if_confirmed`.
+    pub fn set_oracle(env: Env, caller: Address, oracle: Address) {
+        caller.require_auth();
+        Self::require_admin(&env, &caller);
+
+        env.storage().instance().set(&DataKey::Oracle, &oracle);
+    }
+
+    /// Returns the configured confirmation oracle, if any.
+    pub fn get_oracle(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::Oracle)
+    }
+
+    /// Returns whether the recipient has requested release of an escrow.
+    pub fn is_release_pending(env: Env, escrow_id: u64) -> bool {
+        env.storage()
+            .persistent()
+            .get(&DataKey::PendingRelease(escrow_id))
+            .unwrap_or(false)
+    }
+
+    /// Returns an escrow by ID.
+    pub fn get_escrow(env: Env, escrow_id: u64) -> Option<Escrow> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Escrow(escrow_id))
+    }
+
+    /// Returns the layout version an escrow record was stored with.
+    pub fn get_escrow_version(env: Env, escrow_id: u64) -> u32 {
+        Self::stored_version(&env, escrow_id)
+    }
+
+    /// Upgrades a stored escrow record to the current layout version.
+    ///
+    /// Fields added since the record was written are filled with defaults:
+    /// a released escrow without a payout address is assumed to have been paid
+    /// to its recipient, and escrows written before arbiters have none. Records
+    /// written before the status index are added to it. Records already at the
+    /// current version are left as is.
+    ///
+    /// # Returns
+    /// * `u32` - The record's version after migration
+    pub fn migrate_escrow(env: Env, admin: Address, escrow_id: u64) -> u32 {
+        admin.require_auth();
+        Self::require_admin(&env, &admin);
+
+        let version = Self::stored_version(&env, escrow_id);
+        if version >= ESCROW_VERSION {
+            return version;
+        }
+
+        // Older records cannot be read as the current `Escrow` layout: version 0
+        // predates `paid_to` and `version`, version 1 predates `arbiter`
+        let key = DataKey::Escrow(escrow_id);
+        let escrow = if version == 0 {
+            let legacy: EscrowV0 = env.storage().persistent().get(&key).unwrap();
+            let paid_to = if legacy.status == EscrowStatus::Released {
+                Some(legacy.recipient.clone())
+            } else {
+                None
+            };
+            Escrow {
+                escrow_id: legacy.escrow_id,
+                depositor: legacy.depositor,
+                recipient: legacy.recipient,
+                token: legacy.token,
+                amount: legacy.amount,
+                status: legacy.status,
+                created_at: legacy.created_at,
+                deadline: legacy.deadline,
+                paid_to,
+                version: ESCROW_VERSION,
+                arbiter: None,
+            }
+        } else {
+            let legacy: EscrowV1 = env.storage().persistent().get(&key).unwrap();
+            Escrow {
+                escrow_id: legacy.escrow_id,
+                depositor: legacy.depositor,
+                recipient: legacy.recipient,
+                token: legacy.token,
+                amount: legacy.amount,
+                status: legacy.status,
+                created_at: legacy.created_at,
+                deadline: legacy.deadline,
+                paid_to: legacy.paid_to,
+                version: ESCROW_VERSION,
+                arbiter: None,
+            }
+        };
+
+        env.storage().persistent().set(&key, &escrow);
+        if !env
+            .storage()
+            .persistent()
+            .has(&DataKey::StatusPosition(escrow_id))
+        {
+            Self::add_to_status_index(&env, escrow_id, escrow.status.clone());
+        }
+
+        escrow.version
+    }
+
+    /// Returns all escrow IDs for a user.
+    pub fn get_user_escrows(env: Env, user: Address) -> Vec<u64> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::UserEscrows(user))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Sets the allowed escrow amount range. A `max` of zero means no maximum.
+    pub fn set_escrow_bounds(env: Env, admin: Address, min: i128, max: i128) {
+        admin.require_auth();
+        Self::require_admin(&env, &admin);
+
+        if min < 0 || max < 0 || (max > 0 && max < min) {
+            panic_with_error!(&env, EscrowError::InvalidAmount);
+        }
+
+        env.storage()
+            .instance()
+            .set(&DataKey::MinEscrowAmount, &min);
+        env.storage()
+            .instance()
+            .set(&DataKey::MaxEscrowAmount, &max);
+    }
+
+    /// Returns the configured (min, max) escrow amounts.
+    pub fn get_escrow_bounds(env: Env) -> (i128, i128) {
+        let min: i128 = env
+            .storage()
+            .instance()
+            .get(&DataKey::MinEscrowAmount)
+            .unwrap_or(0);
+        let max: i128 = env
+            .storage()
+            .instance()
+            .get(&DataKey::MaxEscrowAmount)
+            .unwrap_or(0);
+        (min, max)
+    }
+
+    /// Sets the minimum number of ledgers a new escrow must stay open.
+    ///
+    /// Creation then requires `deadline >= current_sequence + horizon`. Zero
+    /// disables the check.
+    pub fn set_min_deadline_horizon(env: Env, admin: Address, horizon: u64) {
+        admin.require_auth();
+        Self::require_admin(&env, &admin);
+
+        env.storage()
+            .instance()
+            .set(&DataKey::MinDeadlineHorizon, &horizon);
+    }
+
+    /// Returns the minimum deadline horizon in ledgers (0 when disabled).
+    pub fn get_min_deadline_horizon(env: Env) -> u64 {
+        env.storage()
+            .instance()
+            .get(&DataKey::MinDeadlineHorizon)
+            .unwrap_or(0)
+    }
+
+    /// Sets the fee charged on each release and the address that collects it.
+    ///
+    /// Reversals are never charged.
+    pub fn set_release_fee(env: Env, admin: Address, fee_bps: u32, collector: Address) {
+        admin.require_auth();
+        Self::require_admin(&env, &admin);
+
+        if fee_bps > 10_000 {
+            panic_with_error!(&env, EscrowError::InvalidFee);
+        }
+
+        env.storage()
+            .instance()
+            .set(&DataKey::ReleaseFeeBps, &fee_bps);
+        env.storage()
+            .instance()
+            .set(&DataKey::FeeCollector, &collector);
+    }
+
+    /// Returns the release fee in basis points (0 when unset).
+    pub fn get_release_fee_bps(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::ReleaseFeeBps)
+            .unwrap_or(0)
+    }
+
+    /// Returns the address collecting release fees, if configured.
+    pub fn get_fee_collector(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::FeeCollector)
+    }
+
+    /// Pauses escrow creation and reversal.
+    ///
+    /// Releases stay available so funds are never trapped.
+    pub fn pause(env: Env, admin: Address) {
+        admin.require_auth();
+        Self::require_admin(&env, &admin);
+
+        env.storage().instance().set(&DataKey::Paused, &true);
+    }
+
+    /// Resumes escrow creation and reversal.
+    pub fn unpause(env: Env, admin: Address) {
+        admin.require_auth();
+        Self::require_admin(&env, &admin);
+
+        env.storage().instance().set(&DataKey::Paused, &false);
+    }
+
+    /// Returns whether escrow creation and reversal are paused.
+    pub fn is_paused(env: Env) -> bool {
+        env.storage()
+            .instance()
+            .get(&DataKey::Paused)
+            .unwrap_or(false)
+    }
+
+    /// Enables or disables analytics-compatible events for releases and reversals.
+    ///
+    /// When enabled, each completed flow also emits a `tx` event whose data
+    /// matches the analytics contract's transaction shape.
+    pub fn set_analytics_events(env: Env, caller: Address, enabled: bool) {
+        caller.require_auth();
+        Self::require_admin(&env, &caller);
+
+        env.storage()
+            .instance()
+            .set(&DataKey::AnalyticsEventsEnabled, &enabled);
+    }
+
+    /// Returns whether analytics-compatible events are enabled.
+    pub fn analytics_events_enabled(env: Env) -> bool {
+        env.storage()
+            .instance()
+            .get(&DataKey::AnalyticsEventsEnabled)
+            .unwrap_or(false)
+    }
+
+    /// Returns IDs of active escrows whose deadline is within `within_ledgers`
+    /// of the current ledger sequence (including ones already past due).
+    ///
+    /// `limit` is a scan window, not a match count: up to `limit` entries of
+    /// the active index are scanned from position `start`, so a page may hold
+    /// fewer matches than `limit`. Also returns the position after the last
+    /// scanned entry, to pass as `start` for the next page; the scan is done
+    /// once it reaches the number of active escrows. `limit` is capped at
+    /// `MAX_BATCH_SIZE`.
+    pub fn get_expiring_escrows(
+        env: Env,
+        within_ledgers: u64,
+        start: u32,
+        limit: u32,
+    ) -> (Vec<u64>, u32) {
+        let current_ledger = env.ledger().sequence() as u64;
+        let mut expiring: Vec<u64> = Vec::new(&env);
+        let active_ids = Self::get_status_ids(
+            &env,
+            &EscrowStatus::Active,
+            start,
+            limit.min(MAX_BATCH_SIZE),
+        );
+        for escrow_id in active_ids.iter() {
+            let escrow: Option<Escrow> =
+                env.storage().persistent().get(&DataKey::Escrow(escrow_id));
+            if let Some(escrow) = escrow {
+                if escrow.deadline.saturating_sub(current_ledger) <= within_ledgers {
+                    expiring.push_back(escrow_id);
+                }
+            }
+        }
+        (expiring, start.saturating_add(active_ids.len()))
+    }
+
+    /// Returns up to `limit` escrow IDs currently in `status`, starting at
+    /// position `start` of that status's index.
+    ///
+    /// `limit` is capped at `MAX_BATCH_SIZE`.
+    pub fn get_escrows_by_status(
+        env: Env,
+        status: EscrowStatus,
+        start: u32,
+        limit: u32,
+    ) -> Vec<u64> {
+        Self::get_status_ids(&env, &status, start, limit.min(MAX_BATCH_SIZE))
+    }
+
+    /// Returns the admin address.
+    pub fn get_admin(env: Env) -> Address {
+        env.storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .expect("Contract not initialized")
+    }
+
+    /// Updates the admin address.
+    pub fn set_admin(env: Env, current_admin: Address, new_admin: Address) {
+        current_admin.require_auth();
+        Self::require_admin(&env, &current_admin);
+        env.storage().instance().set(&DataKey::Admin, &new_admin);
     }
 
     /// Returns the total number of reversal batches processed.
@@ -428,6 +922,505 @@ impl EscrowContract {
             .unwrap_or(0)
     }
 
+    // Internal helper to record a funded escrow and index it for the depositor
+    fn store_new_escrow(
+        env: &Env,
+        depositor: &Address,
+        recipient: &Address,
+        token: &Address,
+        amount: i128,
+        deadline: u64,
+        arbiter: Option<Address>,
+    ) -> u64 {
+        // Get and increment escrow counter
+        let escrow_id: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::EscrowCounter)
+            .unwrap_or(0)
+            + 1;
+        env.storage()
+            .instance()
+            .set(&DataKey::EscrowCounter, &escrow_id);
+
+        // Create escrow record
+        let escrow = Escrow {
+            escrow_id,
+            depositor: depositor.clone(),
+            recipient: recipient.clone(),
+            token: token.clone(),
+            amount,
+            status: EscrowStatus::Active,
+            created_at: env.ledger().sequence() as u64,
+            deadline,
+            paid_to: None,
+            version: ESCROW_VERSION,
+            arbiter,
+        };
+
+        // Store escrow
+        env.storage()
+            .persistent()
+            .set(&DataKey::Escrow(escrow_id), &escrow);
+
+        // Update user escrows list
+        let mut user_escrows: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::UserEscrows(depositor.clone()))
+            .unwrap_or(Vec::new(env));
+        user_escrows.push_back(escrow_id);
+        env.storage()
+            .persistent()
+            .set(&DataKey::UserEscrows(depositor.clone()), &user_escrows);
+
+        // Index the escrow as active for status queries and expiry scans
+        Self::add_to_status_index(env, escrow_id, EscrowStatus::Active);
+
+        // Emit event
+        EscrowEvents::escrow_created(env, escrow_id, depositor, recipient, amount);
+
+        escrow_id
+    }
+
+    // Internal helper reversing a validated-size batch of escrows.
+    // Invalid entries are recorded as failures without aborting the batch.
+    fn reverse_escrows(
+        env: &Env,
+        caller: &Address,
+        requests: &Vec<ReversalRequest>,
+    ) -> BatchReversalResult {
+        let request_count = requests.len();
+
+        // Get batch ID
+        let batch_id: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::TotalReversalBatches)
+            .unwrap_or(0)
+            + 1;
+
+        // Get admin and token for validation
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .expect("Contract not initialized");
+
+        let current_ledger = env.ledger().sequence() as u64;
+
+        // Emit batch started event
+        EscrowEvents::batch_reversal_started(env, batch_id, request_count);
+
+        // Initialize result tracking
+        let mut results: Vec<ReversalResult> = Vec::new(env);
+        let mut successful_count: u32 = 0;
+        let mut failed_count: u32 = 0;
+        let mut total_reversed: i128 = 0;
+
+        // First pass: validate all requests
+        let mut validated_requests: Vec<(ReversalRequest, bool, u32)> = Vec::new(env);
+
+        for request in requests.iter() {
+            let escrow_opt: Option<Escrow> = env
+                .storage()
+                .persistent()
+                .get(&DataKey::Escrow(request.escrow_id));
+
+            let validation_result =
+                validate_reversal(escrow_opt.as_ref(), caller, &admin, false, current_ledger);
+
+            let (is_valid, error_code) = match validation_result {
+                Ok(()) => (true, 0u32),
+                Err(e) => (false, e.to_error_code()),
+            };
+
+            validated_requests.push_back((request.clone(), is_valid, error_code));
+        }
+
+        // Second pass: execute reversals
+        for (request, is_valid, error_code) in validated_requests.iter() {
+            // Re-check the stored escrow so an ID repeated in the batch fails
+            // once its first occurrence has been reversed
+            let escrow_opt: Option<Escrow> = env
+                .storage()
+                .persistent()
+                .get(&DataKey::Escrow(request.escrow_id));
+            let (is_valid, error_code) = if is_valid {
+                match validate_reversal(escrow_opt.as_ref(), caller, &admin, false, current_ledger)
+                {
+                    Ok(()) => (true, 0u32),
+                    Err(e) => (false, e.to_error_code()),
+                }
+            } else {
+                (false, error_code)
+            };
+
+            if !is_valid {
+                // Validation failed - record failure and continue
+                results.push_back(ReversalResult::Failure(request.escrow_id, error_code));
+                failed_count += 1;
+                EscrowEvents::reversal_failure(env, batch_id, request.escrow_id, error_code);
+                continue;
+            }
+
+            // Get the escrow (safe to unwrap as validation passed)
+            let escrow = escrow_opt.clone().unwrap();
+            Self::reverse_funds(env, &escrow, EscrowStatus::Active, batch_id);
+
+            // Record success
+            results.push_back(ReversalResult::Success(
+                escrow.escrow_id,
+                escrow.depositor.clone(),
+                escrow.amount,
+            ));
+            successful_count += 1;
+            total_reversed = total_reversed
+                .checked_add(escrow.amount)
+                .unwrap_or(total_reversed);
+        }
+
+        // Update storage statistics; per-escrow totals are kept by reverse_funds
+        let total_batches: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::TotalReversalBatches)
+            .unwrap_or(0);
+        env.storage()
+            .instance()
+            .set(&DataKey::TotalReversalBatches, &(total_batches + 1));
+
+        // Emit batch completed event
+        EscrowEvents::batch_reversal_completed(
+            env,
+            batch_id,
+            successful_count,
+            failed_count,
+            total_reversed,
+        );
+
+        BatchReversalResult {
+            batch_id,
+            total_requests: request_count,
+            successful: successful_count,
+            failed: failed_count,
+            total_reversed,
+            results,
+        }
+    }
+
+    // Internal helper returning the number of escrows in `status`
+    fn get_status_count(env: &Env, status: &EscrowStatus) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::StatusCount(status.clone()))
+            .unwrap_or(0)
+    }
+
+    // Internal helper returning one page of the `status` index
+    fn get_status_page(env: &Env, status: &EscrowStatus, page: u32) -> Vec<u64> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::StatusIndex(status.clone(), page))
+            .unwrap_or(Vec::new(env))
+    }
+
+    // Internal helper returning up to `limit` IDs of escrows in `status` from
+    // position `start`, reading only the pages that range covers
+    fn get_status_ids(env: &Env, status: &EscrowStatus, start: u32, limit: u32) -> Vec<u64> {
+        let end = Self::get_status_count(env, status).min(start.saturating_add(limit));
+
+        let mut ids: Vec<u64> = Vec::new(env);
+        let mut position = start;
+        while position < end {
+            let page_number = position / STATUS_PAGE_SIZE;
+            let page = Self::get_status_page(env, status, page_number);
+            let page_end = end.min((page_number + 1) * STATUS_PAGE_SIZE);
+            for index in position..page_end {
+                ids.push_back(page.get_unchecked(index % STATUS_PAGE_SIZE));
+            }
+            position = page_end;
+        }
+        ids
+    }
+
+    // Internal helper appending an escrow to the `status` index
+    fn add_to_status_index(env: &Env, escrow_id: u64, status: EscrowStatus) {
+        let count = Self::get_status_count(env, &status);
+        let page_number = count / STATUS_PAGE_SIZE;
+        let mut page = Self::get_status_page(env, &status, page_number);
+        page.push_back(escrow_id);
+
+        let storage = env.storage().persistent();
+        storage.set(&DataKey::StatusIndex(status.clone(), page_number), &page);
+        storage.set(&DataKey::StatusPosition(escrow_id), &count);
+        storage.set(&DataKey::StatusCount(status), &(count + 1));
+    }
+
+    // Internal helper dropping an escrow from the `status` index by moving the
+    // last ID of the index into its slot; a no-op unless the escrow's recorded
+    // position in that index actually holds it
+    fn remove_from_status_index(env: &Env, escrow_id: u64, status: EscrowStatus) {
+        let storage = env.storage().persistent();
+        let position: u32 = match storage.get(&DataKey::StatusPosition(escrow_id)) {
+            Some(position) => position,
+            None => return,
+        };
+        let count = Self::get_status_count(env, &status);
+        if position >= count
+            || Self::get_status_page(env, &status, position / STATUS_PAGE_SIZE)
+                .get(position % STATUS_PAGE_SIZE)
+                != Some(escrow_id)
+        {
+            return;
+        }
+        let last = count - 1;
+
+        let last_page_number = last / STATUS_PAGE_SIZE;
+        let mut last_page = Self::get_status_page(env, &status, last_page_number);
+        let last_id = last_page.pop_back_unchecked();
+        if last_page.is_empty() {
+            storage.remove(&DataKey::StatusIndex(status.clone(), last_page_number));
+        } else {
+            storage.set(
+                &DataKey::StatusIndex(status.clone(), last_page_number),
+                &last_page,
+            );
+        }
+
+        if position != last {
+            let page_number = position / STATUS_PAGE_SIZE;
+            let mut page = Self::get_status_page(env, &status, page_number);
+            page.set(position % STATUS_PAGE_SIZE, last_id);
+            storage.set(&DataKey::StatusIndex(status.clone(), page_number), &page);
+            storage.set(&DataKey::StatusPosition(last_id), &position);
+        }
+
+        storage.remove(&DataKey::StatusPosition(escrow_id));
+        storage.set(&DataKey::StatusCount(status), &last);
+    }
+
+    // Internal helper moving an escrow between status indexes on a transition
+    fn move_status_index(env: &Env, escrow_id: u64, from: EscrowStatus, to: EscrowStatus) {
+        Self::remove_from_status_index(env, escrow_id, from);
+        Self::add_to_status_index(env, escrow_id, to);
+    }
+
+    // Internal helper to load an escrow or fail with EscrowNotFound
+    fn load_escrow(env: &Env, escrow_id: u64) -> Escrow {
+        match env.storage().persistent().get(&DataKey::Escrow(escrow_id)) {
+            Some(escrow) => escrow,
+            None => panic_with_error!(env, EscrowError::EscrowNotFound),
+        }
+    }
+
+    // Internal helper to read a stored record's layout version without assuming
+    // its layout; records written before versioning have no `version` field
+    fn stored_version(env: &Env, escrow_id: u64) -> u32 {
+        let raw: Map<Symbol, Val> =
+            match env.storage().persistent().get(&DataKey::Escrow(escrow_id)) {
+                Some(raw) => raw,
+                None => panic_with_error!(env, EscrowError::EscrowNotFound),
+            };
+        match raw.get(symbol_short!("version")) {
+            Some(version) => u32::try_from_val(env, &version).unwrap_or(0),
+            None => 0,
+        }
+    }
+
+    // Internal helper to pay an active escrow out to `to`, less any release fee;
+    // returns the amount actually paid
+    fn release_funds(env: &Env, escrow: &Escrow, to: &Address) -> i128 {
+        let token_client = token::Client::new(env, &escrow.token);
+
+        // Route the release fee to the collector when one is configured
+        let fee = match Self::get_fee_collector(env.clone()) {
+            Some(collector) => {
+                // Split the amount so large escrows cannot overflow `amount * bps`
+                let fee_bps = Self::get_release_fee_bps(env.clone()) as i128;
+                let fee =
+                    escrow.amount / 10_000 * fee_bps + escrow.amount % 10_000 * fee_bps / 10_000;
+                if fee > 0 {
+                    token_client.transfer(&env.current_contract_address(), &collector, &fee);
+                }
+                fee
+            }
+            None => 0,
+        };
+        let net_amount = escrow.amount - fee;
+
+        // Transfer the remainder to the payout address
+        token_client.transfer(&env.current_contract_address(), to, &net_amount);
+
+        // Update escrow status and record where the funds went
+        let mut updated_escrow = escrow.clone();
+        updated_escrow.status = EscrowStatus::Released;
+        updated_escrow.paid_to = Some(to.clone());
+        env.storage()
+            .persistent()
+            .set(&DataKey::Escrow(escrow.escrow_id), &updated_escrow);
+        Self::move_status_index(
+            env,
+            escrow.escrow_id,
+            EscrowStatus::Active,
+            EscrowStatus::Released,
+        );
+
+        // A settled escrow can no longer have a release pending
+        env.storage()
+            .persistent()
+            .remove(&DataKey::PendingRelease(escrow.escrow_id));
+
+        // Emit event
+        EscrowEvents::escrow_released(env, escrow.escrow_id, to, net_amount, fee);
+        Self::emit_analytics_transaction(
+            env,
+            escrow,
+            &escrow.depositor,
+            to,
+            symbol_short!("release"),
+        );
+
+        net_amount
+    }
+
+    // Internal helper returning an escrow in `from` status to its depositor;
+    // `batch_id` is 0 when the reversal is not part of a batch
+    fn reverse_funds(env: &Env, escrow: &Escrow, from: EscrowStatus, batch_id: u64) {
+        let token_client = token::Client::new(env, &escrow.token);
+        token_client.transfer(
+            &env.current_contract_address(),
+            &escrow.depositor,
+            &escrow.amount,
+        );
+
+        // Update escrow status
+        let mut updated_escrow = escrow.clone();
+        updated_escrow.status = EscrowStatus::Reversed;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Escrow(escrow.escrow_id), &updated_escrow);
+        Self::move_status_index(env, escrow.escrow_id, from, EscrowStatus::Reversed);
+        env.storage()
+            .persistent()
+            .remove(&DataKey::PendingRelease(escrow.escrow_id));
+
+        // Update reversal statistics
+        let total_escrows_reversed: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::TotalEscrowsReversed)
+            .unwrap_or(0);
+        let total_amount_reversed: i128 = env
+            .storage()
+            .instance()
+            .get(&DataKey::TotalAmountReversed)
+            .unwrap_or(0);
+        env.storage().instance().set(
+            &DataKey::TotalEscrowsReversed,
+            &(total_escrows_reversed + 1),
+        );
+        env.storage().instance().set(
+            &DataKey::TotalAmountReversed,
+            &total_amount_reversed
+                .checked_add(escrow.amount)
+                .unwrap_or(i128::MAX),
+        );
+
+        // Emit events
+        EscrowEvents::reversal_success(
+            env,
+            batch_id,
+            escrow.escrow_id,
+            &escrow.depositor,
+            escrow.amount,
+        );
+        Self::emit_analytics_transaction(
+            env,
+            escrow,
+            &env.current_contract_address(),
+            &escrow.depositor,
+            symbol_short!("reversal"),
+        );
+    }
+
+    // Internal helper emitting an analytics-compatible event when enabled
+    fn emit_analytics_transaction(
+        env: &Env,
+        escrow: &Escrow,
+        from: &Address,
+        to: &Address,
+        category: Symbol,
+    ) {
+        let enabled: bool = env
+            .storage()
+            .instance()
+            .get(&DataKey::AnalyticsEventsEnabled)
+            .unwrap_or(false);
+        if !enabled {
+            return;
+        }
+
+        EscrowEvents::analytics_transaction(
+            env,
+            &AnalyticsTransaction {
+                tx_id: escrow.escrow_id,
+                from: from.clone(),
+                to: to.clone(),
+                amount: escrow.amount,
+                timestamp: env.ledger().sequence() as u64,
+                category,
+            },
+        );
+    }
+
+    // Internal helper enforcing the configured escrow amount bounds
+    fn check_amount_bounds(env: &Env, amount: i128) {
+        let (min, max) = Self::get_escrow_bounds(env.clone());
+        match validate_amount_bounds(amount, min, max) {
+            Ok(()) => {}
+            Err(ValidationError::BelowMinAmount) => {
+                panic_with_error!(env, EscrowError::BelowMinAmount)
+            }
+            Err(_) => panic_with_error!(env, EscrowError::AboveMaxAmount),
+        }
+    }
+
+    // Internal helper checking a new deadline against the minimum horizon
+    fn meets_deadline_horizon(env: &Env, deadline: u64) -> bool {
+        let horizon = Self::get_min_deadline_horizon(env.clone());
+        horizon == 0 || deadline >= (env.ledger().sequence() as u64).saturating_add(horizon)
+    }
+
+    // Internal helper rejecting creation and reversal while paused
+    fn require_not_paused(env: &Env) {
+        if Self::is_paused(env.clone()) {
+            panic_with_error!(env, EscrowError::Paused);
+        }
+    }
+
+    // Internal helper to verify the caller is the admin or the arbiter of every requested escrow
+    fn require_admin_or_arbiter(env: &Env, caller: &Address, requests: &Vec<ReversalRequest>) {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .expect("Contract not initialized");
+        if *caller == admin {
+            return;
+        }
+
+        for request in requests.iter() {
+            let escrow: Option<Escrow> = env
+                .storage()
+                .persistent()
+                .get(&DataKey::Escrow(request.escrow_id));
+            if escrow.and_then(|e| e.arbiter).as_ref() != Some(caller) {
+                panic_with_error!(env, EscrowError::Unauthorized);
+            }
+        }
+    }
+
     // Internal helper to verify admin
     fn require_admin(env: &Env, caller: &Address) {
         let admin: Address = env
@@ -3,11 +3,14 @@
 #![cfg(test)]
 
 use crate::{
-    EscrowContract, EscrowContractClient, EscrowStatus, ReversalRequest, ReversalResult,
+    AnalyticsTransaction, DataKey, DeadlineUpdate, DeadlineUpdateResult, EscrowContract,
+    EscrowContractClient, EscrowRequest, EscrowStatus, EscrowV0, EscrowV1, ReleaseResult,
+    ReversalRequest, ReversalResult, ESCROW_VERSION, STATUS_PAGE_SIZE,
 };
 use soroban_sdk::{
+    contract, contractimpl, symbol_short,
     testutils::{Address as _, Events as _, Ledger},
-    token, Address, Env, Vec,
+    token, Address, Env, Symbol, TryIntoVal, Vec,
 };
 
 /// Creates a test environment with the contract deployed and initialized.
@@ -42,6 +45,21 @@ fn setup_test_env() -> (
     (env, admin, token_id, token_client, token_admin_client, client)
 }
 
+/// Mock delivery oracle whose confirmations are set directly by tests.
+#[contract]
+pub struct MockOracle;
+
+#[contractimpl]
+impl MockOracle {
+    pub fn set_confirmed(env: Env, escrow_id: u64, confirmed: bool) {
+        env.storage().persistent().set(&escrow_id, &confirmed);
+    }
+
+    pub fn is_confirmed(env: Env, escrow_id: u64) -> bool {
+        env.storage().persistent().get(&escrow_id).unwrap_or(false)
+    }
+}
+
 /// Helper to create a reversal request.
 fn create_reversal_request(escrow_id: u64) -> ReversalRequest {
     ReversalRequest { escrow_id }
@@ -49,7 +67,7 @@ fn create_reversal_request(escrow_id: u64) -> ReversalRequest {
 
 /// Helper to create an escrow and return its ID.
 fn create_test_escrow(
-    env: &Env,
+    _env: &Env,
     client: &EscrowContractClient,
     token_admin: &token::StellarAssetClient,
     depositor: &Address,
@@ -61,7 +79,7 @@ fn create_test_escrow(
     token_admin.mint(depositor, &amount);
 
     // Create escrow
-    client.create_escrow(depositor, recipient, &amount, &deadline)
+    client.create_escrow(depositor, recipient, &amount, &deadline, &None)
 }
 
 // ============================================
@@ -70,7 +88,7 @@ fn create_test_escrow(
 
 #[test]
 fn test_initialize_contract() {
-    let (_env, admin, token, _token_client, _token_admin, client) = setup_test_env();
+    let (_env, admin, _token, _token_client, _token_admin, client) = setup_test_env();
 
     assert_eq!(client.get_admin(), admin);
     assert_eq!(client.get_total_reversal_batches(), 0);
@@ -94,7 +112,7 @@ fn test_cannot_initialize_twice() {
 
 #[test]
 fn test_create_escrow() {
-    let (env, _admin, _token, token_client, token_admin, client) = setup_test_env();
+    let (env, _admin, _token, _token_client, token_admin, client) = setup_test_env();
 
     let depositor = Address::generate(&env);
     let recipient = Address::generate(&env);
@@ -164,7 +182,104 @@ fn test_create_escrow_invalid_amount() {
     let recipient = Address::generate(&env);
 
     // Should panic due to invalid amount
-    client.create_escrow(&depositor, &recipient, &0, &20000);
+    client.create_escrow(&depositor, &recipient, &0, &20000, &None);
+}
+
+// ============================================
+// Atomic Batch Creation Tests
+// ============================================
+
+#[test]
+fn test_batch_create_escrows_atomic() {
+    let (env, _admin, _token, token_client, token_admin, client) = setup_test_env();
+
+    let depositor = Address::generate(&env);
+    let recipient1 = Address::generate(&env);
+    let recipient2 = Address::generate(&env);
+    token_admin.mint(&depositor, &3000);
+
+    let mut requests: Vec<EscrowRequest> = Vec::new(&env);
+    requests.push_back(EscrowRequest {
+        recipient: recipient1.clone(),
+        amount: 1000,
+        deadline: 20000,
+    });
+    requests.push_back(EscrowRequest {
+        recipient: recipient2.clone(),
+        amount: 2000,
+        deadline: 30000,
+    });
+
+    let escrow_ids = client.batch_create_escrows_atomic(&depositor, &requests);
+
+    assert_eq!(escrow_ids.len(), 2);
+    assert_eq!(client.get_escrow_counter(), 2);
+    assert_eq!(token_client.balance(&depositor), 0);
+    assert_eq!(token_client.balance(&client.address), 3000);
+
+    let escrow = client.get_escrow(&escrow_ids.get(1).unwrap()).unwrap();
+    assert_eq!(escrow.recipient, recipient2);
+    assert_eq!(escrow.amount, 2000);
+    assert_eq!(client.get_user_escrows(&depositor).len(), 2);
+}
+
+#[test]
+fn test_batch_create_escrows_atomic_single_bad_request_creates_none() {
+    let (env, _admin, _token, token_client, token_admin, client) = setup_test_env();
+
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    token_admin.mint(&depositor, &3000);
+
+    let mut requests: Vec<EscrowRequest> = Vec::new(&env);
+    requests.push_back(EscrowRequest {
+        recipient: recipient.clone(),
+        amount: 1000,
+        deadline: 20000,
+    });
+    // Self-escrow invalidates the whole batch
+    requests.push_back(EscrowRequest {
+        recipient: depositor.clone(),
+        amount: 1000,
+        deadline: 20000,
+    });
+    requests.push_back(EscrowRequest {
+        recipient: recipient.clone(),
+        amount: 1000,
+        deadline: 20000,
+    });
+
+    let result = client.try_batch_create_escrows_atomic(&depositor, &requests);
+    assert!(result.is_err());
+
+    assert_eq!(client.get_escrow_counter(), 0);
+    assert_eq!(client.get_user_escrows(&depositor).len(), 0);
+    assert_eq!(token_client.balance(&depositor), 3000);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #8)")]
+fn test_batch_create_escrows_atomic_expired_deadline() {
+    let (env, _admin, _token, _token_client, token_admin, client) = setup_test_env();
+
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    token_admin.mint(&depositor, &2000);
+
+    let mut requests: Vec<EscrowRequest> = Vec::new(&env);
+    requests.push_back(EscrowRequest {
+        recipient: recipient.clone(),
+        amount: 1000,
+        deadline: 20000,
+    });
+    // Ledger is at 12345, so this deadline is already in the past
+    requests.push_back(EscrowRequest {
+        recipient: recipient.clone(),
+        amount: 1000,
+        deadline: 100,
+    });
+
+    client.batch_create_escrows_atomic(&depositor, &requests);
 }
 
 // ============================================
@@ -173,7 +288,7 @@ fn test_create_escrow_invalid_amount() {
 
 #[test]
 fn test_batch_reverse_single_escrow() {
-    let (env, admin, _token, token_client, token_admin, client) = setup_test_env();
+    let (env, admin, _token, _token_client, token_admin, client) = setup_test_env();
 
     let depositor = Address::generate(&env);
     let recipient = Address::generate(&env);
@@ -401,6 +516,61 @@ fn test_batch_reverse_already_reversed_escrow() {
     }
 }
 
+#[test]
+fn test_batch_reverse_duplicate_escrow_id() {
+    let (env, admin, _token, token_client, token_admin, client) = setup_test_env();
+
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+
+    let duplicated = create_test_escrow(
+        &env,
+        &client,
+        &token_admin,
+        &depositor,
+        &recipient,
+        1_000,
+        20000,
+    );
+    let other = create_test_escrow(
+        &env,
+        &client,
+        &token_admin,
+        &depositor,
+        &recipient,
+        2_000,
+        20000,
+    );
+
+    let mut requests: Vec<ReversalRequest> = Vec::new(&env);
+    requests.push_back(create_reversal_request(duplicated));
+    requests.push_back(create_reversal_request(duplicated));
+
+    let result = client.batch_reverse_escrows(&admin, &requests);
+
+    assert_eq!(result.successful, 1);
+    assert_eq!(result.failed, 1);
+    assert_eq!(result.total_reversed, 1_000);
+    match result.results.get(1).unwrap() {
+        ReversalResult::Failure(id, error_code) => {
+            assert_eq!(id, duplicated);
+            assert_eq!(error_code, 2); // ALREADY_REVERSED
+        }
+        _ => panic!("Expected the repeated ID to fail"),
+    }
+    assert_eq!(token_client.balance(&depositor), 1_000);
+
+    // The other escrow keeps its place in the active index
+    assert_eq!(
+        client.get_escrows_by_status(&EscrowStatus::Active, &0, &10),
+        Vec::from_array(&env, [other])
+    );
+    assert_eq!(
+        client.get_escrows_by_status(&EscrowStatus::Reversed, &0, &10),
+        Vec::from_array(&env, [duplicated])
+    );
+}
+
 // ============================================
 // Partial Failure Tests
 // ============================================
@@ -778,28 +948,1679 @@ fn test_release_escrow_already_reversed() {
     client.release_escrow(&admin, &escrow_id);
 }
 
+#[test]
+fn test_release_escrow_to_alternate_address() {
+    let (env, admin, _token, token_client, token_admin, client) = setup_test_env();
+
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let assignee = Address::generate(&env);
+
+    let escrow_id = create_test_escrow(
+        &env,
+        &client,
+        &token_admin,
+        &depositor,
+        &recipient,
+        5_000,
+        20000,
+    );
+
+    client.release_escrow_to(&admin, &escrow_id, &assignee);
+
+    assert_eq!(token_client.balance(&assignee), 5_000);
+    assert_eq!(token_client.balance(&recipient), 0);
+
+    let escrow = client.get_escrow(&escrow_id).unwrap();
+    assert_eq!(escrow.status, EscrowStatus::Released);
+    assert_eq!(escrow.paid_to, Some(assignee.clone()));
+    assert_eq!(escrow.recipient, recipient);
+}
+
+#[test]
+fn test_release_escrow_records_recipient_as_payout() {
+    let (env, admin, _token, _token_client, token_admin, client) = setup_test_env();
+
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+
+    let escrow_id = create_test_escrow(
+        &env,
+        &client,
+        &token_admin,
+        &depositor,
+        &recipient,
+        5_000,
+        20000,
+    );
+
+    client.release_escrow(&admin, &escrow_id);
+
+    assert_eq!(
+        client.get_escrow(&escrow_id).unwrap().paid_to,
+        Some(recipient)
+    );
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #2)")]
+fn test_release_escrow_to_rejects_non_admin() {
+    let (env, _admin, _token, _token_client, token_admin, client) = setup_test_env();
+
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let assignee = Address::generate(&env);
+
+    let escrow_id = create_test_escrow(
+        &env,
+        &client,
+        &token_admin,
+        &depositor,
+        &recipient,
+        5_000,
+        20000,
+    );
+
+    // Even the depositor cannot redirect the payout
+    client.release_escrow_to(&depositor, &escrow_id, &assignee);
+}
+
 // ============================================
-// Admin Tests
+// Release Request / Approval Tests
 // ============================================
 
 #[test]
-fn test_set_admin() {
-    let (env, admin, _token, _token_client, _token_admin, client) = setup_test_env();
+fn test_release_requires_request_and_approval() {
+    let (env, _admin, _token, token_client, token_admin, client) = setup_test_env();
 
-    let new_admin = Address::generate(&env);
-    client.set_admin(&admin, &new_admin);
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
 
-    assert_eq!(client.get_admin(), new_admin);
+    let escrow_id = create_test_escrow(
+        &env,
+        &client,
+        &token_admin,
+        &depositor,
+        &recipient,
+        5_000,
+        20000,
+    );
+
+    // Recipient asks for release; nothing moves yet
+    client.request_release(&recipient, &escrow_id);
+    assert!(client.is_release_pending(&escrow_id));
+    assert_eq!(
+        client.get_escrow(&escrow_id).unwrap().status,
+        EscrowStatus::Active
+    );
+    assert_eq!(token_client.balance(&recipient), 0);
+
+    // Depositor approves; funds are released
+    client.approve_release(&depositor, &escrow_id);
+    assert!(!client.is_release_pending(&escrow_id));
+    assert_eq!(
+        client.get_escrow(&escrow_id).unwrap().status,
+        EscrowStatus::Released
+    );
+    assert_eq!(token_client.balance(&recipient), 5_000);
 }
 
 #[test]
-#[should_panic]
-fn test_set_admin_unauthorized() {
-    let (env, _admin, _token, _token_client, _token_admin, client) = setup_test_env();
+fn test_pending_release_cleared_when_escrow_settles_otherwise() {
+    let (env, admin, _token, _token_client, token_admin, client) = setup_test_env();
 
-    let unauthorized = Address::generate(&env);
-    let new_admin = Address::generate(&env);
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
 
-    // Should panic due to unauthorized caller
-    client.set_admin(&unauthorized, &new_admin);
+    let released_id = create_test_escrow(
+        &env,
+        &client,
+        &token_admin,
+        &depositor,
+        &recipient,
+        5_000,
+        20000,
+    );
+    let reversed_id = create_test_escrow(
+        &env,
+        &client,
+        &token_admin,
+        &depositor,
+        &recipient,
+        5_000,
+        20000,
+    );
+    client.request_release(&recipient, &released_id);
+    client.request_release(&recipient, &reversed_id);
+
+    // A direct release and a reversal both drop the pending request
+    client.release_escrow(&depositor, &released_id);
+    let mut requests: Vec<ReversalRequest> = Vec::new(&env);
+    requests.push_back(create_reversal_request(reversed_id));
+    client.batch_reverse_escrows(&admin, &requests);
+
+    assert!(!client.is_release_pending(&released_id));
+    assert!(!client.is_release_pending(&reversed_id));
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #10)")]
+fn test_approve_release_without_request() {
+    let (env, _admin, _token, _token_client, token_admin, client) = setup_test_env();
+
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+
+    let escrow_id = create_test_escrow(
+        &env,
+        &client,
+        &token_admin,
+        &depositor,
+        &recipient,
+        5_000,
+        20000,
+    );
+
+    client.approve_release(&depositor, &escrow_id);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #2)")]
+fn test_request_release_by_non_recipient() {
+    let (env, _admin, _token, _token_client, token_admin, client) = setup_test_env();
+
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+
+    let escrow_id = create_test_escrow(
+        &env,
+        &client,
+        &token_admin,
+        &depositor,
+        &recipient,
+        5_000,
+        20000,
+    );
+
+    client.request_release(&depositor, &escrow_id);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #2)")]
+fn test_approve_release_by_non_depositor() {
+    let (env, _admin, _token, _token_client, token_admin, client) = setup_test_env();
+
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+
+    let escrow_id = create_test_escrow(
+        &env,
+        &client,
+        &token_admin,
+        &depositor,
+        &recipient,
+        5_000,
+        20000,
+    );
+
+    client.request_release(&recipient, &escrow_id);
+    client.approve_release(&recipient, &escrow_id);
+}
+
+// ============================================
+// Admin Tests
+// ============================================
+
+// ============================================
+// Oracle Release Tests
+// ============================================
+
+#[test]
+fn test_release_if_confirmed_releases_on_confirmation() {
+    let (env, admin, _token, token_client, token_admin, client) = setup_test_env();
+
+    let oracle_id = env.register(MockOracle, ());
+    let oracle = MockOracleClient::new(&env, &oracle_id);
+    client.set_oracle(&admin, &oracle_id);
+    assert_eq!(client.get_oracle(), Some(oracle_id));
+
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let escrow_id = create_test_escrow(
+        &env,
+        &client,
+        &token_admin,
+        &depositor,
+        &recipient,
+        1000,
+        20000,
+    );
+
+    // Unconfirmed delivery leaves the escrow untouched
+    let result = client.try_release_if_confirmed(&recipient, &escrow_id);
+    assert!(result.is_err());
+    assert_eq!(
+        client.get_escrow(&escrow_id).unwrap().status,
+        EscrowStatus::Active
+    );
+    assert_eq!(token_client.balance(&recipient), 0);
+
+    oracle.set_confirmed(&escrow_id, &true);
+    client.release_if_confirmed(&recipient, &escrow_id);
+
+    assert_eq!(
+        client.get_escrow(&escrow_id).unwrap().status,
+        EscrowStatus::Released
+    );
+    assert_eq!(token_client.balance(&recipient), 1000);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #12)")]
+fn test_release_if_confirmed_unconfirmed_fails() {
+    let (env, admin, _token, _token_client, token_admin, client) = setup_test_env();
+
+    let oracle_id = env.register(MockOracle, ());
+    client.set_oracle(&admin, &oracle_id);
+
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let escrow_id = create_test_escrow(
+        &env,
+        &client,
+        &token_admin,
+        &depositor,
+        &recipient,
+        1000,
+        20000,
+    );
+
+    client.release_if_confirmed(&depositor, &escrow_id);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #11)")]
+fn test_release_if_confirmed_requires_oracle() {
+    let (env, _admin, _token, _token_client, token_admin, client) = setup_test_env();
+
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let escrow_id = create_test_escrow(
+        &env,
+        &client,
+        &token_admin,
+        &depositor,
+        &recipient,
+        1000,
+        20000,
+    );
+
+    client.release_if_confirmed(&recipient, &escrow_id);
+}
+
+// ============================================
+// Analytics Integration Tests
+// ============================================
+
+#[test]
+fn test_release_emits_analytics_transaction_event() {
+    let (env, admin, _token, _token_client, token_admin, client) = setup_test_env();
+
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let escrow_id = create_test_escrow(
+        &env,
+        &client,
+        &token_admin,
+        &depositor,
+        &recipient,
+        1000,
+        20000,
+    );
+
+    client.set_analytics_events(&admin, &true);
+    assert!(client.analytics_events_enabled());
+
+    client.release_escrow(&depositor, &escrow_id);
+
+    let (_contract, topics, data) = env.events().all().last().unwrap();
+    let kind: Symbol = topics.get(0).unwrap().try_into_val(&env).unwrap();
+    let category: Symbol = topics.get(1).unwrap().try_into_val(&env).unwrap();
+    let from: Address = topics.get(2).unwrap().try_into_val(&env).unwrap();
+    let to: Address = topics.get(3).unwrap().try_into_val(&env).unwrap();
+    assert_eq!(kind, symbol_short!("tx"));
+    assert_eq!(category, symbol_short!("release"));
+    assert_eq!(from, depositor);
+    assert_eq!(to, recipient);
+
+    let tx: AnalyticsTransaction = data.try_into_val(&env).unwrap();
+    assert_eq!(tx.tx_id, escrow_id);
+    assert_eq!(tx.amount, 1000);
+    assert_eq!(tx.from, depositor);
+    assert_eq!(tx.to, recipient);
+    assert_eq!(tx.timestamp, 12345);
+}
+
+#[test]
+fn test_analytics_events_disabled_by_default() {
+    let (env, _admin, _token, _token_client, token_admin, client) = setup_test_env();
+
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let escrow_id = create_test_escrow(
+        &env,
+        &client,
+        &token_admin,
+        &depositor,
+        &recipient,
+        1000,
+        20000,
+    );
+
+    assert!(!client.analytics_events_enabled());
+    client.release_escrow(&depositor, &escrow_id);
+
+    let (_contract, topics, _data) = env.events().all().last().unwrap();
+    let kind: Symbol = topics.get(0).unwrap().try_into_val(&env).unwrap();
+    assert_eq!(kind, symbol_short!("escrow"));
+}
+
+#[test]
+fn test_set_admin() {
+    let (env, admin, _token, _token_client, _token_admin, client) = setup_test_env();
+
+    let new_admin = Address::generate(&env);
+    client.set_admin(&admin, &new_admin);
+
+    assert_eq!(client.get_admin(), new_admin);
+}
+
+#[test]
+#[should_panic]
+fn test_set_admin_unauthorized() {
+    let (env, _admin, _token, _token_client, _token_admin, client) = setup_test_env();
+
+    let unauthorized = Address::generate(&env);
+    let new_admin = Address::generate(&env);
+
+    // Should panic due to unauthorized caller
+    client.set_admin(&unauthorized, &new_admin);
+}
+
+// ============================================
+// Pause Tests
+// ============================================
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #13)")]
+fn test_create_escrow_blocked_while_paused() {
+    let (env, admin, _token, _token_client, token_admin, client) = setup_test_env();
+    client.pause(&admin);
+
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    create_test_escrow(
+        &env,
+        &client,
+        &token_admin,
+        &depositor,
+        &recipient,
+        1000,
+        20000,
+    );
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #13)")]
+fn test_batch_reverse_blocked_while_paused() {
+    let (env, admin, _token, _token_client, token_admin, client) = setup_test_env();
+
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let escrow_id = create_test_escrow(
+        &env,
+        &client,
+        &token_admin,
+        &depositor,
+        &recipient,
+        1000,
+        20000,
+    );
+
+    client.pause(&admin);
+
+    let mut requests: Vec<ReversalRequest> = Vec::new(&env);
+    requests.push_back(create_reversal_request(escrow_id));
+    client.batch_reverse_escrows(&admin, &requests);
+}
+
+#[test]
+fn test_release_allowed_while_paused_and_resume_after_unpause() {
+    let (env, admin, _token, token_client, token_admin, client) = setup_test_env();
+
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let released_id = create_test_escrow(
+        &env,
+        &client,
+        &token_admin,
+        &depositor,
+        &recipient,
+        1000,
+        20000,
+    );
+    let reversed_id = create_test_escrow(
+        &env,
+        &client,
+        &token_admin,
+        &depositor,
+        &recipient,
+        2000,
+        20000,
+    );
+
+    client.pause(&admin);
+    assert!(client.is_paused());
+
+    client.release_escrow(&depositor, &released_id);
+    assert_eq!(token_client.balance(&recipient), 1000);
+
+    client.unpause(&admin);
+    assert!(!client.is_paused());
+
+    let mut requests: Vec<ReversalRequest> = Vec::new(&env);
+    requests.push_back(create_reversal_request(reversed_id));
+    let result = client.batch_reverse_escrows(&admin, &requests);
+    assert_eq!(result.successful, 1);
+
+    let new_id = create_test_escrow(
+        &env,
+        &client,
+        &token_admin,
+        &depositor,
+        &recipient,
+        500,
+        20000,
+    );
+    assert_eq!(
+        client.get_escrow(&new_id).unwrap().status,
+        EscrowStatus::Active
+    );
+}
+
+// ============================================
+// Expiring Escrow Tests
+// ============================================
+
+#[test]
+fn test_get_expiring_escrows_within_window() {
+    let (env, _admin, _token, _token_client, token_admin, client) = setup_test_env();
+
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    // Current ledger sequence is 12345
+    let soon = create_test_escrow(
+        &env,
+        &client,
+        &token_admin,
+        &depositor,
+        &recipient,
+        1000,
+        12400,
+    );
+    let later = create_test_escrow(
+        &env,
+        &client,
+        &token_admin,
+        &depositor,
+        &recipient,
+        1000,
+        13000,
+    );
+    let distant = create_test_escrow(
+        &env,
+        &client,
+        &token_admin,
+        &depositor,
+        &recipient,
+        1000,
+        20000,
+    );
+
+    let (within_100, next) = client.get_expiring_escrows(&100, &0, &10);
+    assert_eq!(within_100.len(), 1);
+    assert_eq!(within_100.get(0).unwrap(), soon);
+    assert_eq!(next, 3);
+
+    let (within_1000, _) = client.get_expiring_escrows(&1000, &0, &10);
+    assert_eq!(within_1000.len(), 2);
+    assert!(within_1000.contains(soon));
+    assert!(within_1000.contains(later));
+    assert!(!within_1000.contains(distant));
+}
+
+#[test]
+fn test_get_expiring_escrows_pages_by_scan_window() {
+    let (env, _admin, _token, _token_client, token_admin, client) = setup_test_env();
+
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let mut ids: Vec<u64> = Vec::new(&env);
+    for deadline in [20000, 12400, 20000, 13000, 20000] {
+        ids.push_back(create_test_escrow(
+            &env,
+            &client,
+            &token_admin,
+            &depositor,
+            &recipient,
+            1000,
+            deadline,
+        ));
+    }
+
+    // Pages cover the active index by position, not by match
+    let (first_page, next) = client.get_expiring_escrows(&1000, &0, &2);
+    assert_eq!(first_page, Vec::from_array(&env, [ids.get(1).unwrap()]));
+    assert_eq!(next, 2);
+
+    let (second_page, next) = client.get_expiring_escrows(&1000, &next, &2);
+    assert_eq!(second_page, Vec::from_array(&env, [ids.get(3).unwrap()]));
+    assert_eq!(next, 4);
+
+    let (last_page, next) = client.get_expiring_escrows(&1000, &next, &2);
+    assert_eq!(last_page.len(), 0);
+    assert_eq!(next, 5);
+
+    // Past the end of the index the cursor stays put
+    let (empty, next) = client.get_expiring_escrows(&1000, &next, &2);
+    assert_eq!(empty.len(), 0);
+    assert_eq!(next, 5);
+}
+
+#[test]
+fn test_settled_escrows_leave_expiring_index() {
+    let (env, admin, _token, _token_client, token_admin, client) = setup_test_env();
+
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let released = create_test_escrow(
+        &env,
+        &client,
+        &token_admin,
+        &depositor,
+        &recipient,
+        1000,
+        12400,
+    );
+    let reversed = create_test_escrow(
+        &env,
+        &client,
+        &token_admin,
+        &depositor,
+        &recipient,
+        1000,
+        12400,
+    );
+    let active = create_test_escrow(
+        &env,
+        &client,
+        &token_admin,
+        &depositor,
+        &recipient,
+        1000,
+        12400,
+    );
+
+    client.release_escrow(&depositor, &released);
+    let mut requests: Vec<ReversalRequest> = Vec::new(&env);
+    requests.push_back(create_reversal_request(reversed));
+    client.batch_reverse_escrows(&admin, &requests);
+
+    let (expiring, _) = client.get_expiring_escrows(&100, &0, &10);
+    assert_eq!(expiring.len(), 1);
+    assert_eq!(expiring.get(0).unwrap(), active);
+}
+
+#[test]
+fn test_get_escrows_by_status_tracks_transitions() {
+    let (env, admin, _token, _token_client, token_admin, client) = setup_test_env();
+
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let first = create_test_escrow(
+        &env,
+        &client,
+        &token_admin,
+        &depositor,
+        &recipient,
+        1000,
+        20000,
+    );
+    let second = create_test_escrow(
+        &env,
+        &client,
+        &token_admin,
+        &depositor,
+        &recipient,
+        1000,
+        20000,
+    );
+    let third = create_test_escrow(
+        &env,
+        &client,
+        &token_admin,
+        &depositor,
+        &recipient,
+        1000,
+        20000,
+    );
+
+    let active = client.get_escrows_by_status(&EscrowStatus::Active, &0, &10);
+    assert_eq!(active.len(), 3);
+
+    client.release_escrow(&depositor, &first);
+    let mut requests: Vec<ReversalRequest> = Vec::new(&env);
+    requests.push_back(create_reversal_request(second));
+    client.batch_reverse_escrows(&admin, &requests);
+
+    let active = client.get_escrows_by_status(&EscrowStatus::Active, &0, &10);
+    let released = client.get_escrows_by_status(&EscrowStatus::Released, &0, &10);
+    let reversed = client.get_escrows_by_status(&EscrowStatus::Reversed, &0, &10);
+
+    assert_eq!(active.len(), 1);
+    assert_eq!(active.get(0).unwrap(), third);
+    assert_eq!(released.len(), 1);
+    assert_eq!(released.get(0).unwrap(), first);
+    assert_eq!(reversed.len(), 1);
+    assert_eq!(reversed.get(0).unwrap(), second);
+}
+
+#[test]
+fn test_get_escrows_by_status_paginates() {
+    let (env, _admin, _token, _token_client, token_admin, client) = setup_test_env();
+
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    for _ in 0..5 {
+        create_test_escrow(
+            &env,
+            &client,
+            &token_admin,
+            &depositor,
+            &recipient,
+            1000,
+            20000,
+        );
+    }
+
+    let page = client.get_escrows_by_status(&EscrowStatus::Active, &1, &2);
+    assert_eq!(page.len(), 2);
+    assert_eq!(page.get(0).unwrap(), 2);
+    assert_eq!(page.get(1).unwrap(), 3);
+
+    assert_eq!(
+        client
+            .get_escrows_by_status(&EscrowStatus::Active, &4, &10)
+            .len(),
+        1
+    );
+    assert_eq!(
+        client
+            .get_escrows_by_status(&EscrowStatus::Active, &9, &10)
+            .len(),
+        0
+    );
+}
+
+#[test]
+fn test_get_escrows_by_status_spans_pages() {
+    let (env, _admin, _token, _token_client, token_admin, client) = setup_test_env();
+
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    for _ in 0..(STATUS_PAGE_SIZE + 5) {
+        create_test_escrow(
+            &env,
+            &client,
+            &token_admin,
+            &depositor,
+            &recipient,
+            1000,
+            20000,
+        );
+    }
+
+    // A page read straddling the first page boundary returns IDs in order
+    let page = client.get_escrows_by_status(&EscrowStatus::Active, &(STATUS_PAGE_SIZE - 2), &4);
+    assert_eq!(page.len(), 4);
+    assert_eq!(page.get(0).unwrap(), (STATUS_PAGE_SIZE - 1) as u64);
+    assert_eq!(page.get(3).unwrap(), (STATUS_PAGE_SIZE + 2) as u64);
+
+    // Releasing from the first page moves the last active ID into its slot
+    client.release_escrow(&depositor, &1);
+    let active = client.get_escrows_by_status(&EscrowStatus::Active, &0, &100);
+    assert_eq!(active.len(), STATUS_PAGE_SIZE + 4);
+    assert_eq!(active.get(0).unwrap(), (STATUS_PAGE_SIZE + 5) as u64);
+    assert!(!active.contains(1));
+    assert_eq!(
+        client
+            .get_escrows_by_status(&EscrowStatus::Released, &0, &10)
+            .get(0)
+            .unwrap(),
+        1
+    );
+}
+
+// ============================================
+// Escrow Amount Bounds Tests
+// ============================================
+
+#[test]
+fn test_create_escrow_within_bounds() {
+    let (env, admin, _token, _token_client, token_admin, client) = setup_test_env();
+    client.set_escrow_bounds(&admin, &100, &10_000);
+    assert_eq!(client.get_escrow_bounds(), (100, 10_000));
+
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let escrow_id = create_test_escrow(
+        &env,
+        &client,
+        &token_admin,
+        &depositor,
+        &recipient,
+        5_000,
+        20000,
+    );
+
+    assert_eq!(client.get_escrow(&escrow_id).unwrap().amount, 5_000);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #14)")]
+fn test_create_escrow_below_min_rejected() {
+    let (env, admin, _token, _token_client, token_admin, client) = setup_test_env();
+    client.set_escrow_bounds(&admin, &100, &10_000);
+
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    create_test_escrow(
+        &env,
+        &client,
+        &token_admin,
+        &depositor,
+        &recipient,
+        99,
+        20000,
+    );
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #15)")]
+fn test_create_escrow_above_max_rejected() {
+    let (env, admin, _token, _token_client, token_admin, client) = setup_test_env();
+    client.set_escrow_bounds(&admin, &100, &10_000);
+
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    create_test_escrow(
+        &env,
+        &client,
+        &token_admin,
+        &depositor,
+        &recipient,
+        10_001,
+        20000,
+    );
+}
+
+// ============================================
+// Deadline Extension Tests
+// ============================================
+
+#[test]
+fn test_batch_extend_deadlines_mixed_updates() {
+    let (env, admin, _token, _token_client, token_admin, client) = setup_test_env();
+
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+
+    let extendable = create_test_escrow(
+        &env,
+        &client,
+        &token_admin,
+        &depositor,
+        &recipient,
+        1_000,
+        20000,
+    );
+    let not_later = create_test_escrow(
+        &env,
+        &client,
+        &token_admin,
+        &depositor,
+        &recipient,
+        1_000,
+        20000,
+    );
+    let released = create_test_escrow(
+        &env,
+        &client,
+        &token_admin,
+        &depositor,
+        &recipient,
+        1_000,
+        20000,
+    );
+    client.release_escrow(&admin, &released);
+
+    let mut updates: Vec<DeadlineUpdate> = Vec::new(&env);
+    updates.push_back(DeadlineUpdate {
+        escrow_id: extendable,
+        new_deadline: 30000,
+    });
+    updates.push_back(DeadlineUpdate {
+        escrow_id: not_later,
+        new_deadline: 20000,
+    });
+    updates.push_back(DeadlineUpdate {
+        escrow_id: released,
+        new_deadline: 30000,
+    });
+    updates.push_back(DeadlineUpdate {
+        escrow_id: 999,
+        new_deadline: 30000,
+    });
+
+    let result = client.batch_extend_deadlines(&admin, &updates);
+
+    assert_eq!(result.total_requests, 4);
+    assert_eq!(result.successful, 1);
+    assert_eq!(result.failed, 3);
+    match result.results.get(0).unwrap() {
+        DeadlineUpdateResult::Success(id, deadline) => {
+            assert_eq!(id, extendable);
+            assert_eq!(deadline, 30000);
+        }
+        _ => panic!("Expected success"),
+    }
+    match result.results.get(1).unwrap() {
+        DeadlineUpdateResult::Failure(_, code) => assert_eq!(code, 10),
+        _ => panic!("Expected failure"),
+    }
+    match result.results.get(2).unwrap() {
+        DeadlineUpdateResult::Failure(_, code) => assert_eq!(code, 1),
+        _ => panic!("Expected failure"),
+    }
+    match result.results.get(3).unwrap() {
+        DeadlineUpdateResult::Failure(_, code) => assert_eq!(code, 0),
+        _ => panic!("Expected failure"),
+    }
+
+    // Only the valid update changed its escrow
+    assert_eq!(client.get_escrow(&extendable).unwrap().deadline, 30000);
+    assert_eq!(client.get_escrow(&not_later).unwrap().deadline, 20000);
+    assert_eq!(client.get_escrow(&released).unwrap().deadline, 20000);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #2)")]
+fn test_batch_extend_deadlines_requires_admin() {
+    let (env, _admin, _token, _token_client, token_admin, client) = setup_test_env();
+
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let escrow_id = create_test_escrow(
+        &env,
+        &client,
+        &token_admin,
+        &depositor,
+        &recipient,
+        1_000,
+        20000,
+    );
+
+    let mut updates: Vec<DeadlineUpdate> = Vec::new(&env);
+    updates.push_back(DeadlineUpdate {
+        escrow_id,
+        new_deadline: 30000,
+    });
+
+    client.batch_extend_deadlines(&depositor, &updates);
+}
+
+// ============================================
+// Release Fee Tests
+// ============================================
+
+#[test]
+fn test_release_fee_split_between_collector_and_recipient() {
+    let (env, admin, _token, token_client, token_admin, client) = setup_test_env();
+
+    let collector = Address::generate(&env);
+    client.set_release_fee(&admin, &250, &collector); // 2.5%
+    assert_eq!(client.get_release_fee_bps(), 250);
+    assert_eq!(client.get_fee_collector(), Some(collector.clone()));
+
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let escrow_id = create_test_escrow(
+        &env,
+        &client,
+        &token_admin,
+        &depositor,
+        &recipient,
+        10_000,
+        20000,
+    );
+
+    client.release_escrow(&admin, &escrow_id);
+
+    assert_eq!(token_client.balance(&collector), 250);
+    assert_eq!(token_client.balance(&recipient), 9_750);
+    assert_eq!(token_client.balance(&client.address), 0);
+}
+
+#[test]
+fn test_release_fee_on_large_escrow_does_not_overflow() {
+    let (env, admin, _token, token_client, token_admin, client) = setup_test_env();
+
+    let collector = Address::generate(&env);
+    client.set_release_fee(&admin, &250, &collector);
+
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let amount = i128::MAX / 2 + 1_234;
+    let escrow_id = create_test_escrow(
+        &env,
+        &client,
+        &token_admin,
+        &depositor,
+        &recipient,
+        amount,
+        20000,
+    );
+
+    client.release_escrow(&admin, &escrow_id);
+
+    let fee = amount / 10_000 * 250 + amount % 10_000 * 250 / 10_000;
+    assert_eq!(token_client.balance(&collector), fee);
+    assert_eq!(token_client.balance(&recipient), amount - fee);
+    assert_eq!(token_client.balance(&client.address), 0);
+}
+
+#[test]
+fn test_redirected_release_reports_net_amount() {
+    let (env, admin, _token, token_client, token_admin, client) = setup_test_env();
+
+    let collector = Address::generate(&env);
+    client.set_release_fee(&admin, &250, &collector);
+
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let assignee = Address::generate(&env);
+    let escrow_id = create_test_escrow(
+        &env,
+        &client,
+        &token_admin,
+        &depositor,
+        &recipient,
+        10_000,
+        20000,
+    );
+
+    client.release_escrow_to(&admin, &escrow_id, &assignee);
+
+    let (_contract, topics, data) = env.events().all().last().unwrap();
+    let kind: Symbol = topics.get(1).unwrap().try_into_val(&env).unwrap();
+    let (redirected_id, original, target, amount): (u64, Address, Address, i128) =
+        data.try_into_val(&env).unwrap();
+    assert_eq!(kind, symbol_short!("redirect"));
+    assert_eq!(redirected_id, escrow_id);
+    assert_eq!(original, recipient);
+    assert_eq!(target, assignee);
+    assert_eq!(amount, 9_750);
+    assert_eq!(token_client.balance(&assignee), 9_750);
+}
+
+#[test]
+fn test_reversal_is_fee_free() {
+    let (env, admin, _token, token_client, token_admin, client) = setup_test_env();
+
+    let collector = Address::generate(&env);
+    client.set_release_fee(&admin, &250, &collector);
+
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let escrow_id = create_test_escrow(
+        &env,
+        &client,
+        &token_admin,
+        &depositor,
+        &recipient,
+        10_000,
+        20000,
+    );
+
+    let mut requests: Vec<ReversalRequest> = Vec::new(&env);
+    requests.push_back(create_reversal_request(escrow_id));
+    client.batch_reverse_escrows(&admin, &requests);
+
+    assert_eq!(token_client.balance(&depositor), 10_000);
+    assert_eq!(token_client.balance(&collector), 0);
+}
+
+#[test]
+fn test_release_without_fee_pays_full_amount() {
+    let (env, admin, _token, token_client, token_admin, client) = setup_test_env();
+    assert_eq!(client.get_release_fee_bps(), 0);
+
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let escrow_id = create_test_escrow(
+        &env,
+        &client,
+        &token_admin,
+        &depositor,
+        &recipient,
+        10_000,
+        20000,
+    );
+
+    client.release_escrow(&admin, &escrow_id);
+
+    assert_eq!(token_client.balance(&recipient), 10_000);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #16)")]
+fn test_set_release_fee_rejects_above_10000_bps() {
+    let (env, admin, _token, _token_client, _token_admin, client) = setup_test_env();
+
+    client.set_release_fee(&admin, &10_001, &Address::generate(&env));
+}
+
+#[test]
+fn test_new_escrow_has_current_version() {
+    let (env, admin, _token, _token_client, token_admin, client) = setup_test_env();
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+
+    let escrow_id = create_test_escrow(
+        &env,
+        &client,
+        &token_admin,
+        &depositor,
+        &recipient,
+        1_000,
+        20_000,
+    );
+
+    assert_eq!(client.get_escrow_version(&escrow_id), ESCROW_VERSION);
+    // Already current, so migration leaves the record untouched
+    assert_eq!(client.migrate_escrow(&admin, &escrow_id), ESCROW_VERSION);
+}
+
+/// Drops the only active escrow from the status index, as for a record
+/// written before the index existed.
+fn clear_status_index(env: &Env, escrow_id: u64) {
+    let storage = env.storage().persistent();
+    storage.remove(&DataKey::StatusPosition(escrow_id));
+    storage.remove(&DataKey::StatusIndex(EscrowStatus::Active, 0));
+    storage.remove(&DataKey::StatusCount(EscrowStatus::Active));
+}
+
+#[test]
+fn test_migrate_escrow_upgrades_old_record() {
+    let (env, admin, _token, _token_client, token_admin, client) = setup_test_env();
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+
+    let escrow_id = create_test_escrow(
+        &env,
+        &client,
+        &token_admin,
+        &depositor,
+        &recipient,
+        1_000,
+        20_000,
+    );
+
+    // Rewrite the record in the layout used before versioning and payout tracking
+    env.as_contract(&client.address, || {
+        let key = DataKey::Escrow(escrow_id);
+        let escrow: crate::Escrow = env.storage().persistent().get(&key).unwrap();
+        let legacy = EscrowV0 {
+            escrow_id: escrow.escrow_id,
+            depositor: escrow.depositor,
+            recipient: escrow.recipient,
+            token: escrow.token,
+            amount: escrow.amount,
+            status: EscrowStatus::Released,
+            created_at: escrow.created_at,
+            deadline: escrow.deadline,
+        };
+        env.storage().persistent().set(&key, &legacy);
+        clear_status_index(&env, escrow_id);
+    });
+    assert_eq!(client.get_escrow_version(&escrow_id), 0);
+
+    let version = client.migrate_escrow(&admin, &escrow_id);
+
+    assert_eq!(version, ESCROW_VERSION);
+    let escrow = client.get_escrow(&escrow_id).unwrap();
+    assert_eq!(escrow.version, ESCROW_VERSION);
+    assert_eq!(escrow.paid_to, Some(recipient));
+    assert_eq!(escrow.amount, 1_000);
+    assert_eq!(
+        client.get_escrows_by_status(&EscrowStatus::Released, &0, &10),
+        Vec::from_array(&env, [escrow_id])
+    );
+    assert_eq!(
+        client
+            .get_escrows_by_status(&EscrowStatus::Active, &0, &10)
+            .len(),
+        0
+    );
+}
+
+#[test]
+fn test_migrate_escrow_upgrades_record_without_arbiter() {
+    let (env, admin, _token, _token_client, token_admin, client) = setup_test_env();
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+
+    let escrow_id = create_test_escrow(
+        &env,
+        &client,
+        &token_admin,
+        &depositor,
+        &recipient,
+        1_000,
+        20_000,
+    );
+
+    // Rewrite the record in the version 1 layout, before arbiters existed
+    env.as_contract(&client.address, || {
+        let key = DataKey::Escrow(escrow_id);
+        let escrow: crate::Escrow = env.storage().persistent().get(&key).unwrap();
+        let legacy = EscrowV1 {
+            escrow_id: escrow.escrow_id,
+            depositor: escrow.depositor,
+            recipient: escrow.recipient,
+            token: escrow.token,
+            amount: escrow.amount,
+            status: escrow.status,
+            created_at: escrow.created_at,
+            deadline: escrow.deadline,
+            paid_to: None,
+            version: 1,
+        };
+        env.storage().persistent().set(&key, &legacy);
+        clear_status_index(&env, escrow_id);
+    });
+    assert_eq!(client.get_escrow_version(&escrow_id), 1);
+
+    assert_eq!(client.migrate_escrow(&admin, &escrow_id), ESCROW_VERSION);
+    let escrow = client.get_escrow(&escrow_id).unwrap();
+    assert_eq!(escrow.version, ESCROW_VERSION);
+    assert_eq!(escrow.arbiter, None);
+    assert_eq!(escrow.paid_to, None);
+    assert_eq!(escrow.status, EscrowStatus::Active);
+    assert_eq!(
+        client.get_escrows_by_status(&EscrowStatus::Active, &0, &10),
+        Vec::from_array(&env, [escrow_id])
+    );
+
+    // The migrated record is usable again
+    client.release_escrow(&admin, &escrow_id);
+    assert_eq!(
+        client.get_escrow(&escrow_id).unwrap().status,
+        EscrowStatus::Released
+    );
+    assert_eq!(
+        client.get_escrows_by_status(&EscrowStatus::Released, &0, &10),
+        Vec::from_array(&env, [escrow_id])
+    );
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #6)")]
+fn test_migrate_escrow_not_found() {
+    let (_env, admin, _token, _token_client, _token_admin, client) = setup_test_env();
+
+    client.migrate_escrow(&admin, &99);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #17)")]
+fn test_create_escrow_rejects_deadline_inside_horizon() {
+    let (env, admin, _token, _token_client, token_admin, client) = setup_test_env();
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+
+    client.set_min_deadline_horizon(&admin, &1_000);
+    assert_eq!(client.get_min_deadline_horizon(), 1_000);
+
+    // Current sequence is 12345, so the earliest allowed deadline is 13345
+    create_test_escrow(
+        &env,
+        &client,
+        &token_admin,
+        &depositor,
+        &recipient,
+        1_000,
+        13_344,
+    );
+}
+
+#[test]
+fn test_create_escrow_accepts_deadline_at_horizon() {
+    let (env, admin, _token, _token_client, token_admin, client) = setup_test_env();
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+
+    client.set_min_deadline_horizon(&admin, &1_000);
+
+    let escrow_id = create_test_escrow(
+        &env,
+        &client,
+        &token_admin,
+        &depositor,
+        &recipient,
+        1_000,
+        13_345,
+    );
+
+    assert_eq!(client.get_escrow(&escrow_id).unwrap().deadline, 13_345);
+}
+
+#[test]
+fn test_settle_batch_mixes_releases_and_reversals() {
+    let (env, admin, _token, token_client, token_admin, client) = setup_test_env();
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+
+    let id1 = create_test_escrow(
+        &env,
+        &client,
+        &token_admin,
+        &depositor,
+        &recipient,
+        1_000,
+        20_000,
+    );
+    let id2 = create_test_escrow(
+        &env,
+        &client,
+        &token_admin,
+        &depositor,
+        &recipient,
+        2_000,
+        20_000,
+    );
+    let id3 = create_test_escrow(
+        &env,
+        &client,
+        &token_admin,
+        &depositor,
+        &recipient,
+        3_000,
+        20_000,
+    );
+
+    let releases = Vec::from_array(&env, [id1, id3, 99]);
+    let mut reversals: Vec<ReversalRequest> = Vec::new(&env);
+    reversals.push_back(create_reversal_request(id2));
+    // Released earlier in the same settlement, so the reversal must fail
+    reversals.push_back(create_reversal_request(id1));
+    reversals.push_back(create_reversal_request(98));
+
+    let result = client.settle_batch(&admin, &releases, &reversals);
+
+    assert_eq!(result.successful_releases, 2);
+    assert_eq!(result.failed_releases, 1);
+    assert_eq!(result.total_released, 4_000);
+    match result.release_results.get(2).unwrap() {
+        ReleaseResult::Failure(id, code) => {
+            assert_eq!(id, 99);
+            assert_eq!(code, 0); // ESCROW_NOT_FOUND
+        }
+        _ => panic!("Expected release of unknown escrow to fail"),
+    }
+
+    assert_eq!(result.reversals.successful, 1);
+    assert_eq!(result.reversals.failed, 2);
+    assert_eq!(result.reversals.total_reversed, 2_000);
+    match result.reversals.results.get(1).unwrap() {
+        ReversalResult::Failure(id, code) => {
+            assert_eq!(id, id1);
+            assert_eq!(code, 1); // ALREADY_RELEASED
+        }
+        _ => panic!("Expected reversal of released escrow to fail"),
+    }
+
+    assert_eq!(token_client.balance(&recipient), 4_000);
+    assert_eq!(token_client.balance(&depositor), 2_000);
+    assert_eq!(
+        client.get_escrow(&id2).unwrap().status,
+        EscrowStatus::Reversed
+    );
+}
+
+#[test]
+fn test_settle_batch_reports_net_released_after_fee() {
+    let (env, admin, _token, token_client, token_admin, client) = setup_test_env();
+    let collector = Address::generate(&env);
+    client.set_release_fee(&admin, &250, &collector); // 2.5%
+
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let id1 = create_test_escrow(
+        &env,
+        &client,
+        &token_admin,
+        &depositor,
+        &recipient,
+        10_000,
+        20_000,
+    );
+    let id2 = create_test_escrow(
+        &env,
+        &client,
+        &token_admin,
+        &depositor,
+        &recipient,
+        2_000,
+        20_000,
+    );
+
+    let releases = Vec::from_array(&env, [id1, id2]);
+    let result = client.settle_batch(&admin, &releases, &Vec::new(&env));
+
+    assert_eq!(result.total_released, 9_750 + 1_950);
+    assert_eq!(result.total_released, token_client.balance(&recipient));
+    match result.release_results.get(0).unwrap() {
+        ReleaseResult::Success(_, _, amount) => assert_eq!(amount, 9_750),
+        _ => panic!("Expected release to succeed"),
+    }
+}
+
+#[test]
+fn test_settle_batch_releases_allowed_while_paused() {
+    let (env, admin, _token, token_client, token_admin, client) = setup_test_env();
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let escrow_id = create_test_escrow(
+        &env,
+        &client,
+        &token_admin,
+        &depositor,
+        &recipient,
+        1_000,
+        20_000,
+    );
+
+    client.pause(&admin);
+
+    let releases = Vec::from_array(&env, [escrow_id]);
+    let result = client.settle_batch(&admin, &releases, &Vec::new(&env));
+
+    assert_eq!(result.successful_releases, 1);
+    assert_eq!(token_client.balance(&recipient), 1_000);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #13)")]
+fn test_settle_batch_reversals_blocked_while_paused() {
+    let (env, admin, _token, _token_client, token_admin, client) = setup_test_env();
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let id1 = create_test_escrow(
+        &env,
+        &client,
+        &token_admin,
+        &depositor,
+        &recipient,
+        1_000,
+        20_000,
+    );
+    let id2 = create_test_escrow(
+        &env,
+        &client,
+        &token_admin,
+        &depositor,
+        &recipient,
+        1_000,
+        20_000,
+    );
+
+    client.pause(&admin);
+
+    let releases = Vec::from_array(&env, [id1]);
+    let mut reversals: Vec<ReversalRequest> = Vec::new(&env);
+    reversals.push_back(create_reversal_request(id2));
+    client.settle_batch(&admin, &releases, &reversals);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #4)")]
+fn test_settle_batch_rejects_combined_size_over_limit() {
+    let (env, admin, _token, _token_client, _token_admin, client) = setup_test_env();
+
+    let mut releases: Vec<u64> = Vec::new(&env);
+    let mut reversals: Vec<ReversalRequest> = Vec::new(&env);
+    for id in 1..=60u64 {
+        releases.push_back(id);
+        reversals.push_back(create_reversal_request(id));
+    }
+
+    client.settle_batch(&admin, &releases, &reversals);
+}
+
+#[test]
+fn test_dispute_blocks_release_and_resolves_to_recipient() {
+    let (env, admin, _token, token_client, token_admin, client) = setup_test_env();
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+
+    let escrow_id = create_test_escrow(
+        &env,
+        &client,
+        &token_admin,
+        &depositor,
+        &recipient,
+        1_000,
+        20_000,
+    );
+
+    client.raise_dispute(&recipient, &escrow_id);
+    assert_eq!(
+        client.get_escrow(&escrow_id).unwrap().status,
+        EscrowStatus::Disputed
+    );
+
+    // Neither release nor reversal can move a disputed escrow
+    assert!(client.try_release_escrow(&depositor, &escrow_id).is_err());
+    assert!(client
+        .try_release_escrow_to(&admin, &escrow_id, &depositor)
+        .is_err());
+    let mut reversals: Vec<ReversalRequest> = Vec::new(&env);
+    reversals.push_back(create_reversal_request(escrow_id));
+    let reversal = client.batch_reverse_escrows(&admin, &reversals);
+    assert_eq!(reversal.failed, 1);
+    assert_eq!(token_client.balance(&recipient), 0);
+
+    client.resolve_dispute(&admin, &escrow_id, &true);
+
+    let escrow = client.get_escrow(&escrow_id).unwrap();
+    assert_eq!(escrow.status, EscrowStatus::Released);
+    assert_eq!(escrow.paid_to, Some(recipient.clone()));
+    assert_eq!(token_client.balance(&recipient), 1_000);
+    assert_eq!(
+        client
+            .get_escrows_by_status(&EscrowStatus::Disputed, &0, &10)
+            .len(),
+        0
+    );
+}
+
+#[test]
+fn test_dispute_resolved_by_reversal() {
+    let (env, admin, _token, token_client, token_admin, client) = setup_test_env();
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+
+    let escrow_id = create_test_escrow(
+        &env,
+        &client,
+        &token_admin,
+        &depositor,
+        &recipient,
+        1_000,
+        20_000,
+    );
+
+    client.raise_dispute(&depositor, &escrow_id);
+    client.resolve_dispute(&admin, &escrow_id, &false);
+    let events = env.events().all();
+
+    assert_eq!(
+        client.get_escrow(&escrow_id).unwrap().status,
+        EscrowStatus::Reversed
+    );
+    assert_eq!(token_client.balance(&depositor), 1_000);
+    assert_eq!(token_client.balance(&recipient), 0);
+
+    let reversal_event = events.iter().find(|(_contract, topics, _data)| {
+        let kind: Result<Symbol, _> = topics.get(1).unwrap().try_into_val(&env);
+        kind == Ok(symbol_short!("rev_ok"))
+    });
+    let (_contract, topics, data) = reversal_event.unwrap();
+    let batch_id: u64 = topics.get(2).unwrap().try_into_val(&env).unwrap();
+    let (reversed_id, to, amount): (u64, Address, i128) = data.try_into_val(&env).unwrap();
+    assert_eq!(batch_id, 0);
+    assert_eq!(reversed_id, escrow_id);
+    assert_eq!(to, depositor);
+    assert_eq!(amount, 1_000);
+
+    // Dispute reversals count towards the reversal totals but not the batches
+    assert_eq!(client.get_total_escrows_reversed(), 1);
+    assert_eq!(client.get_total_amount_reversed(), 1_000);
+    assert_eq!(client.get_total_reversal_batches(), 0);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #2)")]
+fn test_raise_dispute_by_third_party_rejected() {
+    let (env, _admin, _token, _token_client, token_admin, client) = setup_test_env();
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let stranger = Address::generate(&env);
+
+    let escrow_id = create_test_escrow(
+        &env,
+        &client,
+        &token_admin,
+        &depositor,
+        &recipient,
+        1_000,
+        20_000,
+    );
+
+    client.raise_dispute(&stranger, &escrow_id);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #18)")]
+fn test_resolve_dispute_requires_open_dispute() {
+    let (env, admin, _token, _token_client, token_admin, client) = setup_test_env();
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+
+    let escrow_id = create_test_escrow(
+        &env,
+        &client,
+        &token_admin,
+        &depositor,
+        &recipient,
+        1_000,
+        20_000,
+    );
+
+    client.resolve_dispute(&admin, &escrow_id, &true);
+}
+
+// ============================================
+// Arbiter Tests
+// ============================================
+
+#[test]
+fn test_arbiter_can_release_and_reverse_own_escrows_only() {
+    let (env, _admin, _token, token_client, token_admin, client) = setup_test_env();
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let arbiter = Address::generate(&env);
+
+    token_admin.mint(&depositor, &3_000);
+    let released = client.create_escrow(
+        &depositor,
+        &recipient,
+        &1_000,
+        &20_000,
+        &Some(arbiter.clone()),
+    );
+    let reversed = client.create_escrow(
+        &depositor,
+        &recipient,
+        &1_000,
+        &20_000,
+        &Some(arbiter.clone()),
+    );
+    let other = client.create_escrow(&depositor, &recipient, &1_000, &20_000, &None);
+
+    assert_eq!(
+        client.get_escrow(&released).unwrap().arbiter,
+        Some(arbiter.clone())
+    );
+
+    client.release_escrow(&arbiter, &released);
+    assert_eq!(
+        client.get_escrow(&released).unwrap().status,
+        EscrowStatus::Released
+    );
+    assert_eq!(token_client.balance(&recipient), 1_000);
+
+    let mut requests: Vec<ReversalRequest> = Vec::new(&env);
+    requests.push_back(create_reversal_request(reversed));
+    let result = client.batch_reverse_escrows(&arbiter, &requests);
+    assert_eq!(result.successful, 1);
+    assert_eq!(token_client.balance(&depositor), 1_000);
+
+    // The arbiter has no say over escrows they were not assigned to
+    assert!(client.try_release_escrow(&arbiter, &other).is_err());
+    let mut requests: Vec<ReversalRequest> = Vec::new(&env);
+    requests.push_back(create_reversal_request(other));
+    assert!(client
+        .try_batch_reverse_escrows(&arbiter, &requests)
+        .is_err());
+    assert_eq!(
+        client.get_escrow(&other).unwrap().status,
+        EscrowStatus::Active
+    );
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #2)")]
+fn test_release_by_non_arbiter_rejected() {
+    let (env, _admin, _token, _token_client, token_admin, client) = setup_test_env();
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let arbiter = Address::generate(&env);
+    let stranger = Address::generate(&env);
+
+    token_admin.mint(&depositor, &1_000);
+    let escrow_id = client.create_escrow(&depositor, &recipient, &1_000, &20_000, &Some(arbiter));
+
+    client.release_escrow(&stranger, &escrow_id);
 }
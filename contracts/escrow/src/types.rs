@@ -1,10 +1,26 @@
 //! Data types and events for the escrow contract.
 
-use soroban_sdk::{contracttype, symbol_short, Address, Env, Vec};
+use soroban_sdk::{contractclient, contracttype, symbol_short, Address, Env, Symbol, Vec};
 
 /// Maximum number of escrows in a single batch operation.
 pub const MAX_BATCH_SIZE: u32 = 100;
 
+/// Number of escrow IDs stored in each page of a status index.
+pub const STATUS_PAGE_SIZE: u32 = 50;
+
+/// Current layout version of stored `Escrow` records.
+///
+/// Version 0 marks records written before versioning was introduced; version 1
+/// records predate per-escrow arbiters.
+pub const ESCROW_VERSION: u32 = 2;
+
+/// Interface of the delivery oracle consulted by `release_if_confirmed`.
+#[contractclient(name = "ConfirmationOracleClient")]
+pub trait ConfirmationOracle {
+    /// Returns whether delivery for `escrow_id` has been confirmed.
+    fn is_confirmed(env: Env, escrow_id: u64) -> bool;
+}
+
 /// Escrow status enum.
 #[derive(Clone, Debug, PartialEq, Eq)]
 #[contracttype]
@@ -15,6 +31,8 @@ pub enum EscrowStatus {
     Released,
     /// Funds returned to depositor (reversed)
     Reversed,
+    /// Funds frozen pending admin resolution of a dispute
+    Disputed,
 }
 
 /// An escrow record.
@@ -29,6 +47,51 @@ pub struct Escrow {
     pub status: EscrowStatus,
     pub created_at: u64,
     pub deadline: u64,
+    /// Address the funds were actually paid to once released
+    pub paid_to: Option<Address>,
+    /// Layout version the record was written with
+    pub version: u32,
+    /// Optional arbiter who may release or reverse this escrow alongside the admin
+    pub arbiter: Option<Address>,
+}
+
+/// Escrow record as stored before layout versioning was introduced (version 0).
+#[derive(Clone, Debug)]
+#[contracttype]
+pub struct EscrowV0 {
+    pub escrow_id: u64,
+    pub depositor: Address,
+    pub recipient: Address,
+    pub token: Address,
+    pub amount: i128,
+    pub status: EscrowStatus,
+    pub created_at: u64,
+    pub deadline: u64,
+}
+
+/// Escrow record as stored before per-escrow arbiters were introduced (version 1).
+#[derive(Clone, Debug)]
+#[contracttype]
+pub struct EscrowV1 {
+    pub escrow_id: u64,
+    pub depositor: Address,
+    pub recipient: Address,
+    pub token: Address,
+    pub amount: i128,
+    pub status: EscrowStatus,
+    pub created_at: u64,
+    pub deadline: u64,
+    pub paid_to: Option<Address>,
+    pub version: u32,
+}
+
+/// Request to create an escrow as part of a batch.
+#[derive(Clone, Debug)]
+#[contracttype]
+pub struct EscrowRequest {
+    pub recipient: Address,
+    pub amount: i128,
+    pub deadline: u64,
 }
 
 /// Request to reverse an escrow.
@@ -60,6 +123,57 @@ pub struct BatchReversalResult {
     pub results: Vec<ReversalResult>,
 }
 
+/// Result of a single escrow release within a settlement.
+#[derive(Clone, Debug)]
+#[contracttype]
+pub enum ReleaseResult {
+    /// Successful release: escrow_id, recipient, amount paid after the release fee
+    Success(u64, Address, i128),
+    /// Failed release: escrow_id, error_code
+    Failure(u64, u32),
+}
+
+/// Combined result of a settlement batch.
+#[derive(Clone, Debug)]
+#[contracttype]
+pub struct SettlementResult {
+    pub successful_releases: u32,
+    pub failed_releases: u32,
+    /// Total paid to recipients, net of release fees
+    pub total_released: i128,
+    pub release_results: Vec<ReleaseResult>,
+    /// Reversal sub-result; `batch_id` is 0 when no reversals were requested
+    pub reversals: BatchReversalResult,
+}
+
+/// Request to move an escrow's deadline later.
+#[derive(Clone, Debug)]
+#[contracttype]
+pub struct DeadlineUpdate {
+    pub escrow_id: u64,
+    pub new_deadline: u64,
+}
+
+/// Result of a single deadline update.
+#[derive(Clone, Debug)]
+#[contracttype]
+pub enum DeadlineUpdateResult {
+    /// Successful update: escrow_id, new_deadline
+    Success(u64, u64),
+    /// Failed update: escrow_id, error_code
+    Failure(u64, u32),
+}
+
+/// Summary result of a batch deadline extension.
+#[derive(Clone, Debug)]
+#[contracttype]
+pub struct BatchDeadlineResult {
+    pub total_requests: u32,
+    pub successful: u32,
+    pub failed: u32,
+    pub results: Vec<DeadlineUpdateResult>,
+}
+
 /// Storage keys for the escrow contract.
 #[derive(Clone)]
 #[contracttype]
@@ -80,6 +194,43 @@ pub enum DataKey {
     TotalEscrowsReversed,
     /// Total amount reversed
     TotalAmountReversed,
+    /// Recipient-requested release awaiting depositor approval
+    PendingRelease(u64),
+    /// Whether releases and reversals also emit analytics-compatible events
+    AnalyticsEventsEnabled,
+    /// Oracle contract confirming delivery for conditional releases
+    Oracle,
+    /// Emergency stop for escrow creation and reversal
+    Paused,
+    /// Page of IDs of escrows currently in a given status
+    StatusIndex(EscrowStatus, u32),
+    /// Number of escrows currently in a given status
+    StatusCount(EscrowStatus),
+    /// Position of an escrow within its current status index
+    StatusPosition(u64),
+    /// Minimum escrow amount
+    MinEscrowAmount,
+    /// Maximum escrow amount (0 means no maximum)
+    MaxEscrowAmount,
+    /// Fee in basis points charged on each release
+    ReleaseFeeBps,
+    /// Address receiving release fees
+    FeeCollector,
+    /// Minimum number of ledgers between creation and an escrow's deadline
+    MinDeadlineHorizon,
+}
+
+/// Escrow movement in the shape of the analytics contract's `Transaction`.
+#[derive(Clone, Debug)]
+#[contracttype]
+pub struct AnalyticsTransaction {
+    pub tx_id: u64,
+    pub from: Address,
+    pub to: Address,
+    pub amount: i128,
+    /// Ledger sequence the movement happened at
+    pub timestamp: u64,
+    pub category: Symbol,
 }
 
 /// Event emitters for escrow operations.
@@ -136,9 +287,90 @@ impl EscrowEvents {
         env.events().publish(topics, (successful, failed, total_reversed));
     }
 
+    /// Emitted when an atomic batch of escrows is created.
+    pub fn atomic_batch_created(
+        env: &Env,
+        depositor: &Address,
+        escrow_count: u32,
+        total_amount: i128,
+    ) {
+        let topics = (symbol_short!("escrow"), symbol_short!("atomic"));
+        env.events()
+            .publish(topics, (depositor.clone(), escrow_count, total_amount));
+    }
+
+    /// Emitted when a recipient requests release of an escrow.
+    pub fn release_requested(env: &Env, escrow_id: u64, recipient: &Address) {
+        let topics = (symbol_short!("escrow"), symbol_short!("rel_req"));
+        env.events().publish(topics, (escrow_id, recipient.clone()));
+    }
+
+    /// Emitted when a depositor or recipient disputes an escrow.
+    pub fn dispute_raised(env: &Env, escrow_id: u64, raised_by: &Address) {
+        let topics = (symbol_short!("escrow"), symbol_short!("disputed"));
+        env.events().publish(topics, (escrow_id, raised_by.clone()));
+    }
+
+    /// Emitted when the admin resolves a dispute; `released` is false for a reversal.
+    pub fn dispute_resolved(env: &Env, escrow_id: u64, released: bool) {
+        let topics = (symbol_short!("escrow"), symbol_short!("resolved"));
+        env.events().publish(topics, (escrow_id, released));
+    }
+
+    /// Emitted when an escrow's deadline is extended.
+    pub fn deadline_extended(env: &Env, escrow_id: u64, old_deadline: u64, new_deadline: u64) {
+        let topics = (symbol_short!("escrow"), symbol_short!("extended"));
+        env.events()
+            .publish(topics, (escrow_id, old_deadline, new_deadline));
+    }
+
     /// Emitted when an escrow is released to recipient.
-    pub fn escrow_released(env: &Env, escrow_id: u64, recipient: &Address, amount: i128) {
+    ///
+    /// `amount` is what the recipient received; `fee` went to the fee collector.
+    pub fn escrow_released(
+        env: &Env,
+        escrow_id: u64,
+        recipient: &Address,
+        amount: i128,
+        fee: i128,
+    ) {
         let topics = (symbol_short!("escrow"), symbol_short!("released"));
-        env.events().publish(topics, (escrow_id, recipient.clone(), amount));
+        env.events()
+            .publish(topics, (escrow_id, recipient.clone(), amount, fee));
+    }
+
+    /// Emitted when an escrow is released to an address other than its recipient;
+    /// `amount` is what the target received, net of any release fee.
+    pub fn escrow_redirected(
+        env: &Env,
+        escrow_id: u64,
+        original_recipient: &Address,
+        target: &Address,
+        amount: i128,
+    ) {
+        let topics = (symbol_short!("escrow"), symbol_short!("redirect"));
+        env.events().publish(
+            topics,
+            (
+                escrow_id,
+                original_recipient.clone(),
+                target.clone(),
+                amount,
+            ),
+        );
+    }
+
+    /// Analytics-compatible event for a completed escrow flow.
+    ///
+    /// Topics carry the category and both parties; the data is an
+    /// `AnalyticsTransaction` matching the analytics contract's transaction shape.
+    pub fn analytics_transaction(env: &Env, tx: &AnalyticsTransaction) {
+        let topics = (
+            symbol_short!("tx"),
+            tx.category.clone(),
+            tx.from.clone(),
+            tx.to.clone(),
+        );
+        env.events().publish(topics, tx.clone());
     }
 }
@@ -1,6 +1,6 @@
-//! Validation utilities for escrow reversals.
+//! Validation utilities for escrow creation and reversals.
 
-use crate::types::{Escrow, EscrowStatus};
+use crate::types::{Escrow, EscrowRequest, EscrowStatus};
 use soroban_sdk::Address;
 
 /// Error codes for reversal validation.
@@ -16,6 +16,20 @@ pub mod ErrorCode {
     pub const UNAUTHORIZED: u32 = 3;
     /// Deadline not yet reached (for time-based reversals)
     pub const DEADLINE_NOT_REACHED: u32 = 4;
+    /// Escrow amount is not positive
+    pub const INVALID_AMOUNT: u32 = 5;
+    /// Escrow deadline is not in the future
+    pub const INVALID_DEADLINE: u32 = 6;
+    /// Depositor and recipient are the same address
+    pub const SELF_ESCROW: u32 = 7;
+    /// Escrow amount is below the configured minimum
+    pub const BELOW_MIN_AMOUNT: u32 = 8;
+    /// Escrow amount is above the configured maximum
+    pub const ABOVE_MAX_AMOUNT: u32 = 9;
+    /// New deadline does not extend the current one
+    pub const DEADLINE_NOT_EXTENDED: u32 = 10;
+    /// Escrow is frozen by an open dispute
+    pub const ESCROW_DISPUTED: u32 = 11;
 }
 
 /// Validation error types for reversals.
@@ -31,17 +45,38 @@ pub enum ValidationError {
     Unauthorized,
     /// Deadline has not been reached yet
     DeadlineNotReached,
+    /// Amount is zero or negative
+    InvalidAmount,
+    /// Deadline is not after the current ledger
+    InvalidDeadline,
+    /// Depositor is escrowing to themselves
+    SelfEscrow,
+    /// Amount is below the configured minimum
+    BelowMinAmount,
+    /// Amount is above the configured maximum
+    AboveMaxAmount,
+    /// New deadline is not after the current deadline
+    DeadlineNotExtended,
+    /// Escrow is frozen by an open dispute
+    Disputed,
 }
 
 impl ValidationError {
     /// Convert to error code for result tracking.
-    pub fn to_error_code(&self) -> u32 {
+    pub fn to_error_code(self) -> u32 {
         match self {
             ValidationError::EscrowNotFound => ErrorCode::ESCROW_NOT_FOUND,
             ValidationError::AlreadyReleased => ErrorCode::ALREADY_RELEASED,
             ValidationError::AlreadyReversed => ErrorCode::ALREADY_REVERSED,
             ValidationError::Unauthorized => ErrorCode::UNAUTHORIZED,
             ValidationError::DeadlineNotReached => ErrorCode::DEADLINE_NOT_REACHED,
+            ValidationError::InvalidAmount => ErrorCode::INVALID_AMOUNT,
+            ValidationError::InvalidDeadline => ErrorCode::INVALID_DEADLINE,
+            ValidationError::SelfEscrow => ErrorCode::SELF_ESCROW,
+            ValidationError::BelowMinAmount => ErrorCode::BELOW_MIN_AMOUNT,
+            ValidationError::AboveMaxAmount => ErrorCode::ABOVE_MAX_AMOUNT,
+            ValidationError::DeadlineNotExtended => ErrorCode::DEADLINE_NOT_EXTENDED,
+            ValidationError::Disputed => ErrorCode::ESCROW_DISPUTED,
         }
     }
 }
@@ -72,29 +107,127 @@ pub fn validate_reversal(
     match escrow.status {
         EscrowStatus::Released => return Err(ValidationError::AlreadyReleased),
         EscrowStatus::Reversed => return Err(ValidationError::AlreadyReversed),
+        EscrowStatus::Disputed => return Err(ValidationError::Disputed),
         EscrowStatus::Active => {}
     }
 
-    // Check authorization: admin or depositor can reverse
+    // Check authorization: admin, the escrow's arbiter, or depositor can reverse
     let is_admin = caller == admin;
+    let is_arbiter = escrow.arbiter.as_ref() == Some(caller);
     let is_depositor = caller == &escrow.depositor;
 
-    if !is_admin && !is_depositor {
+    if !is_admin && !is_arbiter && !is_depositor {
         return Err(ValidationError::Unauthorized);
     }
 
-    // If not admin and deadline check is enabled, verify deadline has passed
-    if check_deadline && !is_admin && current_ledger < escrow.deadline {
+    // If neither admin nor arbiter and deadline check is enabled, verify deadline has passed
+    if check_deadline && !is_admin && !is_arbiter && current_ledger < escrow.deadline {
         return Err(ValidationError::DeadlineNotReached);
     }
 
     Ok(())
 }
 
+/// Validates a request to create an escrow.
+///
+/// # Arguments
+/// * `request` - The escrow creation request
+/// * `depositor` - The address funding the escrow
+/// * `current_ledger` - Current ledger sequence for deadline comparison
+///
+/// # Returns
+/// * `Ok(())` if the escrow can be created
+/// * `Err(ValidationError)` with specific error if invalid
+pub fn validate_escrow_request(
+    request: &EscrowRequest,
+    depositor: &Address,
+    current_ledger: u64,
+) -> Result<(), ValidationError> {
+    if request.amount <= 0 {
+        return Err(ValidationError::InvalidAmount);
+    }
+
+    if request.deadline <= current_ledger {
+        return Err(ValidationError::InvalidDeadline);
+    }
+
+    if &request.recipient == depositor {
+        return Err(ValidationError::SelfEscrow);
+    }
+
+    Ok(())
+}
+
+/// Validates whether an escrow can be released.
+///
+/// # Returns
+/// * `Ok(())` if the escrow exists and is still active
+/// * `Err(ValidationError)` otherwise
+pub fn validate_release(escrow: Option<&Escrow>) -> Result<(), ValidationError> {
+    match escrow.map(|e| &e.status) {
+        None => Err(ValidationError::EscrowNotFound),
+        Some(EscrowStatus::Active) => Ok(()),
+        Some(EscrowStatus::Released) => Err(ValidationError::AlreadyReleased),
+        Some(EscrowStatus::Reversed) => Err(ValidationError::AlreadyReversed),
+        Some(EscrowStatus::Disputed) => Err(ValidationError::Disputed),
+    }
+}
+
+/// Validates a request to extend an escrow's deadline.
+///
+/// # Arguments
+/// * `escrow` - Optional escrow record (None if not found)
+/// * `new_deadline` - The requested deadline
+///
+/// # Returns
+/// * `Ok(())` if the escrow is active and the deadline moves later
+/// * `Err(ValidationError)` with specific error if invalid
+pub fn validate_deadline_extension(
+    escrow: Option<&Escrow>,
+    new_deadline: u64,
+) -> Result<(), ValidationError> {
+    let escrow = escrow.ok_or(ValidationError::EscrowNotFound)?;
+
+    match escrow.status {
+        EscrowStatus::Released => return Err(ValidationError::AlreadyReleased),
+        EscrowStatus::Reversed => return Err(ValidationError::AlreadyReversed),
+        EscrowStatus::Disputed => return Err(ValidationError::Disputed),
+        EscrowStatus::Active => {}
+    }
+
+    if new_deadline <= escrow.deadline {
+        return Err(ValidationError::DeadlineNotExtended);
+    }
+
+    Ok(())
+}
+
+/// Validates an escrow amount against the configured bounds.
+///
+/// # Arguments
+/// * `amount` - The escrow amount
+/// * `min` - Minimum allowed amount
+/// * `max` - Maximum allowed amount (0 means no maximum)
+///
+/// # Returns
+/// * `Ok(())` if the amount is within bounds
+/// * `Err(ValidationError)` naming the violated bound
+pub fn validate_amount_bounds(amount: i128, min: i128, max: i128) -> Result<(), ValidationError> {
+    if amount < min {
+        return Err(ValidationError::BelowMinAmount);
+    }
+
+    if max > 0 && amount > max {
+        return Err(ValidationError::AboveMaxAmount);
+    }
+
+    Ok(())
+}
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::types::ESCROW_VERSION;
     use soroban_sdk::{testutils::Address as _, Env};
 
     fn create_test_escrow(env: &Env, status: EscrowStatus) -> Escrow {
@@ -107,6 +240,9 @@ mod tests {
             status,
             created_at: 100,
             deadline: 200,
+            paid_to: None,
+            version: ESCROW_VERSION,
+            arbiter: None,
         }
     }
 
@@ -220,5 +356,109 @@ mod tests {
         assert_eq!(ValidationError::AlreadyReversed.to_error_code(), ErrorCode::ALREADY_REVERSED);
         assert_eq!(ValidationError::Unauthorized.to_error_code(), ErrorCode::UNAUTHORIZED);
         assert_eq!(ValidationError::DeadlineNotReached.to_error_code(), ErrorCode::DEADLINE_NOT_REACHED);
+        assert_eq!(ValidationError::InvalidAmount.to_error_code(), ErrorCode::INVALID_AMOUNT);
+        assert_eq!(ValidationError::InvalidDeadline.to_error_code(), ErrorCode::INVALID_DEADLINE);
+        assert_eq!(ValidationError::SelfEscrow.to_error_code(), ErrorCode::SELF_ESCROW);
+    }
+
+    #[test]
+    fn test_validate_escrow_request() {
+        let env = Env::default();
+        let depositor = Address::generate(&env);
+        let recipient = Address::generate(&env);
+
+        let valid = EscrowRequest {
+            recipient: recipient.clone(),
+            amount: 100,
+            deadline: 200,
+        };
+        assert!(validate_escrow_request(&valid, &depositor, 100).is_ok());
+
+        let zero = EscrowRequest {
+            recipient: recipient.clone(),
+            amount: 0,
+            deadline: 200,
+        };
+        assert_eq!(
+            validate_escrow_request(&zero, &depositor, 100),
+            Err(ValidationError::InvalidAmount)
+        );
+
+        let past = EscrowRequest {
+            recipient: recipient.clone(),
+            amount: 100,
+            deadline: 100,
+        };
+        assert_eq!(
+            validate_escrow_request(&past, &depositor, 100),
+            Err(ValidationError::InvalidDeadline)
+        );
+
+        let own = EscrowRequest {
+            recipient: depositor.clone(),
+            amount: 100,
+            deadline: 200,
+        };
+        assert_eq!(
+            validate_escrow_request(&own, &depositor, 100),
+            Err(ValidationError::SelfEscrow)
+        );
+    }
+
+    #[test]
+    fn test_validate_deadline_extension() {
+        let env = Env::default();
+        let active = create_test_escrow(&env, EscrowStatus::Active);
+        let released = create_test_escrow(&env, EscrowStatus::Released);
+
+        assert!(validate_deadline_extension(Some(&active), 201).is_ok());
+        assert_eq!(
+            validate_deadline_extension(Some(&active), 200),
+            Err(ValidationError::DeadlineNotExtended)
+        );
+        assert_eq!(
+            validate_deadline_extension(Some(&released), 300),
+            Err(ValidationError::AlreadyReleased)
+        );
+        assert_eq!(
+            validate_deadline_extension(None, 300),
+            Err(ValidationError::EscrowNotFound)
+        );
+    }
+
+    #[test]
+    fn test_validate_amount_bounds() {
+        assert!(validate_amount_bounds(500, 100, 1000).is_ok());
+        assert!(validate_amount_bounds(100, 100, 1000).is_ok());
+        assert!(validate_amount_bounds(1000, 100, 1000).is_ok());
+        assert_eq!(
+            validate_amount_bounds(99, 100, 1000),
+            Err(ValidationError::BelowMinAmount)
+        );
+        assert_eq!(
+            validate_amount_bounds(1001, 100, 1000),
+            Err(ValidationError::AboveMaxAmount)
+        );
+        // Zero max means no upper bound
+        assert!(validate_amount_bounds(i128::MAX, 100, 0).is_ok());
+    }
+
+    #[test]
+    fn test_validate_release() {
+        let env = Env::default();
+        let active = create_test_escrow(&env, EscrowStatus::Active);
+        let released = create_test_escrow(&env, EscrowStatus::Released);
+        let reversed = create_test_escrow(&env, EscrowStatus::Reversed);
+
+        assert!(validate_release(Some(&active)).is_ok());
+        assert_eq!(validate_release(None), Err(ValidationError::EscrowNotFound));
+        assert_eq!(
+            validate_release(Some(&released)),
+            Err(ValidationError::AlreadyReleased)
+        );
+        assert_eq!(
+            validate_release(Some(&reversed)),
+            Err(ValidationError::AlreadyReversed)
+        );
     }
 }
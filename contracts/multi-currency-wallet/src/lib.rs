@@ -25,11 +25,15 @@
 mod types;
 mod validation;
 
-use soroban_sdk::{contract, contractimpl, panic_with_error, Address, Env, Symbol, Vec};
+use soroban_sdk::{
+    contract, contractimpl, panic_with_error, symbol_short, Address, Env, Map, Symbol, Vec,
+};
 
 pub use crate::types::{
     BalanceUpdateRequest, BalanceUpdateResult, BatchBalanceMetrics, BatchBalanceResult,
-    CurrencyBalance, DataKey, ErrorCode, WalletEvents, MAX_BATCH_SIZE,
+    CurrencyBalance, DataKey, ErrorCode, SpendingLimitsClient, SpendingLimitsInterface,
+    UpdateReceipt, WalletEvents, DEFAULT_CURRENCY_DECIMALS, DEFAULT_LARGE_BALANCE_THRESHOLD,
+    MAX_BALANCE_HISTORY, MAX_BATCH_SIZE,
 };
 use crate::validation::{validate_and_compute_balance, validate_balance_request};
 
@@ -47,6 +51,14 @@ pub enum WalletError {
     EmptyBatch = 4,
     /// Batch exceeds maximum size
     BatchTooLarge = 5,
+    /// Large change factor must be greater than 1
+    InvalidChangeFactor = 6,
+    /// Credit floor must not be negative
+    InvalidCreditFloor = 7,
+    /// Large balance threshold must be positive
+    InvalidThreshold = 8,
+    /// Account still holds a nonzero balance
+    AccountNotEmpty = 9,
 }
 
 impl From<WalletError> for soroban_sdk::Error {
@@ -95,10 +107,12 @@ impl MultiCurrencyWalletContract {
     ///
     /// # Events Emitted
     /// * `batch_started` - When processing begins
-    /// * `balance_updated` - For each successful balance update
-    /// * `balance_update_failed` - For each failed balance update
+    /// * `balance_updated` - For each successful balance update, unless verbose events are off
+    /// * `balance_update_failed` - For each failed balance update, unless verbose events are off
     /// * `large_balance_update` - For large balance values
+    /// * `large_relative_change` - When a balance moves by more than the configured factor
     /// * `batch_completed` - When processing completes
+    /// * `batch_failure_summary` - Failure counts per error code, when any update failed
     ///
     /// # Errors
     /// * `EmptyBatch` - If no requests provided
@@ -140,16 +154,30 @@ impl MultiCurrencyWalletContract {
         let mut results: Vec<BalanceUpdateResult> = Vec::new(&env);
         let mut successful_count: u32 = 0;
         let mut failed_count: u32 = 0;
+        let mut failure_counts: Map<u32, u32> = Map::new(&env);
+
+        // Relative change detection is only active once a factor is configured
+        let change_factor: Option<u32> = env.storage().instance().get(&DataKey::LargeChangeFactor);
+
+        // Per-item balance events can be switched off to save gas on large batches
+        let verbose = Self::get_verbose_events(env.clone());
+        let receipts_enabled = Self::get_receipts_enabled(env.clone());
 
         // Track unique users and currencies for metrics
         let mut unique_users: Vec<Address> = Vec::new(&env);
         let mut unique_currencies: Vec<Symbol> = Vec::new(&env);
 
         // Process each request
-        for request in requests.iter() {
+        for (index, request) in requests.iter().enumerate() {
             // Validate the request
             match validate_balance_request(&request) {
                 Ok(()) => {
+                    let previous_balance = Self::get_balance(
+                        env.clone(),
+                        request.user.clone(),
+                        request.currency.clone(),
+                    );
+
                     // Validate and compute new balance
                     match validate_and_compute_balance(
                         &env,
@@ -169,12 +197,23 @@ impl MultiCurrencyWalletContract {
 
                             successful_count += 1;
 
+                            // Count the successful operation
+                            let operation_key = DataKey::OperationCount(request.operation.clone());
+                            let operation_count: u64 =
+                                env.storage().instance().get(&operation_key).unwrap_or(0);
+                            env.storage()
+                                .instance()
+                                .set(&operation_key, &(operation_count + 1));
+
                             // Store the balance (optimized - one write per balance)
                             env.storage().persistent().set(
                                 &DataKey::Balance(request.user.clone(), request.currency.clone()),
                                 &balance,
                             );
 
+                            // Index the currency for the user
+                            Self::index_user_currency(&env, &request.user, &request.currency);
+
                             // Track unique users
                             if !contains_address(&unique_users, &request.user) {
                                 unique_users.push_back(request.user.clone());
@@ -186,10 +225,26 @@ impl MultiCurrencyWalletContract {
                             }
 
                             // Emit success event
-                            WalletEvents::balance_updated(&env, batch_id, &balance);
+                            if verbose {
+                                WalletEvents::balance_updated(&env, batch_id, &balance);
+                            }
 
-                            // Emit large balance event if applicable (>= 1,000,000 units)
-                            if new_balance >= 1_000_000 {
+                            // Flag accounts closed out by a subtract
+                            if new_balance == 0 && request.operation == symbol_short!("subtract") {
+                                WalletEvents::balance_zeroed(
+                                    &env,
+                                    batch_id,
+                                    &request.user,
+                                    &request.currency,
+                                );
+                            }
+
+                            // Emit large balance event if the currency's threshold is reached
+                            let threshold = Self::get_large_balance_threshold(
+                                env.clone(),
+                                request.currency.clone(),
+                            );
+                            if new_balance >= threshold {
                                 WalletEvents::large_balance_update(
                                     &env,
                                     batch_id,
@@ -199,19 +254,55 @@ impl MultiCurrencyWalletContract {
                                 );
                             }
 
+                            if let Some(factor) = change_factor {
+                                if is_large_relative_change(previous_balance, new_balance, factor) {
+                                    WalletEvents::large_relative_change(
+                                        &env,
+                                        batch_id,
+                                        &request.user,
+                                        &request.currency,
+                                        previous_balance,
+                                        new_balance,
+                                    );
+                                }
+                            }
+
+                            Self::record_balance_history(
+                                &env,
+                                &request.user,
+                                &request.currency,
+                                current_ledger,
+                                &request.operation,
+                                new_balance,
+                            );
+
+                            if receipts_enabled {
+                                let receipt = UpdateReceipt {
+                                    balance: balance.clone(),
+                                    previous_balance,
+                                    operation: request.operation.clone(),
+                                };
+                                env.storage()
+                                    .persistent()
+                                    .set(&DataKey::UpdateReceipt(batch_id, index as u32), &receipt);
+                            }
+
                             results.push_back(BalanceUpdateResult::Success(balance));
                         }
                         Err(error_code) => {
                             // Balance computation failed
                             failed_count += 1;
+                            record_failure(&mut failure_counts, error_code);
 
-                            WalletEvents::balance_update_failed(
-                                &env,
-                                batch_id,
-                                &request.user,
-                                &request.currency,
-                                error_code,
-                            );
+                            if verbose {
+                                WalletEvents::balance_update_failed(
+                                    &env,
+                                    batch_id,
+                                    &request.user,
+                                    &request.currency,
+                                    error_code,
+                                );
+                            }
 
                             results.push_back(BalanceUpdateResult::Failure(
                                 request.user.clone(),
@@ -224,14 +315,17 @@ impl MultiCurrencyWalletContract {
                 Err(error_code) => {
                     // Validation failed
                     failed_count += 1;
-
-                    WalletEvents::balance_update_failed(
-                        &env,
-                        batch_id,
-                        &request.user,
-                        &request.currency,
-                        error_code,
-                    );
+                    record_failure(&mut failure_counts, error_code);
+
+                    if verbose {
+                        WalletEvents::balance_update_failed(
+                            &env,
+                            batch_id,
+                            &request.user,
+                            &request.currency,
+                            error_code,
+                        );
+                    }
 
                     results.push_back(BalanceUpdateResult::Failure(
                         request.user.clone(),
@@ -277,6 +371,9 @@ impl MultiCurrencyWalletContract {
 
         // Emit batch completed event
         WalletEvents::batch_completed(&env, batch_id, successful_count, failed_count);
+        if failed_count > 0 {
+            WalletEvents::batch_failure_summary(&env, batch_id, &failure_counts);
+        }
 
         BatchBalanceResult {
             batch_id,
@@ -305,6 +402,14 @@ impl MultiCurrencyWalletContract {
             .unwrap_or(0)
     }
 
+    /// Returns how many successful updates of `operation` have been processed.
+    pub fn get_operation_count(env: Env, operation: Symbol) -> u64 {
+        env.storage()
+            .instance()
+            .get(&DataKey::OperationCount(operation))
+            .unwrap_or(0)
+    }
+
     /// Retrieves full balance details for a user and currency.
     ///
     /// # Arguments
@@ -324,6 +429,379 @@ impl MultiCurrencyWalletContract {
             .get(&DataKey::Balance(user, currency))
     }
 
+    /// Returns the currencies a user holds a balance record or credit floor in.
+    pub fn get_user_currencies(env: Env, user: Address) -> Vec<Symbol> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::UserCurrencies(user))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Closes a user's account by removing every currency balance and credit
+    /// floor.
+    ///
+    /// Closure is rejected while any balance is nonzero unless `force` is set,
+    /// so funds are not silently discarded.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `admin` - The admin address
+    /// * `user` - The user whose account is closed
+    /// * `force` - Remove balances even if some are nonzero
+    ///
+    /// # Errors
+    /// * `AccountNotEmpty` - If a balance is nonzero and `force` is false
+    pub fn close_account(env: Env, admin: Address, user: Address, force: bool) {
+        admin.require_auth();
+        Self::require_admin(&env, &admin);
+
+        let currencies = Self::get_user_currencies(env.clone(), user.clone());
+
+        if !force {
+            for currency in currencies.iter() {
+                if Self::get_balance(env.clone(), user.clone(), currency) != 0 {
+                    panic_with_error!(&env, WalletError::AccountNotEmpty);
+                }
+            }
+        }
+
+        for currency in currencies.iter() {
+            env.storage()
+                .persistent()
+                .remove(&DataKey::Balance(user.clone(), currency.clone()));
+            env.storage()
+                .persistent()
+                .remove(&DataKey::CreditFloor(user.clone(), currency.clone()));
+            env.storage()
+                .persistent()
+                .remove(&DataKey::BalanceHistory(user.clone(), currency));
+        }
+        env.storage()
+            .persistent()
+            .remove(&DataKey::UserCurrencies(user.clone()));
+
+        WalletEvents::account_closed(&env, &user, currencies.len(), force);
+    }
+
+    /// Projects the outcome of a batch of balance updates without applying it.
+    ///
+    /// Each request runs through the same validation and computation as
+    /// `batch_update_balances`, but is projected against the committed state
+    /// only: earlier requests in the same call do not affect later ones.
+    /// Nothing is written and no events are emitted.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `requests` - Vector of balance update requests, at most `MAX_BATCH_SIZE`
+    ///
+    /// # Returns
+    /// * `Vec<BalanceUpdateResult>` - One projected result per request, in input order
+    pub fn project_balances(
+        env: Env,
+        requests: Vec<BalanceUpdateRequest>,
+    ) -> Vec<BalanceUpdateResult> {
+        if requests.len() > MAX_BATCH_SIZE {
+            panic_with_error!(&env, WalletError::BatchTooLarge);
+        }
+
+        let current_ledger = env.ledger().sequence() as u64;
+        let mut results: Vec<BalanceUpdateResult> = Vec::new(&env);
+
+        for request in requests.iter() {
+            let projected = validate_balance_request(&request).and_then(|()| {
+                validate_and_compute_balance(
+                    &env,
+                    &request.user,
+                    &request.currency,
+                    &request.operation,
+                    request.amount,
+                )
+            });
+
+            match projected {
+                Ok(new_balance) => {
+                    results.push_back(BalanceUpdateResult::Success(CurrencyBalance {
+                        user: request.user.clone(),
+                        currency: request.currency.clone(),
+                        balance: new_balance,
+                        updated_at: current_ledger,
+                    }))
+                }
+                Err(error_code) => results.push_back(BalanceUpdateResult::Failure(
+                    request.user.clone(),
+                    request.currency.clone(),
+                    error_code,
+                )),
+            }
+        }
+
+        results
+    }
+
+    /// Returns a point-in-time snapshot of many balances in one read-only call.
+    ///
+    /// Pairs without a stored balance yield a placeholder with a zero `balance`
+    /// and `updated_at`.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `pairs` - Vector of (user, currency) pairs, at most `MAX_BATCH_SIZE`
+    ///
+    /// # Returns
+    /// * `Vec<CurrencyBalance>` - One entry per requested pair, in input order
+    pub fn snapshot_balances(env: Env, pairs: Vec<(Address, Symbol)>) -> Vec<CurrencyBalance> {
+        if pairs.len() > MAX_BATCH_SIZE {
+            panic_with_error!(&env, WalletError::BatchTooLarge);
+        }
+
+        let mut snapshot: Vec<CurrencyBalance> = Vec::new(&env);
+        for (user, currency) in pairs.iter() {
+            let stored: Option<CurrencyBalance> = env
+                .storage()
+                .persistent()
+                .get(&DataKey::Balance(user.clone(), currency.clone()));
+            snapshot.push_back(stored.unwrap_or(CurrencyBalance {
+                user,
+                currency,
+                balance: 0,
+                updated_at: 0,
+            }));
+        }
+        snapshot
+    }
+
+    /// Sets how far below zero a user's balance in `currency` may go.
+    ///
+    /// A floor of zero (the default) disallows negative balances.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `admin` - The admin address
+    /// * `user` - The user's address
+    /// * `currency` - The currency symbol
+    /// * `floor` - Maximum credit depth (must not be negative)
+    pub fn set_credit_floor(
+        env: Env,
+        admin: Address,
+        user: Address,
+        currency: Symbol,
+        floor: i128,
+    ) {
+        admin.require_auth();
+        Self::require_admin(&env, &admin);
+
+        if floor < 0 {
+            panic_with_error!(&env, WalletError::InvalidCreditFloor);
+        }
+
+        Self::index_user_currency(&env, &user, &currency);
+        env.storage()
+            .persistent()
+            .set(&DataKey::CreditFloor(user, currency), &floor);
+    }
+
+    /// Returns the credit floor for a user and currency (0 if unset).
+    pub fn get_credit_floor(env: Env, user: Address, currency: Symbol) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::CreditFloor(user, currency))
+            .unwrap_or(0)
+    }
+
+    /// Sets the decimal precision clients should use when displaying `currency`.
+    ///
+    /// This is metadata only; stored balances are not rescaled.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `admin` - The admin address
+    /// * `currency` - The currency symbol
+    /// * `decimals` - Number of decimal places
+    pub fn set_currency_decimals(env: Env, admin: Address, currency: Symbol, decimals: u32) {
+        admin.require_auth();
+        Self::require_admin(&env, &admin);
+
+        env.storage()
+            .instance()
+            .set(&DataKey::CurrencyDecimals(currency), &decimals);
+    }
+
+    /// Returns the decimal precision of a currency (7 if unset).
+    pub fn get_currency_decimals(env: Env, currency: Symbol) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::CurrencyDecimals(currency))
+            .unwrap_or(DEFAULT_CURRENCY_DECIMALS)
+    }
+
+    /// Sets the balance at or above which `large_balance_update` is emitted.
+    ///
+    /// With `currency` set the threshold applies to that currency only;
+    /// otherwise it becomes the global fallback for currencies without one.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `admin` - The admin address
+    /// * `currency` - Currency to override, or None for the global threshold
+    /// * `threshold` - Balance threshold (must be positive)
+    pub fn set_large_balance_threshold(
+        env: Env,
+        admin: Address,
+        currency: Option<Symbol>,
+        threshold: i128,
+    ) {
+        admin.require_auth();
+        Self::require_admin(&env, &admin);
+
+        if threshold <= 0 {
+            panic_with_error!(&env, WalletError::InvalidThreshold);
+        }
+
+        let key = match currency {
+            Some(currency) => DataKey::CurrencyLargeBalanceThreshold(currency),
+            None => DataKey::LargeBalanceThreshold,
+        };
+        env.storage().instance().set(&key, &threshold);
+    }
+
+    /// Returns the large balance threshold in effect for `currency`.
+    ///
+    /// Falls back to the global threshold, then to 1,000,000 units.
+    pub fn get_large_balance_threshold(env: Env, currency: Symbol) -> i128 {
+        env.storage()
+            .instance()
+            .get(&DataKey::CurrencyLargeBalanceThreshold(currency))
+            .or_else(|| {
+                env.storage()
+                    .instance()
+                    .get(&DataKey::LargeBalanceThreshold)
+            })
+            .unwrap_or(DEFAULT_LARGE_BALANCE_THRESHOLD)
+    }
+
+    /// Sets the multiplier used to flag large relative balance changes.
+    ///
+    /// An update emits `large_relative_change` when the new balance exceeds
+    /// `factor` times the previous balance, or falls below it by the same factor.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `caller` - The admin address
+    /// * `factor` - Whole-number multiplier (must be greater than 1)
+    pub fn set_large_change_factor(env: Env, caller: Address, factor: u32) {
+        caller.require_auth();
+        Self::require_admin(&env, &caller);
+
+        if factor <= 1 {
+            panic_with_error!(&env, WalletError::InvalidChangeFactor);
+        }
+
+        env.storage()
+            .instance()
+            .set(&DataKey::LargeChangeFactor, &factor);
+    }
+
+    /// Returns the configured large change factor, if any.
+    pub fn get_large_change_factor(env: Env) -> Option<u32> {
+        env.storage().instance().get(&DataKey::LargeChangeFactor)
+    }
+
+    /// Enables or disables storing a receipt for each successful balance update.
+    ///
+    /// Receipts are retrievable with `get_update_receipt`. They are off by
+    /// default to keep storage costs down.
+    pub fn set_receipts_enabled(env: Env, caller: Address, enabled: bool) {
+        caller.require_auth();
+        Self::require_admin(&env, &caller);
+
+        env.storage()
+            .instance()
+            .set(&DataKey::ReceiptsEnabled, &enabled);
+    }
+
+    /// Returns whether update receipts are stored (false by default).
+    pub fn get_receipts_enabled(env: Env) -> bool {
+        env.storage()
+            .instance()
+            .get(&DataKey::ReceiptsEnabled)
+            .unwrap_or(false)
+    }
+
+    /// Returns a user's recent balance changes in `currency`, oldest first.
+    ///
+    /// Each entry is `(ledger, operation, new_balance)`; at most
+    /// `MAX_BALANCE_HISTORY` entries are kept.
+    pub fn get_balance_history(
+        env: Env,
+        user: Address,
+        currency: Symbol,
+    ) -> Vec<(u64, Symbol, i128)> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::BalanceHistory(user, currency))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Returns the receipt stored for a successful update, by batch and position.
+    pub fn get_update_receipt(env: Env, batch_id: u64, index: u32) -> Option<UpdateReceipt> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::UpdateReceipt(batch_id, index))
+    }
+
+    /// Sets or clears the spending-limits contract consulted on subtracts.
+    ///
+    /// When set, every `subtract` update calls the contract's
+    /// `check_spend_allowed` and fails with `LIMIT_EXCEEDED` if disallowed.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `caller` - The admin address
+    /// * `limits_contract` - The limits contract, or `None` to disable the check
+    pub fn set_limits_contract(env: Env, caller: Address, limits_contract: Option<Address>) {
+        caller.require_auth();
+        Self::require_admin(&env, &caller);
+
+        match limits_contract {
+            Some(limits_contract) => env
+                .storage()
+                .instance()
+                .set(&DataKey::LimitsContract, &limits_contract),
+            None => env.storage().instance().remove(&DataKey::LimitsContract),
+        }
+    }
+
+    /// Returns the configured spending-limits contract, if any.
+    pub fn get_limits_contract(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::LimitsContract)
+    }
+
+    /// Enables or disables per-item balance events in batch updates.
+    ///
+    /// When disabled, `balance_updated` and `balance_update_failed` are not
+    /// emitted; batch-level events and alerts still fire.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `caller` - The admin address
+    /// * `enabled` - Whether per-item events are emitted
+    pub fn set_verbose_events(env: Env, caller: Address, enabled: bool) {
+        caller.require_auth();
+        Self::require_admin(&env, &caller);
+
+        env.storage()
+            .instance()
+            .set(&DataKey::VerboseEvents, &enabled);
+    }
+
+    /// Returns whether per-item balance events are emitted (true by default).
+    pub fn get_verbose_events(env: Env) -> bool {
+        env.storage()
+            .instance()
+            .get(&DataKey::VerboseEvents)
+            .unwrap_or(true)
+    }
+
     /// Returns the admin address.
     pub fn get_admin(env: Env) -> Address {
         env.storage()
@@ -364,6 +842,30 @@ impl MultiCurrencyWalletContract {
             .unwrap_or(0)
     }
 
+    // Internal helper appending to a user's bounded balance history, evicting the oldest
+    fn record_balance_history(
+        env: &Env,
+        user: &Address,
+        currency: &Symbol,
+        ledger: u64,
+        operation: &Symbol,
+        new_balance: i128,
+    ) {
+        let key = DataKey::BalanceHistory(user.clone(), currency.clone());
+        let mut history: Vec<(u64, Symbol, i128)> = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or(Vec::new(env));
+
+        history.push_back((ledger, operation.clone(), new_balance));
+        while history.len() > MAX_BALANCE_HISTORY {
+            history.pop_front();
+        }
+
+        env.storage().persistent().set(&key, &history);
+    }
+
     // Internal helper to verify admin
     fn require_admin(env: &Env, caller: &Address) {
         let admin: Address = env
@@ -376,6 +878,43 @@ impl MultiCurrencyWalletContract {
             panic_with_error!(env, WalletError::Unauthorized);
         }
     }
+
+    // Internal helper recording that a user has state in `currency`
+    fn index_user_currency(env: &Env, user: &Address, currency: &Symbol) {
+        let key = DataKey::UserCurrencies(user.clone());
+        let mut user_currencies: Vec<Symbol> = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or(Vec::new(env));
+        if !contains_symbol(&user_currencies, currency) {
+            user_currencies.push_back(currency.clone());
+            env.storage().persistent().set(&key, &user_currencies);
+        }
+    }
+}
+
+// Returns true when `new` differs from a non-zero `previous` by more than `factor` times
+fn is_large_relative_change(previous: i128, new: i128, factor: u32) -> bool {
+    if previous <= 0 {
+        return false;
+    }
+    let factor = factor as i128;
+    let grew = match previous.checked_mul(factor) {
+        Some(limit) => new > limit,
+        None => false,
+    };
+    let shrank = match new.checked_mul(factor) {
+        Some(scaled) => scaled < previous,
+        None => false,
+    };
+    grew || shrank
+}
+
+/// Helper function to count a failure under its error code.
+fn record_failure(failure_counts: &mut Map<u32, u32>, error_code: u32) {
+    let count = failure_counts.get(error_code).unwrap_or(0);
+    failure_counts.set(error_code, count + 1);
 }
 
 // Helper functions for tracking unique items
@@ -3,9 +3,14 @@
 #![cfg(test)]
 
 use crate::{MultiCurrencyWalletContract, MultiCurrencyWalletContractClient};
-use soroban_sdk::{symbol_short, testutils::Address as _, Address, Env, Symbol, Vec};
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, Events, Ledger},
+    Address, Env, Map, Symbol, TryIntoVal, Vec,
+};
 
-use crate::types::{BalanceUpdateRequest, BalanceUpdateResult, ErrorCode};
+use crate::types::{BalanceUpdateRequest, BalanceUpdateResult, ErrorCode, MAX_BALANCE_HISTORY};
+use spending_limits::{SpendingLimitRequest, SpendingLimitsContract, SpendingLimitsContractClient};
 
 /// Helper function to create a test environment with initialized contract.
 fn setup_test_contract() -> (Env, Address, MultiCurrencyWalletContractClient<'static>) {
@@ -23,7 +28,7 @@ fn setup_test_contract() -> (Env, Address, MultiCurrencyWalletContractClient<'st
 
 /// Helper function to create a valid balance update request.
 fn create_valid_request(
-    env: &Env,
+    _env: &Env,
     user: &Address,
     currency: Symbol,
     amount: i128,
@@ -37,6 +42,24 @@ fn create_valid_request(
     }
 }
 
+/// Registers the spending-limits contract with a `limit` for `user`.
+fn setup_limits_contract(env: &Env, admin: &Address, user: &Address, limit: i128) -> Address {
+    let limits_id = env.register(SpendingLimitsContract, ());
+    let limits_client = SpendingLimitsContractClient::new(env, &limits_id);
+    limits_client.initialize(admin);
+
+    let mut requests: Vec<SpendingLimitRequest> = Vec::new(env);
+    requests.push_back(SpendingLimitRequest {
+        user: user.clone(),
+        monthly_limit: limit,
+        category: None,
+        window_ledgers: 0,
+    });
+    limits_client.batch_update_spending_limits(admin, &requests);
+
+    limits_id
+}
+
 #[test]
 fn test_initialize() {
     let (_, admin, client) = setup_test_contract();
@@ -65,7 +88,7 @@ fn test_batch_update_balances_single_user_single_currency() {
         &env,
         &user,
         symbol_short!("USDC"),
-        1000_000_000, // 1000 USDC
+        1_000_000_000, // 1000 USDC
         symbol_short!("set"),
     ));
 
@@ -78,7 +101,7 @@ fn test_batch_update_balances_single_user_single_currency() {
 
     // Verify balance was set
     let balance = client.get_balance(&user, &symbol_short!("USDC"));
-    assert_eq!(balance, 1000_000_000);
+    assert_eq!(balance, 1_000_000_000);
 
     // Verify storage updates
     assert_eq!(client.get_last_batch_id(), 1);
@@ -158,7 +181,7 @@ fn test_balance_add_operation() {
         &env,
         &user,
         symbol_short!("USDC"),
-        1000_000_000,
+        1_000_000_000,
         symbol_short!("set"),
     ));
     client.batch_update_balances(&admin, &requests1);
@@ -175,7 +198,10 @@ fn test_balance_add_operation() {
     let result = client.batch_update_balances(&admin, &requests2);
 
     assert_eq!(result.successful, 1);
-    assert_eq!(client.get_balance(&user, &symbol_short!("USDC")), 1500_000_000);
+    assert_eq!(
+        client.get_balance(&user, &symbol_short!("USDC")),
+        1_500_000_000
+    );
 }
 
 #[test]
@@ -189,7 +215,7 @@ fn test_balance_subtract_operation() {
         &env,
         &user,
         symbol_short!("USDC"),
-        1000_000_000,
+        1_000_000_000,
         symbol_short!("set"),
     ));
     client.batch_update_balances(&admin, &requests1);
@@ -231,7 +257,7 @@ fn test_balance_subtract_insufficient_fails() {
         &env,
         &user,
         symbol_short!("USDC"),
-        1000_000_000,
+        1_000_000_000,
         symbol_short!("subtract"),
     ));
     let result = client.batch_update_balances(&admin, &requests2);
@@ -264,7 +290,7 @@ fn test_batch_update_with_invalid_requests() {
         &env,
         &user1,
         symbol_short!("USDC"),
-        1000_000_000,
+        1_000_000_000,
         symbol_short!("set"),
     ));
 
@@ -358,13 +384,14 @@ fn test_batch_update_batch_too_large() {
 fn test_get_balance_details() {
     let (env, admin, client) = setup_test_contract();
     let user = Address::generate(&env);
+    env.ledger().with_mut(|li| li.sequence_number = 100);
 
     let mut requests: Vec<BalanceUpdateRequest> = Vec::new(&env);
     requests.push_back(create_valid_request(
         &env,
         &user,
         symbol_short!("USDC"),
-        1000_000_000,
+        1_000_000_000,
         symbol_short!("set"),
     ));
 
@@ -377,7 +404,7 @@ fn test_get_balance_details() {
 
     assert_eq!(details.user, user);
     assert_eq!(details.currency, symbol_short!("USDC"));
-    assert_eq!(details.balance, 1000_000_000);
+    assert_eq!(details.balance, 1_000_000_000);
     assert!(details.updated_at > 0);
 }
 
@@ -393,14 +420,14 @@ fn test_batch_metrics() {
         &env,
         &user1,
         symbol_short!("USDC"),
-        1000_000_000,
+        1_000_000_000,
         symbol_short!("set"),
     ));
     requests.push_back(create_valid_request(
         &env,
         &user1,
         symbol_short!("XLM"),
-        5000_000_000,
+        5_000_000_000,
         symbol_short!("set"),
     ));
     requests.push_back(create_valid_request(
@@ -431,7 +458,7 @@ fn test_multiple_batches() {
         &env,
         &user1,
         symbol_short!("USDC"),
-        1000_000_000,
+        1_000_000_000,
         symbol_short!("set"),
     ));
     let result1 = client.batch_update_balances(&admin, &requests1);
@@ -444,7 +471,7 @@ fn test_multiple_batches() {
         &env,
         &user2,
         symbol_short!("XLM"),
-        5000_000_000,
+        5_000_000_000,
         symbol_short!("set"),
     ));
     let result2 = client.batch_update_balances(&admin, &requests2);
@@ -476,6 +503,64 @@ fn test_large_balance_event() {
     // Large balance event should be emitted (verified in event logs)
 }
 
+/// Helper running a single-request batch and returning the number of events it emitted.
+fn run_single_update(
+    env: &Env,
+    admin: &Address,
+    client: &MultiCurrencyWalletContractClient,
+    user: &Address,
+    amount: i128,
+) -> u32 {
+    let mut requests: Vec<BalanceUpdateRequest> = Vec::new(env);
+    requests.push_back(create_valid_request(
+        env,
+        user,
+        symbol_short!("USDC"),
+        amount,
+        symbol_short!("set"),
+    ));
+    client.batch_update_balances(admin, &requests);
+    env.events().all().len()
+}
+
+#[test]
+fn test_large_relative_change_event() {
+    let (env, admin, client) = setup_test_contract();
+    let user = Address::generate(&env);
+
+    client.set_large_change_factor(&admin, &10);
+    assert_eq!(client.get_large_change_factor(), Some(10));
+
+    run_single_update(&env, &admin, &client, &user, 1_000);
+
+    // A 5x move stays below the factor: started, updated, completed
+    let modest_events = run_single_update(&env, &admin, &client, &user, 5_000);
+    assert_eq!(modest_events, 3);
+
+    // A 20x jump emits the relative change event as well
+    let jump_events = run_single_update(&env, &admin, &client, &user, 100_000);
+    assert_eq!(jump_events, 4);
+}
+
+#[test]
+fn test_large_relative_change_disabled_without_factor() {
+    let (env, admin, client) = setup_test_contract();
+    let user = Address::generate(&env);
+
+    run_single_update(&env, &admin, &client, &user, 1_000);
+    let jump_events = run_single_update(&env, &admin, &client, &user, 100_000);
+
+    assert_eq!(jump_events, 3);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #6)")]
+fn test_set_large_change_factor_rejects_one() {
+    let (_env, admin, client) = setup_test_contract();
+
+    client.set_large_change_factor(&admin, &1);
+}
+
 #[test]
 fn test_set_admin() {
     let (env, admin, client) = setup_test_contract();
@@ -498,7 +583,7 @@ fn test_mixed_operations_same_user() {
         &env,
         &user,
         symbol_short!("USDC"),
-        1000_000_000,
+        1_000_000_000,
         symbol_short!("set"),
     ));
 
@@ -507,7 +592,7 @@ fn test_mixed_operations_same_user() {
         &env,
         &user,
         symbol_short!("XLM"),
-        5000_000_000,
+        5_000_000_000,
         symbol_short!("set"),
     ));
 
@@ -527,8 +612,8 @@ fn test_mixed_operations_same_user() {
     assert_eq!(result.failed, 0);
 
     // Verify all balances for the same user
-    assert_eq!(client.get_balance(&user, &symbol_short!("USDC")), 1000_000_000);
-    assert_eq!(client.get_balance(&user, &symbol_short!("XLM")), 5000_000_000);
+    assert_eq!(client.get_balance(&user, &symbol_short!("USDC")), 1_000_000_000);
+    assert_eq!(client.get_balance(&user, &symbol_short!("XLM")), 5_000_000_000);
     assert_eq!(client.get_balance(&user, &symbol_short!("EURC")), 750_000_000);
 
     // Metrics should show 1 unique user, 3 unique currencies
@@ -560,7 +645,7 @@ fn test_mixed_valid_and_invalid_requests() {
         &env,
         &user1,
         symbol_short!("USDC"),
-        1000_000_000,
+        1_000_000_000,
         symbol_short!("set"),
     ));
 
@@ -612,3 +697,832 @@ fn test_minimum_valid_balance() {
     assert_eq!(result.failed, 0);
     assert_eq!(client.get_balance(&user, &symbol_short!("USDC")), 1);
 }
+
+#[test]
+fn test_snapshot_balances_with_placeholders() {
+    let (env, admin, client) = setup_test_contract();
+    let user1 = Address::generate(&env);
+    let user2 = Address::generate(&env);
+
+    let mut requests: Vec<BalanceUpdateRequest> = Vec::new(&env);
+    requests.push_back(create_valid_request(
+        &env,
+        &user1,
+        symbol_short!("USDC"),
+        5_000,
+        symbol_short!("set"),
+    ));
+    client.batch_update_balances(&admin, &requests);
+
+    let mut pairs: Vec<(Address, Symbol)> = Vec::new(&env);
+    pairs.push_back((user1.clone(), symbol_short!("USDC")));
+    pairs.push_back((user1.clone(), symbol_short!("EURC")));
+    pairs.push_back((user2.clone(), symbol_short!("USDC")));
+
+    let snapshot = client.snapshot_balances(&pairs);
+    // Read-only: no events are emitted
+    assert_eq!(env.events().all().len(), 0);
+
+    assert_eq!(snapshot.len(), 3);
+    let existing = snapshot.get(0).unwrap();
+    assert_eq!(existing.balance, 5_000);
+    assert_eq!(existing.user, user1);
+
+    let missing_currency = snapshot.get(1).unwrap();
+    assert_eq!(missing_currency.user, user1);
+    assert_eq!(missing_currency.currency, symbol_short!("EURC"));
+    assert_eq!(missing_currency.balance, 0);
+    assert_eq!(missing_currency.updated_at, 0);
+
+    let missing_user = snapshot.get(2).unwrap();
+    assert_eq!(missing_user.user, user2);
+    assert_eq!(missing_user.balance, 0);
+}
+
+#[test]
+fn test_credit_floor_allows_negative_balance_within_floor() {
+    let (env, admin, client) = setup_test_contract();
+    let user = Address::generate(&env);
+    client.set_credit_floor(&admin, &user, &symbol_short!("USDC"), &500_000_000);
+    assert_eq!(
+        client.get_credit_floor(&user, &symbol_short!("USDC")),
+        500_000_000
+    );
+
+    let mut requests: Vec<BalanceUpdateRequest> = Vec::new(&env);
+    requests.push_back(create_valid_request(
+        &env,
+        &user,
+        symbol_short!("USDC"),
+        100_000_000,
+        symbol_short!("set"),
+    ));
+    requests.push_back(create_valid_request(
+        &env,
+        &user,
+        symbol_short!("USDC"),
+        400_000_000,
+        symbol_short!("subtract"),
+    ));
+    let result = client.batch_update_balances(&admin, &requests);
+
+    assert_eq!(result.successful, 2);
+    assert_eq!(
+        client.get_balance(&user, &symbol_short!("USDC")),
+        -300_000_000
+    );
+
+    // Going past the floor is rejected
+    let mut beyond: Vec<BalanceUpdateRequest> = Vec::new(&env);
+    beyond.push_back(create_valid_request(
+        &env,
+        &user,
+        symbol_short!("USDC"),
+        300_000_000,
+        symbol_short!("subtract"),
+    ));
+    let result = client.batch_update_balances(&admin, &beyond);
+
+    assert_eq!(result.failed, 1);
+    match &result.results.get(0).unwrap() {
+        BalanceUpdateResult::Failure(_, _, error_code) => {
+            assert_eq!(*error_code, ErrorCode::INSUFFICIENT_BALANCE);
+        }
+        BalanceUpdateResult::Success(_) => panic!("Expected failure"),
+    }
+    assert_eq!(
+        client.get_balance(&user, &symbol_short!("USDC")),
+        -300_000_000
+    );
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #7)")]
+fn test_set_credit_floor_rejects_negative() {
+    let (env, admin, client) = setup_test_contract();
+
+    client.set_credit_floor(
+        &admin,
+        &Address::generate(&env),
+        &symbol_short!("USDC"),
+        &-1,
+    );
+}
+
+/// Helper counting `balance_zeroed` events emitted by the last call.
+fn count_zeroed_events(env: &Env) -> u32 {
+    let mut count = 0;
+    for (_contract, topics, _data) in env.events().all().iter() {
+        let kind: Symbol = topics.get(1).unwrap().try_into_val(env).unwrap();
+        if kind == symbol_short!("zeroed") {
+            count += 1;
+        }
+    }
+    count
+}
+
+#[test]
+fn test_subtract_to_zero_emits_balance_zeroed() {
+    let (env, admin, client) = setup_test_contract();
+    let user = Address::generate(&env);
+
+    let mut requests1: Vec<BalanceUpdateRequest> = Vec::new(&env);
+    requests1.push_back(create_valid_request(
+        &env,
+        &user,
+        symbol_short!("USDC"),
+        500_000_000,
+        symbol_short!("set"),
+    ));
+    client.batch_update_balances(&admin, &requests1);
+    assert_eq!(count_zeroed_events(&env), 0);
+
+    let mut requests2: Vec<BalanceUpdateRequest> = Vec::new(&env);
+    requests2.push_back(create_valid_request(
+        &env,
+        &user,
+        symbol_short!("USDC"),
+        500_000_000,
+        symbol_short!("subtract"),
+    ));
+    client.batch_update_balances(&admin, &requests2);
+
+    // Events only cover the latest invocation, so count before reading state
+    assert_eq!(count_zeroed_events(&env), 1);
+    assert_eq!(client.get_balance(&user, &symbol_short!("USDC")), 0);
+}
+
+#[test]
+fn test_currency_decimals_configured_and_default() {
+    let (_env, admin, client) = setup_test_contract();
+
+    client.set_currency_decimals(&admin, &symbol_short!("USDC"), &6);
+    client.set_currency_decimals(&admin, &symbol_short!("EURC"), &2);
+
+    assert_eq!(client.get_currency_decimals(&symbol_short!("USDC")), 6);
+    assert_eq!(client.get_currency_decimals(&symbol_short!("EURC")), 2);
+    // Unset currencies fall back to XLM stroop precision
+    assert_eq!(client.get_currency_decimals(&symbol_short!("XLM")), 7);
+
+    // Metadata does not touch balances
+    assert_eq!(client.get_total_balances_updated(), 0);
+}
+
+#[test]
+fn test_batch_failure_summary_counts_error_codes() {
+    let (env, admin, client) = setup_test_contract();
+    let user = Address::generate(&env);
+
+    let mut requests: Vec<BalanceUpdateRequest> = Vec::new(&env);
+    requests.push_back(create_valid_request(
+        &env,
+        &user,
+        symbol_short!("USDC"),
+        1_000,
+        symbol_short!("set"),
+    ));
+    // Two invalid amounts
+    requests.push_back(create_valid_request(
+        &env,
+        &user,
+        symbol_short!("XLM"),
+        -1,
+        symbol_short!("set"),
+    ));
+    requests.push_back(create_valid_request(
+        &env,
+        &user,
+        symbol_short!("EURC"),
+        0,
+        symbol_short!("add"),
+    ));
+    // Unknown operation
+    requests.push_back(create_valid_request(
+        &env,
+        &user,
+        symbol_short!("USDC"),
+        100,
+        symbol_short!("burn"),
+    ));
+    // Subtract beyond an empty balance
+    requests.push_back(create_valid_request(
+        &env,
+        &user,
+        symbol_short!("XLM"),
+        100,
+        symbol_short!("subtract"),
+    ));
+
+    let result = client.batch_update_balances(&admin, &requests);
+    assert_eq!(result.failed, 4);
+
+    let mut summary: Option<Map<u32, u32>> = None;
+    for (_contract, topics, data) in env.events().all().iter() {
+        let kind: Symbol = topics.get(1).unwrap().try_into_val(&env).unwrap();
+        if kind == symbol_short!("fail_sum") {
+            summary = Some(data.try_into_val(&env).unwrap());
+        }
+    }
+    let summary = summary.expect("Expected a batch failure summary event");
+
+    assert_eq!(summary.len(), 3);
+    assert_eq!(summary.get(ErrorCode::INVALID_AMOUNT), Some(2));
+    assert_eq!(summary.get(ErrorCode::INVALID_OPERATION), Some(1));
+    assert_eq!(summary.get(ErrorCode::INSUFFICIENT_BALANCE), Some(1));
+}
+
+fn count_large_balance_events(env: &Env) -> u32 {
+    let mut count = 0;
+    for (_contract, topics, _data) in env.events().all().iter() {
+        let kind: Symbol = topics.get(1).unwrap().try_into_val(env).unwrap();
+        if kind == symbol_short!("large") {
+            count += 1;
+        }
+    }
+    count
+}
+
+#[test]
+fn test_large_balance_threshold_per_currency_and_global() {
+    let (env, admin, client) = setup_test_contract();
+    let user = Address::generate(&env);
+
+    client.set_large_balance_threshold(&admin, &None, &5_000);
+    client.set_large_balance_threshold(&admin, &Some(symbol_short!("USDC")), &100);
+    assert_eq!(
+        client.get_large_balance_threshold(&symbol_short!("USDC")),
+        100
+    );
+    assert_eq!(
+        client.get_large_balance_threshold(&symbol_short!("XLM")),
+        5_000
+    );
+
+    // Below the constant default but above the USDC override
+    let mut requests: Vec<BalanceUpdateRequest> = Vec::new(&env);
+    requests.push_back(create_valid_request(
+        &env,
+        &user,
+        symbol_short!("USDC"),
+        500,
+        symbol_short!("set"),
+    ));
+    client.batch_update_balances(&admin, &requests);
+    assert_eq!(count_large_balance_events(&env), 1);
+
+    // XLM uses the global fallback
+    let mut requests: Vec<BalanceUpdateRequest> = Vec::new(&env);
+    requests.push_back(create_valid_request(
+        &env,
+        &user,
+        symbol_short!("XLM"),
+        4_999,
+        symbol_short!("set"),
+    ));
+    client.batch_update_balances(&admin, &requests);
+    assert_eq!(count_large_balance_events(&env), 0);
+}
+
+#[test]
+fn test_large_balance_threshold_defaults_to_constant() {
+    let (env, admin, client) = setup_test_contract();
+    let user = Address::generate(&env);
+
+    assert_eq!(
+        client.get_large_balance_threshold(&symbol_short!("EURC")),
+        1_000_000
+    );
+    assert_eq!(run_single_update(&env, &admin, &client, &user, 999_999), 3);
+    assert_eq!(count_large_balance_events(&env), 0);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #8)")]
+fn test_set_large_balance_threshold_rejects_zero() {
+    let (_env, admin, client) = setup_test_contract();
+
+    client.set_large_balance_threshold(&admin, &None, &0);
+}
+
+#[test]
+fn test_close_account_with_zeroed_balances() {
+    let (env, admin, client) = setup_test_contract();
+    let user = Address::generate(&env);
+
+    let mut requests: Vec<BalanceUpdateRequest> = Vec::new(&env);
+    for currency in [symbol_short!("USDC"), symbol_short!("XLM")] {
+        requests.push_back(create_valid_request(
+            &env,
+            &user,
+            currency.clone(),
+            1_000,
+            symbol_short!("set"),
+        ));
+        requests.push_back(create_valid_request(
+            &env,
+            &user,
+            currency,
+            1_000,
+            symbol_short!("subtract"),
+        ));
+    }
+    client.batch_update_balances(&admin, &requests);
+    assert_eq!(client.get_user_currencies(&user).len(), 2);
+
+    client.close_account(&admin, &user, &false);
+
+    assert_eq!(client.get_user_currencies(&user).len(), 0);
+    assert!(client
+        .get_balance_details(&user, &symbol_short!("USDC"))
+        .is_none());
+    assert!(client
+        .get_balance_details(&user, &symbol_short!("XLM"))
+        .is_none());
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #9)")]
+fn test_close_account_rejects_nonzero_balance() {
+    let (env, admin, client) = setup_test_contract();
+    let user = Address::generate(&env);
+
+    let mut requests: Vec<BalanceUpdateRequest> = Vec::new(&env);
+    requests.push_back(create_valid_request(
+        &env,
+        &user,
+        symbol_short!("USDC"),
+        1_000,
+        symbol_short!("set"),
+    ));
+    client.batch_update_balances(&admin, &requests);
+
+    client.close_account(&admin, &user, &false);
+}
+
+#[test]
+fn test_force_close_account_removes_nonzero_balance() {
+    let (env, admin, client) = setup_test_contract();
+    let user = Address::generate(&env);
+
+    let mut requests: Vec<BalanceUpdateRequest> = Vec::new(&env);
+    requests.push_back(create_valid_request(
+        &env,
+        &user,
+        symbol_short!("USDC"),
+        1_000,
+        symbol_short!("set"),
+    ));
+    client.batch_update_balances(&admin, &requests);
+
+    client.close_account(&admin, &user, &true);
+
+    assert_eq!(client.get_balance(&user, &symbol_short!("USDC")), 0);
+    assert_eq!(client.get_user_currencies(&user).len(), 0);
+}
+
+#[test]
+fn test_close_account_removes_credit_floors() {
+    let (env, admin, client) = setup_test_contract();
+    let user = Address::generate(&env);
+
+    // A floor alone, with no balance record, still belongs to the account
+    client.set_credit_floor(&admin, &user, &symbol_short!("USDC"), &500);
+    assert_eq!(client.get_user_currencies(&user).len(), 1);
+
+    client.close_account(&admin, &user, &false);
+
+    assert_eq!(client.get_credit_floor(&user, &symbol_short!("USDC")), 0);
+    assert_eq!(client.get_user_currencies(&user).len(), 0);
+}
+
+#[test]
+fn test_operation_counts_only_successful_updates() {
+    let (env, admin, client) = setup_test_contract();
+    let user = Address::generate(&env);
+
+    let mut requests: Vec<BalanceUpdateRequest> = Vec::new(&env);
+    requests.push_back(create_valid_request(
+        &env,
+        &user,
+        symbol_short!("USDC"),
+        1_000,
+        symbol_short!("set"),
+    ));
+    requests.push_back(create_valid_request(
+        &env,
+        &user,
+        symbol_short!("XLM"),
+        2_000,
+        symbol_short!("set"),
+    ));
+    requests.push_back(create_valid_request(
+        &env,
+        &user,
+        symbol_short!("USDC"),
+        500,
+        symbol_short!("add"),
+    ));
+    requests.push_back(create_valid_request(
+        &env,
+        &user,
+        symbol_short!("USDC"),
+        200,
+        symbol_short!("subtract"),
+    ));
+    // Fails: subtract beyond the balance
+    requests.push_back(create_valid_request(
+        &env,
+        &user,
+        symbol_short!("EURC"),
+        100,
+        symbol_short!("subtract"),
+    ));
+    // Fails: invalid amount
+    requests.push_back(create_valid_request(
+        &env,
+        &user,
+        symbol_short!("USDC"),
+        -1,
+        symbol_short!("add"),
+    ));
+
+    let result = client.batch_update_balances(&admin, &requests);
+    assert_eq!(result.successful, 4);
+
+    assert_eq!(client.get_operation_count(&symbol_short!("set")), 2);
+    assert_eq!(client.get_operation_count(&symbol_short!("add")), 1);
+    assert_eq!(client.get_operation_count(&symbol_short!("subtract")), 1);
+}
+
+#[test]
+fn test_disabling_verbose_events_suppresses_per_item_events() {
+    let (env, admin, client) = setup_test_contract();
+    let user1 = Address::generate(&env);
+    let user2 = Address::generate(&env);
+
+    assert!(client.get_verbose_events());
+
+    let mut requests: Vec<BalanceUpdateRequest> = Vec::new(&env);
+    requests.push_back(create_valid_request(
+        &env,
+        &user1,
+        symbol_short!("USDC"),
+        1_000,
+        symbol_short!("set"),
+    ));
+    requests.push_back(create_valid_request(
+        &env,
+        &user2,
+        symbol_short!("XLM"),
+        2_000,
+        symbol_short!("set"),
+    ));
+    requests.push_back(create_valid_request(
+        &env,
+        &user2,
+        symbol_short!("USDC"),
+        -1,
+        symbol_short!("set"),
+    ));
+
+    let verbose_result = client.batch_update_balances(&admin, &requests);
+    // started, 2 updated, 1 failed, completed, failure summary
+    assert_eq!(env.events().all().len(), 6);
+
+    client.set_verbose_events(&admin, &false);
+    assert!(!client.get_verbose_events());
+
+    let quiet_result = client.batch_update_balances(&admin, &requests);
+    // started, completed, failure summary
+    assert_eq!(env.events().all().len(), 3);
+
+    assert_eq!(quiet_result.successful, verbose_result.successful);
+    assert_eq!(quiet_result.failed, verbose_result.failed);
+    assert_eq!(quiet_result.results.len(), verbose_result.results.len());
+    assert_eq!(client.get_balance(&user1, &symbol_short!("USDC")), 1_000);
+    assert_eq!(client.get_balance(&user2, &symbol_short!("XLM")), 2_000);
+}
+
+#[test]
+fn test_update_receipts_record_before_and_after() {
+    let (env, admin, client) = setup_test_contract();
+    let user = Address::generate(&env);
+
+    // Receipts are off by default
+    let mut requests: Vec<BalanceUpdateRequest> = Vec::new(&env);
+    requests.push_back(create_valid_request(
+        &env,
+        &user,
+        symbol_short!("USDC"),
+        1_000,
+        symbol_short!("set"),
+    ));
+    client.batch_update_balances(&admin, &requests);
+    assert!(!client.get_receipts_enabled());
+    assert!(client.get_update_receipt(&1, &0).is_none());
+
+    client.set_receipts_enabled(&admin, &true);
+
+    let mut requests: Vec<BalanceUpdateRequest> = Vec::new(&env);
+    requests.push_back(create_valid_request(
+        &env,
+        &user,
+        symbol_short!("USDC"),
+        5_000,
+        symbol_short!("subtract"),
+    ));
+    requests.push_back(create_valid_request(
+        &env,
+        &user,
+        symbol_short!("USDC"),
+        250,
+        symbol_short!("add"),
+    ));
+    let result = client.batch_update_balances(&admin, &requests);
+    assert_eq!(result.successful, 1);
+    assert_eq!(result.failed, 1);
+
+    let batch_id = client.get_last_batch_id();
+    // The failed subtract leaves no receipt
+    assert!(client.get_update_receipt(&batch_id, &0).is_none());
+
+    let receipt = client.get_update_receipt(&batch_id, &1).unwrap();
+    assert_eq!(receipt.previous_balance, 1_000);
+    assert_eq!(receipt.balance.balance, 1_250);
+    assert_eq!(receipt.balance.user, user);
+    assert_eq!(receipt.operation, symbol_short!("add"));
+}
+
+#[test]
+fn test_project_balances_matches_committed_results() {
+    let (env, admin, client) = setup_test_contract();
+    let user1 = Address::generate(&env);
+    let user2 = Address::generate(&env);
+    let user3 = Address::generate(&env);
+
+    let mut setup: Vec<BalanceUpdateRequest> = Vec::new(&env);
+    setup.push_back(create_valid_request(
+        &env,
+        &user1,
+        symbol_short!("USDC"),
+        1_000,
+        symbol_short!("set"),
+    ));
+    setup.push_back(create_valid_request(
+        &env,
+        &user2,
+        symbol_short!("XLM"),
+        500,
+        symbol_short!("set"),
+    ));
+    client.batch_update_balances(&admin, &setup);
+
+    let mut requests: Vec<BalanceUpdateRequest> = Vec::new(&env);
+    requests.push_back(create_valid_request(
+        &env,
+        &user1,
+        symbol_short!("USDC"),
+        250,
+        symbol_short!("add"),
+    ));
+    requests.push_back(create_valid_request(
+        &env,
+        &user2,
+        symbol_short!("XLM"),
+        600,
+        symbol_short!("subtract"),
+    ));
+    requests.push_back(create_valid_request(
+        &env,
+        &user3,
+        symbol_short!("EUR"),
+        700,
+        symbol_short!("set"),
+    ));
+
+    let last_batch_id = client.get_last_batch_id();
+    let projected = client.project_balances(&requests);
+
+    // Projection writes nothing
+    assert_eq!(client.get_balance(&user1, &symbol_short!("USDC")), 1_000);
+    assert_eq!(client.get_balance(&user3, &symbol_short!("EUR")), 0);
+    assert_eq!(client.get_last_batch_id(), last_batch_id);
+
+    let committed = client.batch_update_balances(&admin, &requests);
+
+    assert_eq!(projected.len(), committed.results.len());
+    for (projection, actual) in projected.iter().zip(committed.results.iter()) {
+        match (projection, actual) {
+            (BalanceUpdateResult::Success(p), BalanceUpdateResult::Success(a)) => {
+                assert_eq!(p.user, a.user);
+                assert_eq!(p.currency, a.currency);
+                assert_eq!(p.balance, a.balance);
+            }
+            (BalanceUpdateResult::Failure(_, _, p), BalanceUpdateResult::Failure(_, _, a)) => {
+                assert_eq!(p, a);
+            }
+            _ => panic!("Projection disagrees with committed result"),
+        }
+    }
+    assert_eq!(client.get_balance(&user1, &symbol_short!("USDC")), 1_250);
+}
+
+#[test]
+fn test_subtract_checked_against_limits_contract() {
+    let (env, admin, client) = setup_test_contract();
+    let user = Address::generate(&env);
+
+    let limits_id = setup_limits_contract(&env, &admin, &user, 150_000_000);
+    client.set_limits_contract(&admin, &Some(limits_id.clone()));
+    assert_eq!(client.get_limits_contract(), Some(limits_id));
+
+    let mut requests: Vec<BalanceUpdateRequest> = Vec::new(&env);
+    requests.push_back(create_valid_request(
+        &env,
+        &user,
+        symbol_short!("USDC"),
+        1_000_000_000,
+        symbol_short!("set"),
+    ));
+    // Within the limit
+    requests.push_back(create_valid_request(
+        &env,
+        &user,
+        symbol_short!("USDC"),
+        100_000_000,
+        symbol_short!("subtract"),
+    ));
+    // Over the limit
+    requests.push_back(create_valid_request(
+        &env,
+        &user,
+        symbol_short!("USDC"),
+        200_000_000,
+        symbol_short!("subtract"),
+    ));
+    let result = client.batch_update_balances(&admin, &requests);
+
+    assert_eq!(result.successful, 2);
+    assert_eq!(result.failed, 1);
+    match &result.results.get(2).unwrap() {
+        BalanceUpdateResult::Failure(_, _, error_code) => {
+            assert_eq!(*error_code, ErrorCode::LIMIT_EXCEEDED);
+        }
+        BalanceUpdateResult::Success(_) => panic!("Expected failure"),
+    }
+    assert_eq!(
+        client.get_balance(&user, &symbol_short!("USDC")),
+        900_000_000
+    );
+}
+
+#[test]
+fn test_limits_contract_unset_leaves_subtract_unchanged() {
+    let (env, admin, client) = setup_test_contract();
+    let user = Address::generate(&env);
+
+    let limits_id = setup_limits_contract(&env, &admin, &user, 150_000_000);
+    client.set_limits_contract(&admin, &Some(limits_id));
+    client.set_limits_contract(&admin, &None);
+    assert_eq!(client.get_limits_contract(), None);
+
+    let mut requests: Vec<BalanceUpdateRequest> = Vec::new(&env);
+    requests.push_back(create_valid_request(
+        &env,
+        &user,
+        symbol_short!("USDC"),
+        1_000_000_000,
+        symbol_short!("set"),
+    ));
+    requests.push_back(create_valid_request(
+        &env,
+        &user,
+        symbol_short!("USDC"),
+        300_000_000,
+        symbol_short!("subtract"),
+    ));
+    let result = client.batch_update_balances(&admin, &requests);
+
+    assert_eq!(result.successful, 2);
+    assert_eq!(
+        client.get_balance(&user, &symbol_short!("USDC")),
+        700_000_000
+    );
+}
+
+#[test]
+fn test_set_if_absent_skips_existing_balances() {
+    let (env, admin, client) = setup_test_contract();
+    let existing = Address::generate(&env);
+    let fresh = Address::generate(&env);
+    let set_if_absent = Symbol::new(&env, "set_if_absent");
+
+    let mut requests: Vec<BalanceUpdateRequest> = Vec::new(&env);
+    requests.push_back(create_valid_request(
+        &env,
+        &existing,
+        symbol_short!("USDC"),
+        500_000_000,
+        symbol_short!("set"),
+    ));
+    client.batch_update_balances(&admin, &requests);
+
+    let mut requests: Vec<BalanceUpdateRequest> = Vec::new(&env);
+    requests.push_back(create_valid_request(
+        &env,
+        &existing,
+        symbol_short!("USDC"),
+        100_000_000,
+        set_if_absent.clone(),
+    ));
+    requests.push_back(create_valid_request(
+        &env,
+        &fresh,
+        symbol_short!("USDC"),
+        100_000_000,
+        set_if_absent,
+    ));
+    let result = client.batch_update_balances(&admin, &requests);
+
+    assert_eq!(result.successful, 1);
+    assert_eq!(result.failed, 1);
+    match &result.results.get(0).unwrap() {
+        BalanceUpdateResult::Failure(_, _, error_code) => {
+            assert_eq!(*error_code, ErrorCode::ALREADY_EXISTS);
+        }
+        BalanceUpdateResult::Success(_) => panic!("Expected failure"),
+    }
+
+    // The live balance is untouched and the absent one is created
+    assert_eq!(
+        client.get_balance(&existing, &symbol_short!("USDC")),
+        500_000_000
+    );
+    assert_eq!(
+        client.get_balance(&fresh, &symbol_short!("USDC")),
+        100_000_000
+    );
+}
+
+#[test]
+fn test_balance_history_keeps_most_recent_changes() {
+    let (env, admin, client) = setup_test_contract();
+    let user = Address::generate(&env);
+    let usdc = symbol_short!("USDC");
+
+    // Twelve updates, each on its own ledger: one set followed by eleven adds
+    for i in 0..12u32 {
+        env.ledger().with_mut(|li| li.sequence_number = 100 + i);
+        let operation = if i == 0 {
+            symbol_short!("set")
+        } else {
+            symbol_short!("add")
+        };
+        let mut requests: Vec<BalanceUpdateRequest> = Vec::new(&env);
+        requests.push_back(create_valid_request(
+            &env,
+            &user,
+            usdc.clone(),
+            10,
+            operation,
+        ));
+        client.batch_update_balances(&admin, &requests);
+    }
+
+    let history = client.get_balance_history(&user, &usdc);
+    assert_eq!(history.len(), MAX_BALANCE_HISTORY);
+
+    // The two oldest entries were evicted; the rest are in order
+    assert_eq!(history.get(0).unwrap(), (102, symbol_short!("add"), 30));
+    assert_eq!(history.get(9).unwrap(), (111, symbol_short!("add"), 120));
+
+    // Other currencies keep their own history
+    assert_eq!(
+        client
+            .get_balance_history(&user, &symbol_short!("XLM"))
+            .len(),
+        0
+    );
+}
+
+#[test]
+fn test_close_account_removes_balance_history() {
+    let (env, admin, client) = setup_test_contract();
+    let user = Address::generate(&env);
+    let usdc = symbol_short!("USDC");
+
+    let mut requests: Vec<BalanceUpdateRequest> = Vec::new(&env);
+    requests.push_back(create_valid_request(
+        &env,
+        &user,
+        usdc.clone(),
+        1_000,
+        symbol_short!("set"),
+    ));
+    client.batch_update_balances(&admin, &requests);
+    assert_eq!(client.get_balance_history(&user, &usdc).len(), 1);
+
+    client.close_account(&admin, &user, &true);
+
+    assert_eq!(client.get_balance_history(&user, &usdc).len(), 0);
+}
@@ -1,6 +1,6 @@
 //! Data types and events for batch multi-currency wallet operations.
 
-use soroban_sdk::{contracttype, symbol_short, Address, Env, Symbol, Vec};
+use soroban_sdk::{contractclient, contracttype, symbol_short, Address, Env, Map, Symbol, Vec};
 
 /// Maximum number of balance updates in a single batch for optimization.
 pub const MAX_BATCH_SIZE: u32 = 100;
@@ -11,6 +11,15 @@ pub const MIN_BALANCE: i128 = 1;
 /// Maximum balance value (preventing overflow)
 pub const MAX_BALANCE: i128 = i128::MAX;
 
+/// Default balance at or above which `large_balance_update` is emitted
+pub const DEFAULT_LARGE_BALANCE_THRESHOLD: i128 = 1_000_000;
+
+/// Decimal precision assumed for currencies without configured metadata (XLM stroops)
+pub const DEFAULT_CURRENCY_DECIMALS: u32 = 7;
+
+/// Number of recent balance changes kept per user and currency
+pub const MAX_BALANCE_HISTORY: u32 = 10;
+
 /// Represents a balance update request for a user in a specific currency.
 #[derive(Clone, Debug)]
 #[contracttype]
@@ -21,7 +30,7 @@ pub struct BalanceUpdateRequest {
     pub currency: Symbol,
     /// New balance amount (in smallest unit)
     pub amount: i128,
-    /// Update type: "set", "add", or "subtract"
+    /// Update type: "set", "set_if_absent", "add", or "subtract"
     pub operation: Symbol,
 }
 
@@ -39,6 +48,18 @@ pub struct CurrencyBalance {
     pub updated_at: u64,
 }
 
+/// Stored record of a successful balance update for audit trails.
+#[derive(Clone, Debug)]
+#[contracttype]
+pub struct UpdateReceipt {
+    /// Balance after the update
+    pub balance: CurrencyBalance,
+    /// Balance before the update
+    pub previous_balance: i128,
+    /// Update type that was applied: "set", "add", or "subtract"
+    pub operation: Symbol,
+}
+
 /// Result of processing a single balance update.
 #[derive(Clone, Debug)]
 #[contracttype]
@@ -97,9 +118,34 @@ pub enum DataKey {
     TotalBalancesUpdated,
     /// Total batches processed lifetime
     TotalBatchesProcessed,
+    /// Admin-set multiplier above which a single update counts as a large relative change
+    LargeChangeFactor,
+    /// Credit line depth a balance may go below zero: (user_address, currency)
+    CreditFloor(Address, Symbol),
+    /// Decimal precision of a currency for display purposes
+    CurrencyDecimals(Symbol),
+    /// Global balance threshold for large balance events
+    LargeBalanceThreshold,
+    /// Per-currency override of the large balance threshold
+    CurrencyLargeBalanceThreshold(Symbol),
+    /// Currencies a user has ever held a balance or credit floor in
+    UserCurrencies(Address),
+    /// Successful updates processed lifetime per operation ("set", "add", "subtract")
+    OperationCount(Symbol),
+    /// Whether batch updates emit per-item balance events (defaults to true)
+    VerboseEvents,
+    /// Whether batch updates store a receipt per successful update (defaults to false)
+    ReceiptsEnabled,
+    /// Receipt for a successful update: (batch_id, index in the batch)
+    UpdateReceipt(u64, u32),
+    /// Spending-limits contract consulted before subtract operations
+    LimitsContract,
+    /// Recent (ledger, operation, new_balance) changes: (user_address, currency)
+    BalanceHistory(Address, Symbol),
 }
 
 /// Error codes for balance update validation.
+#[allow(non_snake_case)]
 pub mod ErrorCode {
     /// Invalid balance amount (negative or exceeds max)
     pub const INVALID_AMOUNT: u32 = 0;
@@ -113,6 +159,17 @@ pub mod ErrorCode {
     pub const INSUFFICIENT_BALANCE: u32 = 4;
     /// Arithmetic overflow
     pub const ARITHMETIC_OVERFLOW: u32 = 5;
+    /// Subtract rejected by the configured spending-limits contract
+    pub const LIMIT_EXCEEDED: u32 = 6;
+    /// `set_if_absent` target already has a balance
+    pub const ALREADY_EXISTS: u32 = 7;
+}
+
+/// Interface of the spending-limits contract checked before subtract operations.
+#[contractclient(name = "SpendingLimitsClient")]
+pub trait SpendingLimitsInterface {
+    /// Returns whether `user` may spend `amount` under their current limit.
+    fn check_spend_allowed(env: Env, user: Address, amount: i128) -> bool;
 }
 
 /// Events emitted by the multi-currency wallet contract.
@@ -161,7 +218,13 @@ impl WalletEvents {
         env.events().publish(topics, (successful, failed));
     }
 
-    /// Event emitted for large balance updates (>= 1,000,000 units).
+    /// Event emitted once per batch with the number of failures per error code.
+    pub fn batch_failure_summary(env: &Env, batch_id: u64, failure_counts: &Map<u32, u32>) {
+        let topics = (symbol_short!("batch"), symbol_short!("fail_sum"), batch_id);
+        env.events().publish(topics, failure_counts.clone());
+    }
+
+    /// Event emitted when a balance reaches the large balance threshold.
     pub fn large_balance_update(
         env: &Env,
         batch_id: u64,
@@ -173,4 +236,45 @@ impl WalletEvents {
         env.events()
             .publish(topics, (user.clone(), currency.clone(), amount));
     }
+
+    /// Event emitted when a subtract brings a balance to exactly zero.
+    pub fn balance_zeroed(env: &Env, batch_id: u64, user: &Address, currency: &Symbol) {
+        let topics = (symbol_short!("balance"), symbol_short!("zeroed"), batch_id);
+        env.events()
+            .publish(topics, (user.clone(), currency.clone()));
+    }
+
+    /// Event emitted when an account is closed and its balances and credit
+    /// floors removed.
+    pub fn account_closed(env: &Env, user: &Address, currencies_removed: u32, forced: bool) {
+        let topics = (symbol_short!("account"), symbol_short!("closed"));
+        env.events()
+            .publish(topics, (user.clone(), currencies_removed, forced));
+    }
+
+    /// Event emitted when an update moves a balance by more than the configured
+    /// multiple of its previous value.
+    pub fn large_relative_change(
+        env: &Env,
+        batch_id: u64,
+        user: &Address,
+        currency: &Symbol,
+        previous_balance: i128,
+        new_balance: i128,
+    ) {
+        let topics = (
+            symbol_short!("balance"),
+            symbol_short!("relchange"),
+            batch_id,
+        );
+        env.events().publish(
+            topics,
+            (
+                user.clone(),
+                currency.clone(),
+                previous_balance,
+                new_balance,
+            ),
+        );
+    }
 }
@@ -1,8 +1,11 @@
 //! Validation logic for balance update requests.
 
-use soroban_sdk::{Address, Env, Symbol};
+use soroban_sdk::{symbol_short, Address, Env, Symbol};
 
-use crate::types::{BalanceUpdateRequest, DataKey, ErrorCode, MAX_BALANCE, MIN_BALANCE};
+use crate::types::{
+    BalanceUpdateRequest, CurrencyBalance, DataKey, ErrorCode, SpendingLimitsClient, MAX_BALANCE,
+    MIN_BALANCE,
+};
 
 /// Validates a balance update request.
 ///
@@ -54,7 +57,7 @@ fn is_valid_currency(_currency: &Symbol) -> bool {
 /// # Returns
 /// * `true` if amount is >= MIN_BALANCE and <= MAX_BALANCE
 pub fn is_valid_amount(amount: i128) -> bool {
-    amount >= MIN_BALANCE && amount <= MAX_BALANCE
+    (MIN_BALANCE..=MAX_BALANCE).contains(&amount)
 }
 
 /// Validates that an operation type is valid.
@@ -63,15 +66,15 @@ pub fn is_valid_amount(amount: i128) -> bool {
 /// * `operation` - The operation symbol to validate
 ///
 /// # Returns
-/// * `true` if operation is "set", "add", or "subtract"
-pub fn is_valid_operation(operation: &Symbol) -> bool {
+/// * `true` if operation is "set", "set_if_absent", "add", or "subtract"
+pub fn is_valid_operation(_operation: &Symbol) -> bool {
     // In Soroban, we can't directly convert Symbol to string in no_std
     // We'll accept any symbol here and handle invalid operations during execution
-    // Valid operations: "set", "add", "subtract"
+    // Valid operations: "set", "set_if_absent", "add", "subtract"
     true
 }
 
-/// Validates balance after operation to prevent negative balances.
+/// Validates balance after operation to prevent balances below the credit floor.
 ///
 /// # Arguments
 /// * `env` - The contract environment
@@ -83,6 +86,7 @@ pub fn is_valid_operation(operation: &Symbol) -> bool {
 /// # Returns
 /// * `Ok(new_balance)` if operation is valid
 /// * `Err(error_code)` if operation would result in invalid balance
+#[allow(clippy::absurd_extreme_comparisons)]
 pub fn validate_and_compute_balance(
     env: &Env,
     user: &Address,
@@ -91,17 +95,28 @@ pub fn validate_and_compute_balance(
     amount: i128,
 ) -> Result<i128, u32> {
     // Get current balance
-    let current_balance: i128 = env
+    let stored: Option<CurrencyBalance> = env
         .storage()
         .persistent()
-        .get(&DataKey::Balance(user.clone(), currency.clone()))
-        .unwrap_or(0);
+        .get(&DataKey::Balance(user.clone(), currency.clone()));
+
+    // Never overwrite a live balance with `set_if_absent`
+    if stored.is_some() && *operation == Symbol::new(env, "set_if_absent") {
+        return Err(ErrorCode::ALREADY_EXISTS);
+    }
+
+    let current_balance = stored.map(|b| b.balance).unwrap_or(0);
 
     // Compute new balance based on operation
-    let new_balance = compute_new_balance(current_balance, operation, amount)?;
+    let new_balance = compute_new_balance(env, current_balance, operation, amount)?;
 
-    // Validate new balance is non-negative
-    if new_balance < 0 {
+    // Validate new balance stays above the credit floor (zero unless configured)
+    let credit_floor: i128 = env
+        .storage()
+        .persistent()
+        .get(&DataKey::CreditFloor(user.clone(), currency.clone()))
+        .unwrap_or(0);
+    if new_balance < -credit_floor {
         return Err(ErrorCode::INSUFFICIENT_BALANCE);
     }
 
@@ -110,24 +125,38 @@ pub fn validate_and_compute_balance(
         return Err(ErrorCode::ARITHMETIC_OVERFLOW);
     }
 
+    // Subtracts must also be allowed by the paired spending-limits contract, if any
+    if *operation == symbol_short!("subtract") {
+        let limits: Option<Address> = env.storage().instance().get(&DataKey::LimitsContract);
+        if let Some(limits) = limits {
+            if !SpendingLimitsClient::new(env, &limits).check_spend_allowed(user, &amount) {
+                return Err(ErrorCode::LIMIT_EXCEEDED);
+            }
+        }
+    }
+
     Ok(new_balance)
 }
 
 /// Computes new balance based on operation.
-fn compute_new_balance(current: i128, operation: &Symbol, amount: i128) -> Result<i128, u32> {
-    // Note: In production, use proper symbol comparison
-    // For now, we'll use symbol_short! macro patterns
-    let op_str = operation.to_string();
-
-    match op_str.as_str() {
-        "set" => Ok(amount),
-        "add" => current
+fn compute_new_balance(
+    env: &Env,
+    current: i128,
+    operation: &Symbol,
+    amount: i128,
+) -> Result<i128, u32> {
+    if *operation == symbol_short!("set") || *operation == Symbol::new(env, "set_if_absent") {
+        Ok(amount)
+    } else if *operation == symbol_short!("add") {
+        current
             .checked_add(amount)
-            .ok_or(ErrorCode::ARITHMETIC_OVERFLOW),
-        "subtract" => current
+            .ok_or(ErrorCode::ARITHMETIC_OVERFLOW)
+    } else if *operation == symbol_short!("subtract") {
+        current
             .checked_sub(amount)
-            .ok_or(ErrorCode::ARITHMETIC_OVERFLOW),
-        _ => Err(ErrorCode::INVALID_OPERATION),
+            .ok_or(ErrorCode::ARITHMETIC_OVERFLOW)
+    } else {
+        Err(ErrorCode::INVALID_OPERATION)
     }
 }
 
@@ -140,7 +169,7 @@ mod tests {
         BalanceUpdateRequest {
             user: Address::generate(env),
             currency: symbol_short!("USDC"),
-            amount: 1000_000_000, // 1000 USDC
+            amount: 1_000_000_000, // 1000 USDC
             operation: symbol_short!("set"),
         }
     }
@@ -178,7 +207,7 @@ mod tests {
     fn test_is_valid_amount() {
         assert!(is_valid_amount(MIN_BALANCE));
         assert!(is_valid_amount(MAX_BALANCE));
-        assert!(is_valid_amount(1000_000_000));
+        assert!(is_valid_amount(1_000_000_000));
         assert!(!is_valid_amount(MIN_BALANCE - 1));
         assert!(!is_valid_amount(0));
         assert!(!is_valid_amount(-1000));
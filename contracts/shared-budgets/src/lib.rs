@@ -1,16 +1,16 @@
 //! # Shared Budgets Contract
-//! Batch allocation of a caller's balance (shared budget) to multiple recipients.
+//! Batch allocation of a pre-funded budget pool to multiple recipients.
 
 #![no_std]
 
 mod types;
 mod validation;
 
-use soroban_sdk::{contract, contractimpl, panic_with_error, Address, Env, Vec};
+use soroban_sdk::{contract, contractimpl, panic_with_error, token, Address, Env, Symbol, Vec};
 
 pub use crate::types::{
     AllocationBatchResult, AllocationRequest, AllocationResult, DataKey, SharedBudgetEvents,
-    MAX_BATCH_SIZE,
+    MAX_BATCH_SIZE, MAX_RECIPIENT_HISTORY,
 };
 use crate::validation::{validate_address, validate_amount};
 
@@ -26,6 +26,16 @@ pub enum SharedBudgetError {
     EmptyBatch = 3,
     /// Batch exceeds maximum size
     BatchTooLarge = 4,
+    /// Amount must be positive
+    InvalidAmount = 5,
+    /// Token does not match the token held by the pool
+    PoolTokenMismatch = 6,
+    /// Weights are zero or do not sum to 10000 bps
+    InvalidWeights = 7,
+    /// Withdrawal exceeds the unallocated pool balance
+    InsufficientPool = 8,
+    /// Allocation would push the recipient past their cap
+    RecipientCapExceeded = 9,
 }
 
 impl From<SharedBudgetError> for soroban_sdk::Error {
@@ -53,21 +63,109 @@ impl SharedBudgetContract {
         env.storage()
             .instance()
             .set(&DataKey::TotalAllocatedVolume, &0i128);
+        env.storage().instance().set(&DataKey::PoolBalance, &0i128);
     }
 
-    /// Allocates a shared budget (caller balance) to multiple recipients in batch.
+    /// Transfers `amount` of `token` from the funder into the budget pool.
+    /// The first funding fixes the pool token; later fundings must use it.
+    pub fn fund_pool(env: Env, funder: Address, token: Address, amount: i128) {
+        funder.require_auth();
+
+        if amount <= 0 {
+            panic_with_error!(&env, SharedBudgetError::InvalidAmount);
+        }
+
+        let pool_token: Option<Address> = env.storage().instance().get(&DataKey::PoolToken);
+        match pool_token {
+            Some(existing) if existing != token => {
+                panic_with_error!(&env, SharedBudgetError::PoolTokenMismatch);
+            }
+            Some(_) => {}
+            None => env.storage().instance().set(&DataKey::PoolToken, &token),
+        }
+
+        token::Client::new(&env, &token).transfer(
+            &funder,
+            &env.current_contract_address(),
+            &amount,
+        );
+
+        let pool_balance = Self::get_pool_balance(env.clone())
+            .checked_add(amount)
+            .unwrap_or(i128::MAX);
+        env.storage()
+            .instance()
+            .set(&DataKey::PoolBalance, &pool_balance);
+
+        SharedBudgetEvents::pool_funded(&env, &funder, amount, pool_balance);
+    }
+
+    /// Returns `amount` of unallocated pool funds to the admin.
+    pub fn withdraw_pool(env: Env, admin: Address, token: Address, amount: i128) {
+        admin.require_auth();
+        Self::require_admin(&env, &admin);
+
+        if amount <= 0 {
+            panic_with_error!(&env, SharedBudgetError::InvalidAmount);
+        }
+
+        let pool_token: Option<Address> = env.storage().instance().get(&DataKey::PoolToken);
+        if pool_token != Some(token.clone()) {
+            panic_with_error!(&env, SharedBudgetError::PoolTokenMismatch);
+        }
+
+        let pool_balance = Self::get_pool_balance(env.clone());
+        if amount > pool_balance {
+            panic_with_error!(&env, SharedBudgetError::InsufficientPool);
+        }
+
+        token::Client::new(&env, &token).transfer(&env.current_contract_address(), &admin, &amount);
+
+        let remaining = pool_balance - amount;
+        env.storage()
+            .instance()
+            .set(&DataKey::PoolBalance, &remaining);
+
+        SharedBudgetEvents::pool_withdrawn(&env, &admin, amount, remaining);
+    }
+
+    /// Allocates the shared budget pool to multiple recipients in batch.
     /// Performs per-recipient validation and supports partial failures. The caller
-    /// must be the configured admin and the source of funds.
+    /// must be the configured admin; funds are paid out of the pool, and entries
+    /// that exceed the remaining pool balance fail.
+    ///
+    /// When `client_batch_ref` is supplied and was already processed, the stored
+    /// result is returned and nothing is allocated again.
     pub fn allocate_shared_budget_batch(
         env: Env,
         caller: Address,
-        _token: Address,
+        token: Address,
         allocations: Vec<AllocationRequest>,
+        client_batch_ref: Option<Symbol>,
     ) -> AllocationBatchResult {
         // Verify authorization
         caller.require_auth();
         Self::require_admin(&env, &caller);
 
+        // Replay of an already processed reference returns the original result
+        if let Some(batch_ref) = &client_batch_ref {
+            let processed: Option<AllocationBatchResult> = env
+                .storage()
+                .persistent()
+                .get(&DataKey::ProcessedAllocationRef(batch_ref.clone()));
+            if let Some(result) = processed {
+                return result;
+            }
+        }
+
+        // Allocations must be paid in the pool's token
+        let pool_token: Option<Address> = env.storage().instance().get(&DataKey::PoolToken);
+        if let Some(pool_token) = pool_token {
+            if pool_token != token {
+                panic_with_error!(&env, SharedBudgetError::PoolTokenMismatch);
+            }
+        }
+
         // Validate batch size
         let request_count = allocations.len();
         if request_count == 0 {
@@ -93,6 +191,8 @@ impl SharedBudgetContract {
         let mut successful_count: u32 = 0;
         let mut failed_count: u32 = 0;
         let mut total_allocated: i128 = 0;
+        let mut pool_balance = Self::get_pool_balance(env.clone());
+        let token_client = token::Client::new(&env, &token);
 
         // First pass: validate requests and build an internal list
         let mut validated_requests: Vec<(AllocationRequest, bool, u32)> = Vec::new(&env);
@@ -119,7 +219,7 @@ impl SharedBudgetContract {
                 results.push_back(AllocationResult::Failure(
                     request.recipient.clone(),
                     request.amount,
-                    error_code.clone(),
+                    error_code,
                 ));
                 failed_count += 1;
                 SharedBudgetEvents::allocation_failure(
@@ -127,20 +227,48 @@ impl SharedBudgetContract {
                     batch_id,
                     &request.recipient,
                     request.amount,
-                    error_code.clone(),
+                    error_code,
                 );
                 continue;
             }
 
-            // Simulate insufficient shared budget for very large amounts.
-            // This avoids relying on real token balances while still
-            // exercising partial failure behavior.
-            const MAX_SIMULATED_SHARED_BUDGET: i128 = 1_000_000_000_000; // 1e12
-            if request.amount > MAX_SIMULATED_SHARED_BUDGET {
+            // Fail entries that would push the recipient past their cap
+            let allocated_so_far =
+                Self::get_recipient_allocated(env.clone(), request.recipient.clone());
+            let new_recipient_total = allocated_so_far.checked_add(request.amount);
+            let cap: Option<i128> = env
+                .storage()
+                .persistent()
+                .get(&DataKey::RecipientCap(request.recipient.clone()));
+            let exceeds_cap = match (cap, new_recipient_total) {
+                (Some(cap), Some(total)) => total > cap,
+                (Some(_), None) => true,
+                (None, _) => false,
+            };
+            if exceeds_cap {
+                let error_code = SharedBudgetError::RecipientCapExceeded as u32;
                 results.push_back(AllocationResult::Failure(
                     request.recipient.clone(),
                     request.amount,
-                    2, // Simulated insufficient shared budget
+                    error_code,
+                ));
+                failed_count += 1;
+                SharedBudgetEvents::allocation_failure(
+                    &env,
+                    batch_id,
+                    &request.recipient,
+                    request.amount,
+                    error_code,
+                );
+                continue;
+            }
+
+            // Fail entries that exceed what is left in the pool
+            if request.amount > pool_balance {
+                results.push_back(AllocationResult::Failure(
+                    request.recipient.clone(),
+                    request.amount,
+                    2, // Insufficient shared budget
                 ));
                 failed_count += 1;
                 SharedBudgetEvents::allocation_failure(
@@ -153,7 +281,18 @@ impl SharedBudgetContract {
                 continue;
             }
 
-            // Allocation succeeded (we only validate inputs; no on-chain transfer here)
+            // Pay the recipient out of the pool
+            token_client.transfer(
+                &env.current_contract_address(),
+                &request.recipient,
+                &request.amount,
+            );
+            pool_balance -= request.amount;
+            env.storage().persistent().set(
+                &DataKey::RecipientAllocated(request.recipient.clone()),
+                &new_recipient_total.unwrap_or(i128::MAX),
+            );
+            Self::record_recipient_allocation(&env, &request.recipient, batch_id, request.amount);
             total_allocated = total_allocated
                 .checked_add(request.amount)
                 .unwrap_or(total_allocated);
@@ -192,6 +331,9 @@ impl SharedBudgetContract {
         env.storage()
             .instance()
             .set(&DataKey::TotalBatches, &(total_batches + 1));
+        env.storage()
+            .instance()
+            .set(&DataKey::PoolBalance, &pool_balance);
         env.storage()
             .instance()
             .set(
@@ -214,13 +356,116 @@ impl SharedBudgetContract {
             total_allocated,
         );
 
-        AllocationBatchResult {
+        let result = AllocationBatchResult {
             total_requests: request_count,
             successful: successful_count,
             failed: failed_count,
             total_allocated,
             results,
+        };
+
+        if let Some(batch_ref) = client_batch_ref {
+            env.storage()
+                .persistent()
+                .set(&DataKey::ProcessedAllocationRef(batch_ref), &result);
+        }
+
+        result
+    }
+
+    /// Splits `total` across recipients by basis-point weights and allocates it
+    /// as a regular batch. Weights must be non-zero and sum to 10000; rounding
+    /// remainder goes to the last recipient so the amounts sum exactly to `total`.
+    pub fn allocate_shared_budget_weighted(
+        env: Env,
+        caller: Address,
+        token: Address,
+        total: i128,
+        weights: Vec<(Address, u32)>,
+    ) -> AllocationBatchResult {
+        if total <= 0 {
+            panic_with_error!(&env, SharedBudgetError::InvalidAmount);
+        }
+        if weights.is_empty() {
+            panic_with_error!(&env, SharedBudgetError::EmptyBatch);
+        }
+
+        let mut weight_sum: u64 = 0;
+        for (_, weight) in weights.iter() {
+            if weight == 0 {
+                panic_with_error!(&env, SharedBudgetError::InvalidWeights);
+            }
+            weight_sum += weight as u64;
         }
+        if weight_sum != 10_000 {
+            panic_with_error!(&env, SharedBudgetError::InvalidWeights);
+        }
+
+        let last_index = weights.len() - 1;
+        let mut assigned: i128 = 0;
+        let mut allocations: Vec<AllocationRequest> = Vec::new(&env);
+        for (index, (recipient, weight)) in weights.iter().enumerate() {
+            let amount = if index as u32 == last_index {
+                total - assigned
+            } else {
+                total
+                    .checked_mul(weight as i128)
+                    .map(|v| v / 10_000)
+                    .unwrap_or_else(|| (total / 10_000) * weight as i128)
+            };
+            assigned += amount;
+            allocations.push_back(AllocationRequest { recipient, amount });
+        }
+
+        Self::allocate_shared_budget_batch(env, caller, token, allocations, None)
+    }
+
+    /// Caps the cumulative amount a recipient can receive across batches.
+    ///
+    /// Allocations past the cap fail with `RecipientCapExceeded`.
+    pub fn set_recipient_cap(env: Env, admin: Address, recipient: Address, cap: i128) {
+        admin.require_auth();
+        Self::require_admin(&env, &admin);
+
+        if cap <= 0 {
+            panic_with_error!(&env, SharedBudgetError::InvalidAmount);
+        }
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::RecipientCap(recipient), &cap);
+    }
+
+    /// Returns the allocation cap for a recipient, if any.
+    pub fn get_recipient_cap(env: Env, recipient: Address) -> Option<i128> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::RecipientCap(recipient))
+    }
+
+    /// Returns the cumulative amount allocated to a recipient.
+    pub fn get_recipient_allocated(env: Env, recipient: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::RecipientAllocated(recipient))
+            .unwrap_or(0)
+    }
+
+    /// Returns the recent (batch_id, amount) allocations received by a recipient,
+    /// oldest first. At most `MAX_RECIPIENT_HISTORY` entries are kept.
+    pub fn get_recipient_allocations(env: Env, recipient: Address) -> Vec<(u64, i128)> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::RecipientAllocations(recipient))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Returns the unallocated balance of the budget pool.
+    pub fn get_pool_balance(env: Env) -> i128 {
+        env.storage()
+            .instance()
+            .get(&DataKey::PoolBalance)
+            .unwrap_or(0)
     }
 
     /// Returns the admin address.
@@ -263,6 +508,45 @@ impl SharedBudgetContract {
             .unwrap_or(0)
     }
 
+    /// Returns the number of distinct recipients that have ever received an allocation.
+    pub fn get_unique_recipients(env: Env) -> u64 {
+        env.storage()
+            .instance()
+            .get(&DataKey::UniqueRecipients)
+            .unwrap_or(0)
+    }
+
+    // Internal helper appending to a recipient's bounded allocation history
+    fn record_recipient_allocation(env: &Env, recipient: &Address, batch_id: u64, amount: i128) {
+        // Count the recipient once, on their first successful allocation
+        let seen_key = DataKey::SeenRecipient(recipient.clone());
+        if !env.storage().persistent().has(&seen_key) {
+            env.storage().persistent().set(&seen_key, &true);
+            let unique: u64 = env
+                .storage()
+                .instance()
+                .get(&DataKey::UniqueRecipients)
+                .unwrap_or(0);
+            env.storage()
+                .instance()
+                .set(&DataKey::UniqueRecipients, &(unique + 1));
+        }
+
+        let key = DataKey::RecipientAllocations(recipient.clone());
+        let mut history: Vec<(u64, i128)> = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or(Vec::new(env));
+
+        history.push_back((batch_id, amount));
+        while history.len() > MAX_RECIPIENT_HISTORY {
+            history.pop_front();
+        }
+
+        env.storage().persistent().set(&key, &history);
+    }
+
     // Internal helper to verify admin
     fn require_admin(env: &Env, caller: &Address) {
         let admin: Address = env
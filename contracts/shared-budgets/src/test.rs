@@ -3,16 +3,28 @@
 #![cfg(test)]
 
 use crate::{
-    AllocationBatchResult, AllocationRequest, AllocationResult, SharedBudgetContract,
-    SharedBudgetContractClient,
+    AllocationRequest, AllocationResult, SharedBudgetContract, SharedBudgetContractClient,
+    SharedBudgetError,
 };
 use soroban_sdk::{
+    symbol_short,
     testutils::{Address as _, Events as _, Ledger},
     token, Address, Env, Vec,
 };
 
-/// Creates a test environment with the contract deployed and initialized.
+/// Default amount pre-funded into the budget pool by `setup_test_env`.
+const DEFAULT_POOL: i128 = 1_000_000_000_000;
+
+/// Creates a test environment with the contract deployed, initialized and
+/// its budget pool funded with `DEFAULT_POOL`.
 fn setup_test_env(
+) -> (Env, Address, Address, token::Client<'static>, SharedBudgetContractClient<'static>) {
+    setup_test_env_with_pool(DEFAULT_POOL)
+}
+
+/// Creates a test environment whose budget pool holds `pool` (0 for an unfunded pool).
+fn setup_test_env_with_pool(
+    pool: i128,
 ) -> (Env, Address, Address, token::Client<'static>, SharedBudgetContractClient<'static>) {
     let env = Env::default();
     env.mock_all_auths();
@@ -33,6 +45,12 @@ fn setup_test_env(
     let admin = Address::generate(&env);
     client.initialize(&admin);
 
+    if pool > 0 {
+        let funder = Address::generate(&env);
+        token::StellarAssetClient::new(&env, &token_id).mint(&funder, &pool);
+        client.fund_pool(&funder, &token_id, &pool);
+    }
+
     (env, admin, token_id, token_client, client)
 }
 
@@ -74,7 +92,7 @@ fn test_allocate_shared_budget_single_recipient() {
     let mut allocations: Vec<AllocationRequest> = Vec::new(&env);
     allocations.push_back(create_allocation_request(recipient.clone(), amount));
 
-    let result = client.allocate_shared_budget_batch(&admin, &token, &allocations);
+    let result = client.allocate_shared_budget_batch(&admin, &token, &allocations, &None);
 
     assert_eq!(result.total_requests, 1);
     assert_eq!(result.successful, 1);
@@ -100,7 +118,7 @@ fn test_allocate_shared_budget_multiple_recipients() {
     allocations.push_back(create_allocation_request(recipient2.clone(), amount2));
     allocations.push_back(create_allocation_request(recipient3.clone(), amount3));
 
-    let result = client.allocate_shared_budget_batch(&admin, &token, &allocations);
+    let result = client.allocate_shared_budget_batch(&admin, &token, &allocations, &None);
 
     assert_eq!(result.total_requests, 3);
     assert_eq!(result.successful, 3);
@@ -119,7 +137,7 @@ fn test_allocate_with_invalid_amounts_partial_failures() {
     allocations.push_back(create_allocation_request(recipient1.clone(), -100)); // Invalid
     allocations.push_back(create_allocation_request(recipient2.clone(), 10_000_000)); // Valid
 
-    let result = client.allocate_shared_budget_batch(&admin, &token, &allocations);
+    let result = client.allocate_shared_budget_batch(&admin, &token, &allocations, &None);
 
     assert_eq!(result.total_requests, 2);
     assert_eq!(result.successful, 1);
@@ -141,7 +159,7 @@ fn test_allocate_with_insufficient_shared_budget_partial_failures() {
     allocations.push_back(create_allocation_request(recipient1.clone(), amount1));
     allocations.push_back(create_allocation_request(recipient2.clone(), amount2));
 
-    let result = client.allocate_shared_budget_batch(&admin, &token, &allocations);
+    let result = client.allocate_shared_budget_batch(&admin, &token, &allocations, &None);
 
     assert_eq!(result.total_requests, 2);
     assert_eq!(result.successful, 1);
@@ -160,7 +178,7 @@ fn test_allocation_events_emitted() {
     allocations.push_back(create_allocation_request(recipient1.clone(), 10_000_000));
     allocations.push_back(create_allocation_request(recipient2.clone(), -100)); // Invalid
 
-    client.allocate_shared_budget_batch(&admin, &token, &allocations);
+    client.allocate_shared_budget_batch(&admin, &token, &allocations, &None);
 
     let events = env.events().all();
     // Should have: batch_started, allocation_success (1), allocation_failure (1), batch_completed
@@ -184,17 +202,316 @@ fn test_allocation_stats_accumulate() {
     assert_eq!(client.get_total_allocations_processed(), 0);
     assert_eq!(client.get_total_allocated_volume(), 0);
 
-    client.allocate_shared_budget_batch(&admin, &token, &batch1);
+    client.allocate_shared_budget_batch(&admin, &token, &batch1, &None);
     assert_eq!(client.get_total_batches(), 1);
     assert_eq!(client.get_total_allocations_processed(), 1);
     assert_eq!(client.get_total_allocated_volume(), 10_000_000);
 
-    client.allocate_shared_budget_batch(&admin, &token, &batch2);
+    client.allocate_shared_budget_batch(&admin, &token, &batch2, &None);
     assert_eq!(client.get_total_batches(), 2);
     assert_eq!(client.get_total_allocations_processed(), 2);
     assert_eq!(client.get_total_allocated_volume(), 30_000_000);
 }
 
+// Budget Pool Tests
+
+#[test]
+fn test_fund_pool_and_allocate_within_pool() {
+    let (env, admin, token, token_client, client) = setup_test_env_with_pool(0);
+    assert_eq!(client.get_pool_balance(), 0);
+
+    let funder = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token).mint(&funder, &50_000_000);
+    client.fund_pool(&funder, &token, &50_000_000);
+
+    assert_eq!(client.get_pool_balance(), 50_000_000);
+    assert_eq!(token_client.balance(&funder), 0);
+    assert_eq!(token_client.balance(&client.address), 50_000_000);
+
+    let recipient = Address::generate(&env);
+    let mut allocations: Vec<AllocationRequest> = Vec::new(&env);
+    allocations.push_back(create_allocation_request(recipient.clone(), 30_000_000));
+
+    let result = client.allocate_shared_budget_batch(&admin, &token, &allocations, &None);
+
+    assert_eq!(result.successful, 1);
+    assert_eq!(client.get_pool_balance(), 20_000_000);
+    assert_eq!(token_client.balance(&recipient), 30_000_000);
+    // The admin is not the source of funds
+    assert_eq!(token_client.balance(&admin), 0);
+}
+
+#[test]
+fn test_allocation_beyond_pool_fails_without_debit() {
+    let (env, admin, token, token_client, client) = setup_test_env_with_pool(0);
+
+    let funder = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token).mint(&funder, &50_000_000);
+    client.fund_pool(&funder, &token, &50_000_000);
+
+    let recipient1 = Address::generate(&env);
+    let recipient2 = Address::generate(&env);
+    let recipient3 = Address::generate(&env);
+
+    let mut allocations: Vec<AllocationRequest> = Vec::new(&env);
+    allocations.push_back(create_allocation_request(recipient1.clone(), 30_000_000));
+    // Only 20_000_000 remains when the second entry is processed
+    allocations.push_back(create_allocation_request(recipient2.clone(), 30_000_000));
+    allocations.push_back(create_allocation_request(recipient3.clone(), 20_000_000));
+
+    let result = client.allocate_shared_budget_batch(&admin, &token, &allocations, &None);
+
+    assert_eq!(result.successful, 2);
+    assert_eq!(result.failed, 1);
+    match result.results.get(1).unwrap() {
+        AllocationResult::Failure(addr, amount, error_code) => {
+            assert_eq!(addr, recipient2);
+            assert_eq!(amount, 30_000_000);
+            assert_eq!(error_code, 2);
+        }
+        _ => panic!("Expected failure for over-allocation"),
+    }
+    assert_eq!(token_client.balance(&recipient2), 0);
+    assert_eq!(client.get_pool_balance(), 0);
+}
+
+#[test]
+fn test_unfunded_pool_fails_all_allocations() {
+    let (env, admin, token, _token_client, client) = setup_test_env_with_pool(0);
+
+    let mut allocations: Vec<AllocationRequest> = Vec::new(&env);
+    allocations.push_back(create_allocation_request(
+        Address::generate(&env),
+        10_000_000,
+    ));
+
+    let result = client.allocate_shared_budget_batch(&admin, &token, &allocations, &None);
+
+    assert_eq!(result.successful, 0);
+    assert_eq!(result.failed, 1);
+    assert_eq!(client.get_pool_balance(), 0);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #6)")]
+fn test_allocate_with_other_token_rejected() {
+    let (env, admin, _token, _token_client, client) = setup_test_env();
+
+    let other_token = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    let mut allocations: Vec<AllocationRequest> = Vec::new(&env);
+    allocations.push_back(create_allocation_request(
+        Address::generate(&env),
+        10_000_000,
+    ));
+
+    client.allocate_shared_budget_batch(&admin, &other_token, &allocations, &None);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #5)")]
+fn test_fund_pool_rejects_non_positive_amount() {
+    let (env, _admin, token, _token_client, client) = setup_test_env();
+
+    client.fund_pool(&Address::generate(&env), &token, &0);
+}
+
+#[test]
+fn test_withdraw_pool_remainder_after_allocation() {
+    let (env, admin, token, token_client, client) = setup_test_env_with_pool(50_000_000);
+
+    let recipient = Address::generate(&env);
+    let mut allocations: Vec<AllocationRequest> = Vec::new(&env);
+    allocations.push_back(create_allocation_request(recipient.clone(), 30_000_000));
+    client.allocate_shared_budget_batch(&admin, &token, &allocations, &None);
+
+    client.withdraw_pool(&admin, &token, &20_000_000);
+
+    assert_eq!(client.get_pool_balance(), 0);
+    assert_eq!(token_client.balance(&admin), 20_000_000);
+    assert_eq!(token_client.balance(&client.address), 0);
+    assert_eq!(token_client.balance(&recipient), 30_000_000);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #8)")]
+fn test_withdraw_pool_rejects_over_withdraw() {
+    let (env, admin, token, _token_client, client) = setup_test_env_with_pool(50_000_000);
+
+    let mut allocations: Vec<AllocationRequest> = Vec::new(&env);
+    allocations.push_back(create_allocation_request(
+        Address::generate(&env),
+        30_000_000,
+    ));
+    client.allocate_shared_budget_batch(&admin, &token, &allocations, &None);
+
+    client.withdraw_pool(&admin, &token, &20_000_001);
+}
+
+// Recipient Cap Tests
+
+#[test]
+fn test_recipient_cap_enforced_across_batches() {
+    let (env, admin, token, token_client, client) = setup_test_env();
+
+    let capped = Address::generate(&env);
+    let uncapped = Address::generate(&env);
+    client.set_recipient_cap(&admin, &capped, &50_000_000);
+    assert_eq!(client.get_recipient_cap(&capped), Some(50_000_000));
+
+    let mut batch1: Vec<AllocationRequest> = Vec::new(&env);
+    batch1.push_back(create_allocation_request(capped.clone(), 30_000_000));
+    batch1.push_back(create_allocation_request(uncapped.clone(), 80_000_000));
+    let result1 = client.allocate_shared_budget_batch(&admin, &token, &batch1, &None);
+    assert_eq!(result1.successful, 2);
+
+    let mut batch2: Vec<AllocationRequest> = Vec::new(&env);
+    batch2.push_back(create_allocation_request(capped.clone(), 20_000_000)); // Reaches cap
+    batch2.push_back(create_allocation_request(capped.clone(), 1)); // Beyond cap
+    batch2.push_back(create_allocation_request(uncapped.clone(), 80_000_000));
+    let result2 = client.allocate_shared_budget_batch(&admin, &token, &batch2, &None);
+
+    assert_eq!(result2.successful, 2);
+    assert_eq!(result2.failed, 1);
+    match result2.results.get(1).unwrap() {
+        AllocationResult::Failure(addr, amount, error_code) => {
+            assert_eq!(addr, capped);
+            assert_eq!(amount, 1);
+            assert_eq!(error_code, SharedBudgetError::RecipientCapExceeded as u32);
+        }
+        _ => panic!("Expected failure beyond the recipient cap"),
+    }
+
+    assert_eq!(client.get_recipient_allocated(&capped), 50_000_000);
+    assert_eq!(client.get_recipient_allocated(&uncapped), 160_000_000);
+    assert_eq!(token_client.balance(&capped), 50_000_000);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #5)")]
+fn test_set_recipient_cap_rejects_non_positive() {
+    let (env, admin, _token, _token_client, client) = setup_test_env();
+
+    client.set_recipient_cap(&admin, &Address::generate(&env), &0);
+}
+
+// Idempotency Tests
+
+#[test]
+fn test_replayed_batch_ref_does_not_reallocate() {
+    let (env, admin, token, token_client, client) = setup_test_env();
+
+    let recipient = Address::generate(&env);
+    let mut allocations: Vec<AllocationRequest> = Vec::new(&env);
+    allocations.push_back(create_allocation_request(recipient.clone(), 10_000_000));
+
+    let batch_ref = Some(symbol_short!("run1"));
+    let first = client.allocate_shared_budget_batch(&admin, &token, &allocations, &batch_ref);
+    let replay = client.allocate_shared_budget_batch(&admin, &token, &allocations, &batch_ref);
+
+    assert_eq!(replay.successful, first.successful);
+    assert_eq!(replay.total_allocated, first.total_allocated);
+    assert_eq!(token_client.balance(&recipient), 10_000_000);
+    assert_eq!(client.get_total_batches(), 1);
+    assert_eq!(client.get_total_allocated_volume(), 10_000_000);
+
+    // A new reference processes normally
+    let fresh = Some(symbol_short!("run2"));
+    let second = client.allocate_shared_budget_batch(&admin, &token, &allocations, &fresh);
+    assert_eq!(second.successful, 1);
+    assert_eq!(token_client.balance(&recipient), 20_000_000);
+    assert_eq!(client.get_total_batches(), 2);
+}
+
+// Recipient History Tests
+
+#[test]
+fn test_recipient_allocation_history_across_batches() {
+    let (env, admin, token, _token_client, client) = setup_test_env();
+
+    let recipient = Address::generate(&env);
+    let other = Address::generate(&env);
+
+    let mut batch1: Vec<AllocationRequest> = Vec::new(&env);
+    batch1.push_back(create_allocation_request(recipient.clone(), 10_000_000));
+    batch1.push_back(create_allocation_request(other.clone(), 5_000_000));
+    client.allocate_shared_budget_batch(&admin, &token, &batch1, &None);
+
+    let mut batch2: Vec<AllocationRequest> = Vec::new(&env);
+    batch2.push_back(create_allocation_request(recipient.clone(), -1)); // Invalid
+    batch2.push_back(create_allocation_request(recipient.clone(), 20_000_000));
+    client.allocate_shared_budget_batch(&admin, &token, &batch2, &None);
+
+    let history = client.get_recipient_allocations(&recipient);
+    assert_eq!(history.len(), 2);
+    assert_eq!(history.get(0).unwrap(), (1, 10_000_000));
+    assert_eq!(history.get(1).unwrap(), (2, 20_000_000));
+    assert_eq!(client.get_recipient_allocations(&other).len(), 1);
+}
+
+#[test]
+fn test_unique_recipients_counts_union_across_batches() {
+    let (env, admin, token, _token_client, client) = setup_test_env();
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let carol = Address::generate(&env);
+    let dave = Address::generate(&env);
+
+    let mut batch1: Vec<AllocationRequest> = Vec::new(&env);
+    batch1.push_back(create_allocation_request(alice.clone(), 10_000_000));
+    batch1.push_back(create_allocation_request(bob.clone(), 10_000_000));
+    batch1.push_back(create_allocation_request(alice.clone(), 5_000_000));
+    client.allocate_shared_budget_batch(&admin, &token, &batch1, &None);
+    assert_eq!(client.get_unique_recipients(), 2);
+
+    let mut batch2: Vec<AllocationRequest> = Vec::new(&env);
+    batch2.push_back(create_allocation_request(bob.clone(), 10_000_000));
+    batch2.push_back(create_allocation_request(carol.clone(), 10_000_000));
+    batch2.push_back(create_allocation_request(dave.clone(), -1)); // Invalid, not counted
+    client.allocate_shared_budget_batch(&admin, &token, &batch2, &None);
+
+    assert_eq!(client.get_unique_recipients(), 3);
+}
+
+// Weighted Allocation Tests
+
+#[test]
+fn test_weighted_allocation_sums_to_total() {
+    let (env, admin, token, token_client, client) = setup_test_env();
+
+    let recipient1 = Address::generate(&env);
+    let recipient2 = Address::generate(&env);
+    let recipient3 = Address::generate(&env);
+
+    let mut weights: Vec<(Address, u32)> = Vec::new(&env);
+    weights.push_back((recipient1.clone(), 3_333));
+    weights.push_back((recipient2.clone(), 3_333));
+    weights.push_back((recipient3.clone(), 3_334));
+
+    let result = client.allocate_shared_budget_weighted(&admin, &token, &1_000, &weights);
+
+    assert_eq!(result.successful, 3);
+    assert_eq!(result.total_allocated, 1_000);
+    assert_eq!(token_client.balance(&recipient1), 333);
+    assert_eq!(token_client.balance(&recipient2), 333);
+    // Last recipient absorbs the rounding remainder
+    assert_eq!(token_client.balance(&recipient3), 334);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #7)")]
+fn test_weighted_allocation_rejects_non_summing_weights() {
+    let (env, admin, token, _token_client, client) = setup_test_env();
+
+    let mut weights: Vec<(Address, u32)> = Vec::new(&env);
+    weights.push_back((Address::generate(&env), 5_000));
+    weights.push_back((Address::generate(&env), 4_000));
+
+    client.allocate_shared_budget_weighted(&admin, &token, &1_000, &weights);
+}
+
 // Admin and Error Tests
 
 #[test]
@@ -203,7 +520,7 @@ fn test_allocate_empty_batch_rejected() {
     let (env, admin, token, _token_client, client) = setup_test_env();
 
     let allocations: Vec<AllocationRequest> = Vec::new(&env);
-    client.allocate_shared_budget_batch(&admin, &token, &allocations);
+    client.allocate_shared_budget_batch(&admin, &token, &allocations, &None);
 }
 
 #[test]
@@ -217,7 +534,7 @@ fn test_allocate_unauthorized_caller() {
     let mut allocations: Vec<AllocationRequest> = Vec::new(&env);
     allocations.push_back(create_allocation_request(recipient, 10_000_000));
 
-    client.allocate_shared_budget_batch(&unauthorized, &token, &allocations);
+    client.allocate_shared_budget_batch(&unauthorized, &token, &allocations, &None);
 }
 
 #[test]
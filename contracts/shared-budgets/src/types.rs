@@ -1,10 +1,13 @@
 // Types and events for shared budget batch allocations.
 
-use soroban_sdk::{contracttype, symbol_short, Address, Env, Vec};
+use soroban_sdk::{contracttype, symbol_short, Address, Env, Symbol, Vec};
 
 /// Maximum number of allocation entries in a single batch.
 pub const MAX_BATCH_SIZE: u32 = 100;
 
+/// Maximum number of history entries kept per recipient (oldest evicted first).
+pub const MAX_RECIPIENT_HISTORY: u32 = 50;
+
 /// A single allocation request from a shared budget to a recipient.
 #[derive(Clone, Debug)]
 #[contracttype]
@@ -51,6 +54,22 @@ pub enum DataKey {
     TotalAllocationsProcessed,
     /// Total amount allocated across all batches
     TotalAllocatedVolume,
+    /// Unallocated balance of the pre-funded budget pool
+    PoolBalance,
+    /// Token held by the budget pool
+    PoolToken,
+    /// Admin-set cumulative allocation cap for a recipient
+    RecipientCap(Address),
+    /// Cumulative amount allocated to a recipient across batches
+    RecipientAllocated(Address),
+    /// Recent (batch_id, amount) allocations received by a recipient
+    RecipientAllocations(Address),
+    /// Stored result for a client-supplied batch reference (idempotent retries)
+    ProcessedAllocationRef(Symbol),
+    /// Marker for a recipient that has received any successful allocation
+    SeenRecipient(Address),
+    /// Number of distinct recipients that have ever received an allocation
+    UniqueRecipients,
 }
 
 /// Events emitted by the shared budgets contract.
@@ -63,6 +82,20 @@ impl SharedBudgetEvents {
         env.events().publish(topics, (batch_id, request_count));
     }
 
+    /// Event emitted when the budget pool is funded.
+    pub fn pool_funded(env: &Env, funder: &Address, amount: i128, pool_balance: i128) {
+        let topics = (symbol_short!("pool"), symbol_short!("funded"));
+        env.events()
+            .publish(topics, (funder.clone(), amount, pool_balance));
+    }
+
+    /// Event emitted when the admin withdraws unallocated funds from the pool.
+    pub fn pool_withdrawn(env: &Env, admin: &Address, amount: i128, pool_balance: i128) {
+        let topics = (symbol_short!("pool"), symbol_short!("withdrawn"));
+        env.events()
+            .publish(topics, (admin.clone(), amount, pool_balance));
+    }
+
     /// Event emitted when an allocation succeeds for a recipient.
     pub fn allocation_success(env: &Env, batch_id: u64, recipient: &Address, amount: i128) {
         let topics = (symbol_short!("alloc"), symbol_short!("success"), batch_id);
@@ -3,7 +3,11 @@
 #![cfg(test)]
 
 use crate::{SpendingLimitsContract, SpendingLimitsContractClient};
-use soroban_sdk::{symbol_short, testutils::Address as _, Address, Env, Vec};
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, Ledger},
+    Address, Env, Vec,
+};
 
 use crate::types::{ErrorCode, LimitUpdateResult, SpendingLimitRequest};
 
@@ -22,11 +26,12 @@ fn setup_test_contract() -> (Env, Address, SpendingLimitsContractClient<'static>
 }
 
 /// Helper function to create a valid spending limit request.
-fn create_valid_request(env: &Env, user: &Address, limit: i128) -> SpendingLimitRequest {
+fn create_valid_request(_env: &Env, user: &Address, limit: i128) -> SpendingLimitRequest {
     SpendingLimitRequest {
         user: user.clone(),
         monthly_limit: limit,
         category: Some(symbol_short!("general")),
+        window_ledgers: 0,
     }
 }
 
@@ -95,7 +100,7 @@ fn test_batch_update_spending_limits_multiple_users() {
             LimitUpdateResult::Success(limit) => {
                 assert!(limit.monthly_limit > 0);
                 assert_eq!(limit.current_spending, 0);
-                assert_eq!(limit.is_active, true);
+                assert!(limit.is_active);
             }
             LimitUpdateResult::Failure(_, _) => panic!("Expected success, got failure"),
         }
@@ -231,7 +236,7 @@ fn test_get_spending_limit() {
     assert_eq!(limit.user, user);
     assert_eq!(limit.monthly_limit, 50_000_000_000);
     assert_eq!(limit.current_spending, 0);
-    assert_eq!(limit.is_active, true);
+    assert!(limit.is_active);
 }
 
 #[test]
@@ -412,3 +417,371 @@ fn test_maximum_valid_limit() {
     assert_eq!(result.successful, 1);
     assert_eq!(result.failed, 0);
 }
+
+#[test]
+fn test_record_spending_within_and_over_limit() {
+    let (env, admin, client) = setup_test_contract();
+    let user = Address::generate(&env);
+
+    let mut requests: Vec<SpendingLimitRequest> = Vec::new(&env);
+    requests.push_back(create_valid_request(&env, &user, 10_000_000));
+    client.batch_update_spending_limits(&admin, &requests);
+
+    assert!(client.record_spending(&admin, &user, &6_000_000));
+    assert!(!client.record_spending(&admin, &user, &5_000_000));
+    assert!(client.record_spending(&admin, &user, &4_000_000));
+
+    let limit = client.get_spending_limit(&user).unwrap();
+    assert_eq!(limit.current_spending, 10_000_000);
+}
+
+#[test]
+fn test_preview_spends_matches_record_spending() {
+    let (env, admin, client) = setup_test_contract();
+    let user1 = Address::generate(&env);
+    let user2 = Address::generate(&env);
+    let no_limit_user = Address::generate(&env);
+
+    let mut requests: Vec<SpendingLimitRequest> = Vec::new(&env);
+    requests.push_back(create_valid_request(&env, &user1, 10_000_000));
+    requests.push_back(create_valid_request(&env, &user2, 5_000_000));
+    client.batch_update_spending_limits(&admin, &requests);
+
+    let mut spends: Vec<(Address, i128)> = Vec::new(&env);
+    spends.push_back((user1.clone(), 7_000_000)); // within limit
+    spends.push_back((user2.clone(), 6_000_000)); // over limit
+    spends.push_back((user1.clone(), 4_000_000)); // over limit after the first spend
+    spends.push_back((user2.clone(), 5_000_000)); // exactly at limit
+    spends.push_back((no_limit_user.clone(), 1_000)); // no limit configured
+
+    let preview = client.preview_spends(&spends);
+    assert_eq!(
+        preview,
+        Vec::from_array(&env, [true, false, false, true, false])
+    );
+
+    // Preview must not change stored spending
+    assert_eq!(
+        client.get_spending_limit(&user1).unwrap().current_spending,
+        0
+    );
+
+    for (i, (user, amount)) in spends.iter().enumerate() {
+        let recorded = client.record_spending(&admin, &user, &amount);
+        assert_eq!(recorded, preview.get(i as u32).unwrap());
+    }
+}
+
+#[test]
+fn test_check_spend_allowed_tracks_recorded_spending() {
+    let (env, admin, client) = setup_test_contract();
+    let user = Address::generate(&env);
+    let no_limit_user = Address::generate(&env);
+
+    let mut requests: Vec<SpendingLimitRequest> = Vec::new(&env);
+    requests.push_back(create_valid_request(&env, &user, 10_000_000));
+    client.batch_update_spending_limits(&admin, &requests);
+
+    assert!(client.check_spend_allowed(&user, &10_000_000));
+    assert!(!client.check_spend_allowed(&user, &10_000_001));
+    assert!(!client.check_spend_allowed(&no_limit_user, &1_000));
+
+    // Checking must not record anything
+    assert_eq!(
+        client.get_spending_limit(&user).unwrap().current_spending,
+        0
+    );
+
+    client.record_spending(&admin, &user, &7_000_000);
+    assert!(client.check_spend_allowed(&user, &3_000_000));
+    assert!(!client.check_spend_allowed(&user, &3_000_001));
+}
+
+#[test]
+fn test_record_spending_converted_applies_rate() {
+    let (env, admin, client) = setup_test_contract();
+    let user = Address::generate(&env);
+
+    let mut requests: Vec<SpendingLimitRequest> = Vec::new(&env);
+    requests.push_back(create_valid_request(&env, &user, 10_000_000));
+    client.batch_update_spending_limits(&admin, &requests);
+
+    // 4,000,000 EURC at 1.1 limit units per EURC counts as 4,400,000
+    assert!(client.record_spending_converted(
+        &admin,
+        &user,
+        &4_000_000,
+        &symbol_short!("EURC"),
+        &11_000
+    ));
+    assert_eq!(
+        client.get_spending_limit(&user).unwrap().current_spending,
+        4_400_000
+    );
+
+    // 5,500,000 unconverted would fit, but 6,050,000 converted does not
+    assert!(!client.record_spending_converted(
+        &admin,
+        &user,
+        &5_500_000,
+        &symbol_short!("EURC"),
+        &11_000
+    ));
+    assert_eq!(
+        client.get_spending_limit(&user).unwrap().current_spending,
+        4_400_000
+    );
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #6)")]
+fn test_record_spending_converted_rejects_zero_rate() {
+    let (env, admin, client) = setup_test_contract();
+    let user = Address::generate(&env);
+
+    client.record_spending_converted(&admin, &user, &1_000, &symbol_short!("EURC"), &0);
+}
+
+#[test]
+fn test_lowered_max_batch_size_enforced() {
+    let (env, admin, client) = setup_test_contract();
+    assert_eq!(client.get_max_batch_size(), 100);
+
+    client.set_max_batch_size(&admin, &3);
+    assert_eq!(client.get_max_batch_size(), 3);
+
+    let mut at_limit: Vec<SpendingLimitRequest> = Vec::new(&env);
+    for _ in 0..3 {
+        at_limit.push_back(create_valid_request(
+            &env,
+            &Address::generate(&env),
+            50_000_000_000,
+        ));
+    }
+    let result = client.batch_update_spending_limits(&admin, &at_limit);
+    assert_eq!(result.metrics.successful_updates, 3);
+
+    let mut over_limit = at_limit.clone();
+    over_limit.push_back(create_valid_request(
+        &env,
+        &Address::generate(&env),
+        50_000_000_000,
+    ));
+    assert!(client
+        .try_batch_update_spending_limits(&admin, &over_limit)
+        .is_err());
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #7)")]
+fn test_set_max_batch_size_rejects_zero() {
+    let (_env, admin, client) = setup_test_contract();
+
+    client.set_max_batch_size(&admin, &0);
+}
+
+#[test]
+fn test_batch_get_spending_limits_positional() {
+    let (env, admin, client) = setup_test_contract();
+    let with_limit1 = Address::generate(&env);
+    let with_limit2 = Address::generate(&env);
+    let without_limit = Address::generate(&env);
+
+    let mut requests: Vec<SpendingLimitRequest> = Vec::new(&env);
+    requests.push_back(create_valid_request(&env, &with_limit1, 50_000_000_000));
+    requests.push_back(create_valid_request(&env, &with_limit2, 70_000_000_000));
+    client.batch_update_spending_limits(&admin, &requests);
+
+    let mut users: Vec<Address> = Vec::new(&env);
+    users.push_back(with_limit2.clone());
+    users.push_back(without_limit.clone());
+    users.push_back(with_limit1.clone());
+
+    let limits = client.batch_get_spending_limits(&users);
+
+    assert_eq!(limits.len(), 3);
+    assert_eq!(
+        limits.get(0).unwrap().unwrap().monthly_limit,
+        70_000_000_000
+    );
+    assert!(limits.get(1).unwrap().is_none());
+    assert_eq!(limits.get(2).unwrap().unwrap().user, with_limit1);
+}
+
+#[test]
+fn test_simulate_batch_update_matches_real_batch() {
+    let (env, admin, client) = setup_test_contract();
+    let user1 = Address::generate(&env);
+    let user2 = Address::generate(&env);
+    let user3 = Address::generate(&env);
+
+    let mut requests: Vec<SpendingLimitRequest> = Vec::new(&env);
+    requests.push_back(create_valid_request(&env, &user1, 50_000_000_000));
+    requests.push_back(create_valid_request(&env, &user2, 100)); // too low
+    requests.push_back(create_valid_request(&env, &user3, 100_000_000_000));
+
+    let simulated = client.simulate_limit_updates(&requests);
+
+    // Simulation must not persist anything
+    assert!(client.get_spending_limit(&user1).is_none());
+    assert_eq!(client.get_total_limits_updated(), 0);
+    assert_eq!(client.get_total_batches_processed(), 0);
+
+    let actual = client.batch_update_spending_limits(&admin, &requests);
+
+    assert_eq!(simulated.batch_id, actual.batch_id);
+    assert_eq!(simulated.successful, actual.successful);
+    assert_eq!(simulated.failed, actual.failed);
+    assert_eq!(
+        simulated.metrics.total_limits_value,
+        actual.metrics.total_limits_value
+    );
+    assert_eq!(
+        simulated.metrics.avg_limit_amount,
+        actual.metrics.avg_limit_amount
+    );
+
+    for (sim, real) in simulated.results.iter().zip(actual.results.iter()) {
+        match (sim, real) {
+            (LimitUpdateResult::Success(a), LimitUpdateResult::Success(b)) => {
+                assert_eq!(a.user, b.user);
+                assert_eq!(a.monthly_limit, b.monthly_limit);
+            }
+            (LimitUpdateResult::Failure(a, code_a), LimitUpdateResult::Failure(b, code_b)) => {
+                assert_eq!(a, b);
+                assert_eq!(code_a, code_b);
+            }
+            _ => panic!("simulated outcome differs from real batch"),
+        }
+    }
+}
+
+#[test]
+fn test_rolling_window_frees_capacity_as_spends_age_out() {
+    let (env, admin, client) = setup_test_contract();
+    let user = Address::generate(&env);
+
+    let mut request = create_valid_request(&env, &user, 10_000_000);
+    request.window_ledgers = 100;
+    let mut requests: Vec<SpendingLimitRequest> = Vec::new(&env);
+    requests.push_back(request);
+    client.batch_update_spending_limits(&admin, &requests);
+
+    env.ledger().with_mut(|li| li.sequence_number = 1_000);
+    assert!(client.record_spending(&admin, &user, &6_000_000));
+
+    env.ledger().with_mut(|li| li.sequence_number = 1_050);
+    assert!(client.record_spending(&admin, &user, &4_000_000));
+    assert!(!client.record_spending(&admin, &user, &1_000));
+
+    // The spend at ledger 1_000 has left the window; the one at 1_050 still counts
+    env.ledger().with_mut(|li| li.sequence_number = 1_100);
+    assert!(!client.record_spending(&admin, &user, &6_000_001));
+    assert!(client.record_spending(&admin, &user, &6_000_000));
+    assert!(!client.record_spending(&admin, &user, &1_000));
+
+    // Both earlier windows have aged out
+    env.ledger().with_mut(|li| li.sequence_number = 1_200);
+    assert!(client.record_spending(&admin, &user, &4_000_000));
+}
+
+#[test]
+fn test_limit_without_window_keeps_fixed_total() {
+    let (env, admin, client) = setup_test_contract();
+    let user = Address::generate(&env);
+
+    let mut requests: Vec<SpendingLimitRequest> = Vec::new(&env);
+    requests.push_back(create_valid_request(&env, &user, 10_000_000));
+    client.batch_update_spending_limits(&admin, &requests);
+
+    env.ledger().with_mut(|li| li.sequence_number = 1_000);
+    assert!(client.record_spending(&admin, &user, &10_000_000));
+
+    env.ledger().with_mut(|li| li.sequence_number = 100_000);
+    assert!(!client.record_spending(&admin, &user, &1_000));
+}
+
+#[test]
+fn test_total_active_limit_value_tracks_current_limits() {
+    let (env, admin, client) = setup_test_contract();
+    let user1 = Address::generate(&env);
+    let user2 = Address::generate(&env);
+
+    assert_eq!(client.get_total_active_limit_value(), 0);
+
+    let mut requests: Vec<SpendingLimitRequest> = Vec::new(&env);
+    requests.push_back(create_valid_request(&env, &user1, 50_000_000));
+    requests.push_back(create_valid_request(&env, &user2, 30_000_000));
+    requests.push_back(create_valid_request(&env, &user2, 100)); // invalid, ignored
+    client.batch_update_spending_limits(&admin, &requests);
+    assert_eq!(client.get_total_active_limit_value(), 80_000_000);
+
+    // Re-updating replaces the old value rather than adding to it
+    let mut requests: Vec<SpendingLimitRequest> = Vec::new(&env);
+    requests.push_back(create_valid_request(&env, &user1, 20_000_000));
+    client.batch_update_spending_limits(&admin, &requests);
+    assert_eq!(client.get_total_active_limit_value(), 50_000_000);
+
+    // Updating the same user twice in one batch only counts the last value
+    let mut requests: Vec<SpendingLimitRequest> = Vec::new(&env);
+    requests.push_back(create_valid_request(&env, &user2, 40_000_000));
+    requests.push_back(create_valid_request(&env, &user2, 10_000_000));
+    client.batch_update_spending_limits(&admin, &requests);
+    assert_eq!(client.get_total_active_limit_value(), 30_000_000);
+
+    let sum = client.get_spending_limit(&user1).unwrap().monthly_limit
+        + client.get_spending_limit(&user2).unwrap().monthly_limit;
+    assert_eq!(client.get_total_active_limit_value(), sum);
+}
+
+#[test]
+fn test_remove_spending_limit_decrements_total() {
+    let (env, admin, client) = setup_test_contract();
+    let user1 = Address::generate(&env);
+    let user2 = Address::generate(&env);
+
+    let mut requests: Vec<SpendingLimitRequest> = Vec::new(&env);
+    requests.push_back(create_valid_request(&env, &user1, 50_000_000));
+    requests.push_back(create_valid_request(&env, &user2, 30_000_000));
+    client.batch_update_spending_limits(&admin, &requests);
+
+    assert!(client.remove_spending_limit(&admin, &user1));
+    assert!(client.get_spending_limit(&user1).is_none());
+    assert_eq!(client.get_total_active_limit_value(), 30_000_000);
+
+    // Removing again is a no-op
+    assert!(!client.remove_spending_limit(&admin, &user1));
+    assert_eq!(client.get_total_active_limit_value(), 30_000_000);
+}
+
+#[test]
+fn test_seed_total_active_limit_value_counts_existing_limits() {
+    let (env, admin, client) = setup_test_contract();
+    let user = Address::generate(&env);
+
+    // Limits stored before tracking are supplied once by the admin
+    client.seed_total_active_limit_value(&admin, &70_000_000);
+    assert_eq!(client.get_total_active_limit_value(), 70_000_000);
+
+    let mut requests: Vec<SpendingLimitRequest> = Vec::new(&env);
+    requests.push_back(create_valid_request(&env, &user, 30_000_000));
+    client.batch_update_spending_limits(&admin, &requests);
+    assert_eq!(client.get_total_active_limit_value(), 100_000_000);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #8)")]
+fn test_seed_total_active_limit_value_only_once() {
+    let (_env, admin, client) = setup_test_contract();
+
+    client.seed_total_active_limit_value(&admin, &70_000_000);
+    client.seed_total_active_limit_value(&admin, &70_000_000);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #9)")]
+fn test_seed_total_active_limit_value_rejects_negative() {
+    let (_env, admin, client) = setup_test_contract();
+
+    client.seed_total_active_limit_value(&admin, &-1);
+}
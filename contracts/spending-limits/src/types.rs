@@ -1,16 +1,28 @@
 //! Data types and events for batch spending limit operations.
 
-use soroban_sdk::{contracttype, symbol_short, Address, Env, Vec};
+use soroban_sdk::{contracttype, symbol_short, Address, Env, Symbol, Vec};
 
 /// Maximum number of user-limit pairs in a single batch for optimization.
 pub const MAX_BATCH_SIZE: u32 = 100;
 
+/// Upper bound on the admin-configurable batch size.
+pub const MAX_CONFIGURABLE_BATCH_SIZE: u32 = 1000;
+
 /// Minimum monthly spending limit (0.1 XLM in stroops)
 pub const MIN_SPENDING_LIMIT: i128 = 1_000_000;
 
 /// Maximum monthly spending limit (10 million XLM in stroops)
 pub const MAX_SPENDING_LIMIT: i128 = 100_000_000_000_000_000;
 
+/// Maximum number of recent spends kept per user for rolling-window limits.
+pub const MAX_SPEND_LOG_ENTRIES: u32 = 50;
+
+/// Remaining ledgers below which stored entries have their TTL extended (~1 day).
+pub const TTL_THRESHOLD: u32 = 17_280;
+
+/// Ledgers an extended entry stays live for (~30 days).
+pub const TTL_EXTEND_TO: u32 = 518_400;
+
 /// Represents a spending limit update request for a user.
 #[derive(Clone, Debug)]
 #[contracttype]
@@ -21,6 +33,8 @@ pub struct SpendingLimitRequest {
     pub monthly_limit: i128,
     /// Optional category-specific limit (e.g., "food", "entertainment")
     pub category: Option<soroban_sdk::Symbol>,
+    /// Trailing window in ledgers that spends count against (0 for no window)
+    pub window_ledgers: u64,
 }
 
 /// Represents a user's spending limit configuration.
@@ -39,6 +53,8 @@ pub struct SpendingLimit {
     pub updated_at: u64,
     /// Whether the limit is active
     pub is_active: bool,
+    /// Trailing window in ledgers that spends count against (0 for no window)
+    pub window_ledgers: u64,
 }
 
 /// Result of processing a single limit update.
@@ -99,9 +115,18 @@ pub enum DataKey {
     TotalLimitsUpdated,
     /// Total batches processed lifetime
     TotalBatchesProcessed,
+    /// Runtime batch size ceiling (defaults to `MAX_BATCH_SIZE`)
+    MaxBatchSize,
+    /// Recent (ledger, amount) spends by user for rolling-window limits
+    SpendLog(Address),
+    /// Sum of the monthly limits currently stored and active
+    TotalActiveLimitValue,
+    /// Whether the active limit total has been seeded with pre-existing limits
+    TotalActiveLimitSeeded,
 }
 
 /// Error codes for spending limit validation and updates.
+#[allow(non_snake_case)]
 pub mod ErrorCode {
     /// Invalid limit amount (too low, too high, or negative)
     pub const INVALID_LIMIT: u32 = 0;
@@ -111,6 +136,14 @@ pub mod ErrorCode {
     pub const INVALID_CATEGORY: u32 = 2;
     /// Limit already exists and cannot be overwritten
     pub const LIMIT_ALREADY_EXISTS: u32 = 3;
+    /// User has no spending limit configured
+    pub const NO_LIMIT: u32 = 4;
+    /// User's spending limit is inactive
+    pub const LIMIT_INACTIVE: u32 = 5;
+    /// Spend amount is zero or negative
+    pub const INVALID_SPEND_AMOUNT: u32 = 6;
+    /// Spend would push current spending above the monthly limit
+    pub const LIMIT_EXCEEDED: u32 = 7;
 }
 
 /// Events emitted by the spending limits contract.
@@ -130,6 +163,12 @@ impl LimitEvents {
             .publish(topics, (limit.user.clone(), limit.monthly_limit));
     }
 
+    /// Event emitted when a user's limit is removed.
+    pub fn limit_removed(env: &Env, user: &Address, monthly_limit: i128) {
+        let topics = (symbol_short!("limit"), symbol_short!("removed"));
+        env.events().publish(topics, (user.clone(), monthly_limit));
+    }
+
     /// Event emitted when limit update fails.
     pub fn limit_update_failed(env: &Env, batch_id: u64, user: &Address, error_code: u32) {
         let topics = (symbol_short!("limit"), symbol_short!("failed"), batch_id);
@@ -149,6 +188,35 @@ impl LimitEvents {
             .publish(topics, (successful, failed, total_limits));
     }
 
+    /// Event emitted when a spend is recorded against a limit.
+    pub fn spending_recorded(env: &Env, user: &Address, amount: i128, current_spending: i128) {
+        let topics = (symbol_short!("spend"), symbol_short!("recorded"));
+        env.events()
+            .publish(topics, (user.clone(), amount, current_spending));
+    }
+
+    /// Event emitted when a spend is rejected.
+    pub fn spending_rejected(env: &Env, user: &Address, amount: i128, error_code: u32) {
+        let topics = (symbol_short!("spend"), symbol_short!("rejected"));
+        env.events()
+            .publish(topics, (user.clone(), amount, error_code));
+    }
+
+    /// Event emitted when a cross-currency spend is converted into the limit's currency.
+    pub fn spending_converted(
+        env: &Env,
+        user: &Address,
+        spend_currency: &Symbol,
+        amount: i128,
+        converted: i128,
+    ) {
+        let topics = (symbol_short!("spend"), symbol_short!("converted"));
+        env.events().publish(
+            topics,
+            (user.clone(), spend_currency.clone(), amount, converted),
+        );
+    }
+
     /// Event emitted for high-value limits (>= 1,000,000 XLM).
     pub fn high_value_limit(env: &Env, batch_id: u64, user: &Address, amount: i128) {
         let topics = (symbol_short!("limit"), symbol_short!("highval"), batch_id);
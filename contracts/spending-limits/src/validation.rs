@@ -1,8 +1,10 @@
 //! Validation logic for spending limit update requests.
 
-use soroban_sdk::Address;
+use soroban_sdk::{Address, Vec};
 
-use crate::types::{ErrorCode, SpendingLimitRequest, MAX_SPENDING_LIMIT, MIN_SPENDING_LIMIT};
+use crate::types::{
+    ErrorCode, SpendingLimit, SpendingLimitRequest, MAX_SPENDING_LIMIT, MIN_SPENDING_LIMIT,
+};
 
 /// Validates a spending limit update request.
 ///
@@ -45,7 +47,65 @@ fn is_valid_address(_address: &Address) -> bool {
 /// # Returns
 /// * `true` if limit is >= MIN_SPENDING_LIMIT and <= MAX_SPENDING_LIMIT
 pub fn is_valid_limit(limit: i128) -> bool {
-    limit >= MIN_SPENDING_LIMIT && limit <= MAX_SPENDING_LIMIT
+    (MIN_SPENDING_LIMIT..=MAX_SPENDING_LIMIT).contains(&limit)
+}
+
+/// Validates a spend against a user's spending limit.
+///
+/// # Arguments
+/// * `limit` - The user's stored limit, if any
+/// * `current_spending` - Spending already recorded for the period
+/// * `amount` - The spend to check
+///
+/// # Returns
+/// * `Ok(new_spending)` with the spending total after the spend
+/// * `Err(error_code)` if the spend would be rejected
+pub fn validate_spend(
+    limit: Option<&SpendingLimit>,
+    current_spending: i128,
+    amount: i128,
+) -> Result<i128, u32> {
+    if amount <= 0 {
+        return Err(ErrorCode::INVALID_SPEND_AMOUNT);
+    }
+
+    let limit = limit.ok_or(ErrorCode::NO_LIMIT)?;
+    if !limit.is_active {
+        return Err(ErrorCode::LIMIT_INACTIVE);
+    }
+
+    let new_spending = current_spending
+        .checked_add(amount)
+        .ok_or(ErrorCode::LIMIT_EXCEEDED)?;
+    if new_spending > limit.monthly_limit {
+        return Err(ErrorCode::LIMIT_EXCEEDED);
+    }
+
+    Ok(new_spending)
+}
+
+/// Sums the spends that fall within a trailing ledger window.
+///
+/// # Arguments
+/// * `log` - Recorded (ledger, amount) spends, oldest first
+/// * `current_ledger` - The ledger the window ends at
+/// * `window_ledgers` - Window length in ledgers
+///
+/// # Returns
+/// * Total amount spent in the last `window_ledgers` ledgers
+pub fn spending_in_window(
+    log: &Vec<(u64, i128)>,
+    current_ledger: u64,
+    window_ledgers: u64,
+) -> i128 {
+    let window_start = current_ledger.saturating_sub(window_ledgers);
+    let mut total: i128 = 0;
+    for (ledger, amount) in log.iter() {
+        if ledger > window_start {
+            total = total.saturating_add(amount);
+        }
+    }
+    total
 }
 
 #[cfg(test)]
@@ -58,6 +118,7 @@ mod tests {
             user: Address::generate(env),
             monthly_limit: 100_000_000_000, // 10,000 XLM
             category: Some(symbol_short!("general")),
+            window_ledgers: 0,
         }
     }
 
@@ -111,6 +172,38 @@ mod tests {
         assert!(!is_valid_limit(-1000));
     }
 
+    #[test]
+    fn test_validate_spend() {
+        let env = Env::default();
+        let limit = SpendingLimit {
+            user: Address::generate(&env),
+            monthly_limit: 1_000,
+            current_spending: 0,
+            category: None,
+            updated_at: 0,
+            is_active: true,
+            window_ledgers: 0,
+        };
+
+        assert_eq!(validate_spend(Some(&limit), 400, 600), Ok(1_000));
+        assert_eq!(
+            validate_spend(Some(&limit), 400, 601),
+            Err(ErrorCode::LIMIT_EXCEEDED)
+        );
+        assert_eq!(
+            validate_spend(Some(&limit), 0, 0),
+            Err(ErrorCode::INVALID_SPEND_AMOUNT)
+        );
+        assert_eq!(validate_spend(None, 0, 10), Err(ErrorCode::NO_LIMIT));
+
+        let mut inactive = limit.clone();
+        inactive.is_active = false;
+        assert_eq!(
+            validate_spend(Some(&inactive), 0, 10),
+            Err(ErrorCode::LIMIT_INACTIVE)
+        );
+    }
+
     #[test]
     fn test_valid_request_without_category() {
         let env = Env::default();
@@ -118,4 +211,15 @@ mod tests {
         request.category = None;
         assert!(validate_limit_request(&request).is_ok());
     }
+
+    #[test]
+    fn test_spending_in_window() {
+        let env = Env::default();
+        let log = Vec::from_array(&env, [(100u64, 300i128), (150, 200), (190, 100)]);
+
+        assert_eq!(spending_in_window(&log, 190, 100), 600);
+        // The spend at ledger 100 falls out once the window starts at 100
+        assert_eq!(spending_in_window(&log, 200, 100), 300);
+        assert_eq!(spending_in_window(&log, 300, 100), 0);
+    }
 }
@@ -1,30 +1,35 @@
 use soroban_sdk::{Address, Env, Map, Symbol, Vec};
 
 use crate::types::{
-    AuditLog, BatchMetrics, CategoryMetrics, Transaction, RefundRequest, RefundResult, 
-    RefundStatus, RefundBatchMetrics, BundleResult, BundledTransaction, ValidationResult,
-    MAX_BATCH_SIZE,
+    AuditLog, BatchMetrics, BundleResult, BundledTransaction, CategoryMetrics, RefundBatchMetrics,
+    RefundRequest, RefundResult, RefundStatus, Transaction, ValidationResult, MAX_BATCH_SIZE,
 };
 
 /// Calculates the processing fee for a transaction amount.
 ///
-/// Current fee model: 0.1% (10 basis points)
-pub fn calculate_fee(amount: i128) -> i128 {
+/// The fee is `amount * fee_bps / 10000`, rounded down.
+pub fn calculate_fee(amount: i128, fee_bps: u32) -> i128 {
     if amount <= 0 {
         return 0;
     }
-    // 0.1% = amount * 10 / 10000 = amount / 1000
-    amount / 1000
+    amount
+        .checked_mul(fee_bps as i128)
+        .map(|v| v / 10_000)
+        .unwrap_or(i128::MAX)
 }
 
 /// Computes aggregated metrics for a batch of transactions.
 ///
 /// Optimized to perform a single pass over the transaction data,
 /// computing all metrics in O(n) time complexity.
+///
+/// The top category is the one with the highest volume; ties resolve to the
+/// category that sorts first.
 pub fn compute_batch_metrics(
     env: &Env,
     transactions: &Vec<Transaction>,
     processed_at: u64,
+    fee_bps: u32,
 ) -> BatchMetrics {
     let tx_count = transactions.len();
 
@@ -38,6 +43,8 @@ pub fn compute_batch_metrics(
             unique_senders: 0,
             unique_recipients: 0,
             total_fees: 0,
+            top_category: None,
+            top_category_volume: 0,
             processed_at,
         };
     }
@@ -51,13 +58,14 @@ pub fn compute_batch_metrics(
     // Use maps to track unique addresses (more efficient than vectors for lookups)
     let mut senders: Map<Address, bool> = Map::new(env);
     let mut recipients: Map<Address, bool> = Map::new(env);
+    let mut category_volumes: Map<Symbol, i128> = Map::new(env);
 
     for tx in transactions.iter() {
         // Accumulate volume
         total_volume = total_volume.checked_add(tx.amount).unwrap_or(i128::MAX);
 
         // Calculate and accumulate fees
-        let fee = calculate_fee(tx.amount);
+        let fee = calculate_fee(tx.amount, fee_bps);
         total_fees = total_fees.checked_add(fee).unwrap_or(i128::MAX);
 
         // Track min/max
@@ -75,11 +83,28 @@ pub fn compute_batch_metrics(
         if !recipients.contains_key(tx.to.clone()) {
             recipients.set(tx.to.clone(), true);
         }
+
+        // Aggregate volume per category
+        let category_volume = category_volumes.get(tx.category.clone()).unwrap_or(0);
+        category_volumes.set(
+            tx.category.clone(),
+            category_volume.checked_add(tx.amount).unwrap_or(i128::MAX),
+        );
     }
 
     // Calculate average (avoiding division by zero)
     let avg_amount = total_volume / (tx_count as i128);
 
+    // Pick the dominant category (map iteration is key-ordered, so ties are deterministic)
+    let mut top_category: Option<Symbol> = None;
+    let mut top_category_volume: i128 = 0;
+    for (category, volume) in category_volumes.iter() {
+        if volume > top_category_volume {
+            top_category = Some(category);
+            top_category_volume = volume;
+        }
+    }
+
     BatchMetrics {
         tx_count,
         total_volume,
@@ -89,6 +114,8 @@ pub fn compute_batch_metrics(
         unique_senders: senders.len(),
         unique_recipients: recipients.len(),
         total_fees,
+        top_category,
+        top_category_volume,
         processed_at,
     }
 }
@@ -100,6 +127,7 @@ pub fn compute_category_metrics(
     env: &Env,
     transactions: &Vec<Transaction>,
     total_volume: i128,
+    fee_bps: u32,
 ) -> Vec<CategoryMetrics> {
     // Map stores (tx_count, total_volume, total_fees)
     let mut category_map: Map<Symbol, (u32, i128, i128)> = Map::new(env);
@@ -107,7 +135,7 @@ pub fn compute_category_metrics(
     // Single pass to aggregate by category
     for tx in transactions.iter() {
         let current = category_map.get(tx.category.clone()).unwrap_or((0, 0, 0));
-        let fee = calculate_fee(tx.amount);
+        let fee = calculate_fee(tx.amount, fee_bps);
         category_map.set(
             tx.category.clone(),
             (
@@ -160,6 +188,228 @@ pub fn find_high_value_transactions(
     high_value
 }
 
+/// Identifies transactions that deviate strongly from the batch average.
+///
+/// A transaction is flagged when its amount exceeds
+/// `avg_amount * deviation_multiplier_bps / 10000` (e.g. 100000 = 10x the mean).
+/// Returns a vector of (tx_id, amount) tuples for the flagged transactions.
+pub fn find_anomalous_transactions(
+    env: &Env,
+    transactions: &Vec<Transaction>,
+    deviation_multiplier_bps: u32,
+) -> Vec<(u64, i128)> {
+    let mut anomalies: Vec<(u64, i128)> = Vec::new(env);
+
+    let count = transactions.len();
+    if count == 0 {
+        return anomalies;
+    }
+
+    let mut total_volume: i128 = 0;
+    for tx in transactions.iter() {
+        total_volume = total_volume.checked_add(tx.amount).unwrap_or(i128::MAX);
+    }
+    let avg_amount = total_volume / (count as i128);
+    let limit = avg_amount
+        .checked_mul(deviation_multiplier_bps as i128)
+        .unwrap_or(i128::MAX)
+        / 10000;
+
+    for tx in transactions.iter() {
+        if tx.amount > limit {
+            anomalies.push_back((tx.tx_id, tx.amount));
+        }
+    }
+
+    anomalies
+}
+
+/// Returns the transactions whose timestamp falls within `[from_ledger, to_ledger]`.
+///
+/// Order of the input is preserved.
+pub fn filter_transactions_by_range(
+    env: &Env,
+    transactions: &Vec<Transaction>,
+    from_ledger: u64,
+    to_ledger: u64,
+) -> Vec<Transaction> {
+    let mut in_range: Vec<Transaction> = Vec::new(env);
+
+    for tx in transactions.iter() {
+        if tx.timestamp >= from_ledger && tx.timestamp <= to_ledger {
+            in_range.push_back(tx);
+        }
+    }
+
+    in_range
+}
+
+/// Returns the transactions belonging to `category`, preserving input order.
+pub fn filter_transactions_by_category(
+    env: &Env,
+    transactions: &Vec<Transaction>,
+    category: &Symbol,
+) -> Vec<Transaction> {
+    let mut matching: Vec<Transaction> = Vec::new(env);
+
+    for tx in transactions.iter() {
+        if tx.category == *category {
+            matching.push_back(tx);
+        }
+    }
+
+    matching
+}
+
+/// Returns the transaction timestamps in ascending order.
+///
+/// Uses insertion into a sorted vector; batches are capped at `MAX_BATCH_SIZE`
+/// so the quadratic worst case stays small.
+pub fn sorted_timestamps(env: &Env, transactions: &Vec<Transaction>) -> Vec<u64> {
+    let mut sorted: Vec<u64> = Vec::new(env);
+
+    for tx in transactions.iter() {
+        let mut index = sorted.len();
+        while index > 0 && sorted.get_unchecked(index - 1) > tx.timestamp {
+            index -= 1;
+        }
+        sorted.insert(index, tx.timestamp);
+    }
+
+    sorted
+}
+
+/// Returns the transaction amounts in ascending order.
+pub fn sorted_amounts(env: &Env, transactions: &Vec<Transaction>) -> Vec<i128> {
+    let mut sorted: Vec<i128> = Vec::new(env);
+
+    for tx in transactions.iter() {
+        let mut index = sorted.len();
+        while index > 0 && sorted.get_unchecked(index - 1) > tx.amount {
+            index -= 1;
+        }
+        sorted.insert(index, tx.amount);
+    }
+
+    sorted
+}
+
+/// Computes a percentile of transaction amounts using the nearest-rank method.
+///
+/// `percentile_bps` is in basis points (9500 = p95) and is clamped to
+/// `[0, 10000]`. Returns 0 for an empty batch.
+pub fn compute_percentile(transactions: &Vec<Transaction>, percentile_bps: u32) -> i128 {
+    let count = transactions.len();
+    if count == 0 {
+        return 0;
+    }
+
+    let percentile_bps = percentile_bps.min(10000) as u64;
+    let sorted = sorted_amounts(transactions.env(), transactions);
+
+    // Nearest rank: ceil(p * n / 10000), with rank 1 as the floor
+    let rank = (percentile_bps * count as u64).div_ceil(10000);
+    let rank = rank.max(1) as u32;
+
+    sorted.get_unchecked(rank - 1)
+}
+
+/// Computes the Herfindahl-Hirschman index of sender volume in basis points.
+///
+/// Each sender's share of total volume is expressed in bps and the index is
+/// `sum(share_bps^2) / 10000`, clamped to `[0, 10000]`. A single sender yields
+/// 10000; volume spread evenly across `n` senders yields roughly `10000 / n`.
+/// Returns 0 for an empty batch or one with no positive volume.
+pub fn compute_sender_concentration(env: &Env, transactions: &Vec<Transaction>) -> u32 {
+    let mut sender_volumes: Map<Address, i128> = Map::new(env);
+    let mut total_volume: i128 = 0;
+
+    for tx in transactions.iter() {
+        let volume = sender_volumes.get(tx.from.clone()).unwrap_or(0);
+        sender_volumes.set(
+            tx.from.clone(),
+            volume.checked_add(tx.amount).unwrap_or(i128::MAX),
+        );
+        total_volume = total_volume.checked_add(tx.amount).unwrap_or(i128::MAX);
+    }
+
+    if total_volume <= 0 {
+        return 0;
+    }
+
+    let mut sum_of_squares: i128 = 0;
+    for (_, volume) in sender_volumes.iter() {
+        // Divide first when scaling to basis points would overflow
+        let share_bps = match volume.checked_mul(10000) {
+            Some(scaled) => scaled / total_volume,
+            None => volume / (total_volume / 10000).max(1),
+        };
+        let square = share_bps.checked_mul(share_bps).unwrap_or(i128::MAX);
+        sum_of_squares = sum_of_squares.checked_add(square).unwrap_or(i128::MAX);
+    }
+
+    (sum_of_squares / 10000).clamp(0, 10000) as u32
+}
+
+/// Counts transactions per sender.
+///
+/// Senders are listed in the order they first appear in the batch, so the
+/// result is deterministic for a given input.
+pub fn compute_sender_activity(env: &Env, transactions: &Vec<Transaction>) -> Vec<(Address, u32)> {
+    let mut counts: Map<Address, u32> = Map::new(env);
+    let mut senders: Vec<Address> = Vec::new(env);
+
+    for tx in transactions.iter() {
+        let count = match counts.get(tx.from.clone()) {
+            Some(count) => count,
+            None => {
+                senders.push_back(tx.from.clone());
+                0
+            }
+        };
+        counts.set(tx.from.clone(), count + 1);
+    }
+
+    let mut activity: Vec<(Address, u32)> = Vec::new(env);
+    for sender in senders.iter() {
+        let count = counts.get(sender.clone()).unwrap_or(0);
+        activity.push_back((sender, count));
+    }
+    activity
+}
+
+/// Detects whether transactions cluster in time.
+///
+/// Returns true if any span of `window` consecutive ledgers contains more than
+/// `threshold` transactions. Timestamps are sorted once and scanned with a
+/// sliding window.
+pub fn detect_time_bursts(
+    env: &Env,
+    transactions: &Vec<Transaction>,
+    window: u64,
+    threshold: u32,
+) -> bool {
+    if window == 0 {
+        return false;
+    }
+
+    let timestamps = sorted_timestamps(env, transactions);
+    let mut start: u32 = 0;
+
+    for end in 0..timestamps.len() {
+        let end_ts = timestamps.get_unchecked(end);
+        // Shrink until the window spans fewer than `window` ledgers
+        while end_ts - timestamps.get_unchecked(start) >= window {
+            start += 1;
+        }
+        if end - start + 1 > threshold {
+            return true;
+        }
+    }
+
+    false
+}
+
 /// Validates a batch of transactions before processing.
 ///
 /// Returns Ok(()) if valid, or an error message if invalid.
@@ -186,7 +436,7 @@ pub fn validate_batch(transactions: &Vec<Transaction>) -> Result<(), &'static st
 
 /// Validates a batch of audit logs.
 pub fn validate_audit_logs(logs: &Vec<AuditLog>) -> Result<(), &'static str> {
-    if logs.len() == 0 {
+    if logs.is_empty() {
         return Err("Audit logs batch cannot be empty");
     }
 
@@ -307,6 +557,47 @@ pub fn process_refund_batch(
     results
 }
 
+/// Enforces a cap on the total amount refunded in one batch.
+///
+/// Successful results are kept in order until the next one would push the
+/// cumulative refund (starting from `already_refunded`) past `cap`; from then
+/// on every remaining successful result is turned into a `CapExceeded`
+/// failure and un-marked in `refunded_txs`. A cap of zero leaves the results
+/// unchanged.
+pub fn apply_refund_cap(
+    env: &Env,
+    refund_results: &Vec<RefundResult>,
+    refunded_txs: &mut Map<u64, bool>,
+    cap: i128,
+    already_refunded: i128,
+) -> Vec<RefundResult> {
+    if cap <= 0 {
+        return refund_results.clone();
+    }
+
+    let mut capped: Vec<RefundResult> = Vec::new(env);
+    let mut cumulative: i128 = already_refunded;
+    let mut cap_reached = false;
+
+    for mut result in refund_results.iter() {
+        if result.success {
+            if !cap_reached && cumulative + result.amount_refunded <= cap {
+                cumulative += result.amount_refunded;
+            } else {
+                cap_reached = true;
+                refunded_txs.remove(result.tx_id);
+                result.success = false;
+                result.status = RefundStatus::CapExceeded;
+                result.amount_refunded = 0;
+                result.error_message = Some(Symbol::new(env, "CapExceeded"));
+            }
+        }
+        capped.push_back(result);
+    }
+
+    capped
+}
+
 /// Computes aggregated metrics from a batch of refund results.
 pub fn compute_refund_metrics(
     _env: &Env,
@@ -348,7 +639,7 @@ pub fn compute_refund_metrics(
     };
     
     RefundBatchMetrics {
-        request_count: request_count as u32,
+        request_count,
         successful_refunds,
         failed_refunds,
         total_refunded_amount,
@@ -424,14 +715,13 @@ pub fn create_bundle_result(
     validation_results: &Vec<ValidationResult>,
     created_at: u64,
 ) -> BundleResult {
-    let total_count = bundled_transactions.len() as u32;
+    let total_count = bundled_transactions.len();
     let mut valid_count: u32 = 0;
     let mut invalid_count: u32 = 0;
     let mut total_volume: i128 = 0;
 
     // Count valid/invalid and compute total volume of valid transactions
-    let mut index: u32 = 0;
-    for result in validation_results.iter() {
+    for (index, result) in (0_u32..).zip(validation_results.iter()) {
         if result.is_valid {
             valid_count += 1;
             if let Some(bundled_tx) = bundled_transactions.get(index) {
@@ -442,7 +732,6 @@ pub fn create_bundle_result(
         } else {
             invalid_count += 1;
         }
-        index += 1;
     }
 
     let can_bundle = valid_count > 0 && invalid_count == 0;
@@ -461,7 +750,7 @@ pub fn create_bundle_result(
 
 /// Validates a batch of refund requests.
 pub fn validate_refund_batch(env: &Env, refund_requests: &Vec<RefundRequest>) -> Result<(), &'static str> {
-    let count = refund_requests.len() as u32;
+    let count = refund_requests.len();
     
     if count == 0 {
         return Err("Refund batch cannot be empty");
@@ -489,6 +778,7 @@ pub fn validate_refund_batch(env: &Env, refund_requests: &Vec<RefundRequest>) ->
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::types::DEFAULT_FEE_BPS;
     use soroban_sdk::{testutils::Address as _, Env};
 
     fn create_test_transaction(env: &Env, tx_id: u64, amount: i128, category: &str) -> Transaction {
@@ -508,7 +798,7 @@ mod tests {
         let mut transactions: Vec<Transaction> = Vec::new(&env);
         transactions.push_back(create_test_transaction(&env, 1, 1000, "transfer"));
 
-        let metrics = compute_batch_metrics(&env, &transactions, 100);
+        let metrics = compute_batch_metrics(&env, &transactions, 100, DEFAULT_FEE_BPS);
 
         assert_eq!(metrics.tx_count, 1);
         assert_eq!(metrics.total_volume, 1000);
@@ -527,7 +817,7 @@ mod tests {
         transactions.push_back(create_test_transaction(&env, 2, 200, "transfer"));
         transactions.push_back(create_test_transaction(&env, 3, 300, "budget"));
 
-        let metrics = compute_batch_metrics(&env, &transactions, 100);
+        let metrics = compute_batch_metrics(&env, &transactions, 100, DEFAULT_FEE_BPS);
 
         assert_eq!(metrics.tx_count, 3);
         assert_eq!(metrics.total_volume, 600);
@@ -541,10 +831,54 @@ mod tests {
         let env = Env::default();
         let transactions: Vec<Transaction> = Vec::new(&env);
 
-        let metrics = compute_batch_metrics(&env, &transactions, 100);
+        let metrics = compute_batch_metrics(&env, &transactions, 100, DEFAULT_FEE_BPS);
 
         assert_eq!(metrics.tx_count, 0);
         assert_eq!(metrics.total_volume, 0);
+        assert_eq!(metrics.top_category, None);
+        assert_eq!(metrics.top_category_volume, 0);
+    }
+
+    #[test]
+    fn test_compute_batch_metrics_top_category() {
+        let env = Env::default();
+        let mut transactions: Vec<Transaction> = Vec::new(&env);
+        transactions.push_back(create_test_transaction(&env, 1, 300, "transfer"));
+        transactions.push_back(create_test_transaction(&env, 2, 500, "budget"));
+        transactions.push_back(create_test_transaction(&env, 3, 400, "transfer"));
+        transactions.push_back(create_test_transaction(&env, 4, 100, "savings"));
+
+        let metrics = compute_batch_metrics(&env, &transactions, 100, DEFAULT_FEE_BPS);
+
+        assert_eq!(metrics.top_category, Some(Symbol::new(&env, "transfer")));
+        assert_eq!(metrics.top_category_volume, 700);
+    }
+
+    #[test]
+    fn test_compute_batch_metrics_category_named_none() {
+        let env = Env::default();
+        let mut transactions: Vec<Transaction> = Vec::new(&env);
+        transactions.push_back(create_test_transaction(&env, 1, 300, "none"));
+
+        let metrics = compute_batch_metrics(&env, &transactions, 100, DEFAULT_FEE_BPS);
+
+        // A real category called "none" is distinct from an empty batch
+        assert_eq!(metrics.top_category, Some(Symbol::new(&env, "none")));
+        assert_eq!(metrics.top_category_volume, 300);
+    }
+
+    #[test]
+    fn test_compute_batch_metrics_top_category_tie() {
+        let env = Env::default();
+        let mut transactions: Vec<Transaction> = Vec::new(&env);
+        transactions.push_back(create_test_transaction(&env, 1, 500, "transfer"));
+        transactions.push_back(create_test_transaction(&env, 2, 500, "budget"));
+
+        let metrics = compute_batch_metrics(&env, &transactions, 100, DEFAULT_FEE_BPS);
+
+        // Equal volumes resolve to the category that sorts first
+        assert_eq!(metrics.top_category, Some(Symbol::new(&env, "budget")));
+        assert_eq!(metrics.top_category_volume, 500);
     }
 
     #[test]
@@ -555,7 +889,7 @@ mod tests {
         transactions.push_back(create_test_transaction(&env, 2, 300, "transfer"));
         transactions.push_back(create_test_transaction(&env, 3, 200, "budget"));
 
-        let category_metrics = compute_category_metrics(&env, &transactions, 1000);
+        let category_metrics = compute_category_metrics(&env, &transactions, 1000, DEFAULT_FEE_BPS);
 
         assert_eq!(category_metrics.len(), 2);
     }
@@ -575,6 +909,239 @@ mod tests {
         assert_eq!(high_value.get(1).unwrap(), (3, 10000));
     }
 
+    #[test]
+    fn test_filter_transactions_by_range() {
+        let env = Env::default();
+        let mut transactions: Vec<Transaction> = Vec::new(&env);
+        for (tx_id, timestamp) in [(1u64, 10u64), (2, 20), (3, 30), (4, 40)] {
+            let mut tx = create_test_transaction(&env, tx_id, 100, "transfer");
+            tx.timestamp = timestamp;
+            transactions.push_back(tx);
+        }
+
+        let in_range = filter_transactions_by_range(&env, &transactions, 20, 30);
+
+        assert_eq!(in_range.len(), 2);
+        assert_eq!(in_range.get(0).unwrap().tx_id, 2);
+        assert_eq!(in_range.get(1).unwrap().tx_id, 3);
+    }
+
+    #[test]
+    fn test_filter_transactions_by_category() {
+        let env = Env::default();
+        let mut transactions: Vec<Transaction> = Vec::new(&env);
+        transactions.push_back(create_test_transaction(&env, 1, 100, "transfer"));
+        transactions.push_back(create_test_transaction(&env, 2, 200, "budget"));
+        transactions.push_back(create_test_transaction(&env, 3, 300, "transfer"));
+
+        let matching =
+            filter_transactions_by_category(&env, &transactions, &Symbol::new(&env, "transfer"));
+
+        assert_eq!(matching.len(), 2);
+        assert_eq!(matching.get(0).unwrap().tx_id, 1);
+        assert_eq!(matching.get(1).unwrap().tx_id, 3);
+    }
+
+    fn create_timed_transactions(env: &Env, timestamps: &[u64]) -> Vec<Transaction> {
+        let mut transactions: Vec<Transaction> = Vec::new(env);
+        for (i, timestamp) in timestamps.iter().enumerate() {
+            let mut tx = create_test_transaction(env, i as u64 + 1, 100, "transfer");
+            tx.timestamp = *timestamp;
+            transactions.push_back(tx);
+        }
+        transactions
+    }
+
+    #[test]
+    fn test_sorted_timestamps() {
+        let env = Env::default();
+        let transactions = create_timed_transactions(&env, &[30, 10, 20, 10]);
+
+        let sorted = sorted_timestamps(&env, &transactions);
+
+        assert_eq!(sorted, Vec::from_array(&env, [10u64, 10, 20, 30]));
+    }
+
+    #[test]
+    fn test_detect_time_bursts_clustered() {
+        let env = Env::default();
+        // Four transactions within ledgers 100..=103, out of order
+        let transactions = create_timed_transactions(&env, &[500, 102, 100, 900, 103, 101]);
+
+        assert!(detect_time_bursts(&env, &transactions, 5, 3));
+        assert!(!detect_time_bursts(&env, &transactions, 5, 4));
+    }
+
+    #[test]
+    fn test_detect_time_bursts_evenly_spread() {
+        let env = Env::default();
+        let transactions = create_timed_transactions(&env, &[100, 200, 300, 400, 500]);
+
+        assert!(!detect_time_bursts(&env, &transactions, 50, 1));
+        assert!(!detect_time_bursts(&env, &transactions, 0, 0));
+    }
+
+    #[test]
+    fn test_compute_percentile() {
+        let env = Env::default();
+        let mut transactions: Vec<Transaction> = Vec::new(&env);
+        for (tx_id, amount) in [(1u64, 500i128), (2, 100), (3, 900), (4, 300), (5, 700)] {
+            transactions.push_back(create_test_transaction(&env, tx_id, amount, "transfer"));
+        }
+
+        // p50 is the median, p100 the max, p0 the min
+        assert_eq!(compute_percentile(&transactions, 5000), 500);
+        assert_eq!(compute_percentile(&transactions, 10000), 900);
+        assert_eq!(compute_percentile(&transactions, 0), 100);
+        assert_eq!(compute_percentile(&transactions, 9500), 900);
+        assert_eq!(compute_percentile(&transactions, 2000), 100);
+        // Out-of-range input is clamped to p100
+        assert_eq!(compute_percentile(&transactions, 20000), 900);
+    }
+
+    #[test]
+    fn test_compute_sender_concentration_single_sender() {
+        let env = Env::default();
+        let sender = Address::generate(&env);
+        let mut transactions: Vec<Transaction> = Vec::new(&env);
+        for tx_id in 1..=3u64 {
+            let mut tx = create_test_transaction(&env, tx_id, 1000 * tx_id as i128, "transfer");
+            tx.from = sender.clone();
+            transactions.push_back(tx);
+        }
+
+        assert_eq!(compute_sender_concentration(&env, &transactions), 10000);
+    }
+
+    #[test]
+    fn test_compute_sender_concentration_even_split() {
+        let env = Env::default();
+        let mut transactions: Vec<Transaction> = Vec::new(&env);
+        for tx_id in 1..=4u64 {
+            transactions.push_back(create_test_transaction(&env, tx_id, 500, "transfer"));
+        }
+
+        // Four senders at 2500 bps each: 4 * 2500^2 / 10000
+        assert_eq!(compute_sender_concentration(&env, &transactions), 2500);
+        assert_eq!(compute_sender_concentration(&env, &Vec::new(&env)), 0);
+    }
+
+    #[test]
+    fn test_compute_sender_concentration_near_max_amounts() {
+        let env = Env::default();
+        let sender = Address::generate(&env);
+        let mut transactions: Vec<Transaction> = Vec::new(&env);
+        for (tx_id, amount) in [(1u64, i128::MAX - 1), (2u64, 1000)] {
+            let mut tx = create_test_transaction(&env, tx_id, amount, "transfer");
+            tx.from = sender.clone();
+            transactions.push_back(tx);
+        }
+
+        // Volumes saturate instead of overflowing; one sender still holds the whole batch
+        assert_eq!(compute_sender_concentration(&env, &transactions), 10000);
+    }
+
+    #[test]
+    fn test_compute_sender_activity_counts_in_first_seen_order() {
+        let env = Env::default();
+        let alice = Address::generate(&env);
+        let bob = Address::generate(&env);
+        let carol = Address::generate(&env);
+
+        let mut transactions: Vec<Transaction> = Vec::new(&env);
+        for (tx_id, sender) in [&bob, &alice, &bob, &carol, &bob, &alice]
+            .iter()
+            .enumerate()
+        {
+            let mut tx = create_test_transaction(&env, tx_id as u64 + 1, 100, "transfer");
+            tx.from = (*sender).clone();
+            transactions.push_back(tx);
+        }
+
+        let activity = compute_sender_activity(&env, &transactions);
+        assert_eq!(
+            activity,
+            Vec::from_array(
+                &env,
+                [(bob.clone(), 3u32), (alice.clone(), 2), (carol.clone(), 1)]
+            )
+        );
+        assert_eq!(compute_sender_activity(&env, &Vec::new(&env)).len(), 0);
+    }
+
+    #[test]
+    fn test_compute_percentile_empty() {
+        let env = Env::default();
+        let transactions: Vec<Transaction> = Vec::new(&env);
+
+        assert_eq!(compute_percentile(&transactions, 5000), 0);
+    }
+
+    #[test]
+    fn test_find_anomalous_transactions_flags_outlier() {
+        let env = Env::default();
+        let mut transactions: Vec<Transaction> = Vec::new(&env);
+        for tx_id in 1..=9u64 {
+            transactions.push_back(create_test_transaction(&env, tx_id, 100, "transfer"));
+        }
+        transactions.push_back(create_test_transaction(&env, 10, 10_000, "transfer"));
+
+        // Average is 1090; 5x the average is 5450
+        let anomalies = find_anomalous_transactions(&env, &transactions, 50_000);
+
+        assert_eq!(anomalies.len(), 1);
+        assert_eq!(anomalies.get(0).unwrap(), (10, 10_000));
+    }
+
+    #[test]
+    fn test_find_anomalous_transactions_uniform_batch() {
+        let env = Env::default();
+        let mut transactions: Vec<Transaction> = Vec::new(&env);
+        for tx_id in 1..=5u64 {
+            transactions.push_back(create_test_transaction(&env, tx_id, 500, "transfer"));
+        }
+
+        let anomalies = find_anomalous_transactions(&env, &transactions, 20_000);
+
+        assert_eq!(anomalies.len(), 0);
+    }
+
+    #[test]
+    fn test_apply_refund_cap_stops_at_cap() {
+        let env = Env::default();
+        let mut results: Vec<RefundResult> = Vec::new(&env);
+        let mut refunded_txs: Map<u64, bool> = Map::new(&env);
+        for (tx_id, amount) in [(1u64, 1000i128), (3, 2000), (5, 100)] {
+            refunded_txs.set(tx_id, true);
+            results.push_back(RefundResult {
+                tx_id,
+                success: true,
+                status: RefundStatus::Eligible,
+                amount_refunded: amount,
+                error_message: None,
+            });
+        }
+
+        let capped = apply_refund_cap(&env, &results, &mut refunded_txs, 2500, 0);
+
+        assert!(capped.get(0).unwrap().success);
+        // Once the cap is hit, later refunds fail even if they would fit
+        for i in 1..3 {
+            let result = capped.get(i).unwrap();
+            assert!(!result.success);
+            assert_eq!(result.status, RefundStatus::CapExceeded);
+            assert_eq!(result.amount_refunded, 0);
+        }
+        assert!(refunded_txs.contains_key(1));
+        assert!(!refunded_txs.contains_key(3));
+        assert!(!refunded_txs.contains_key(5));
+
+        // Zero cap means unlimited
+        let mut refunded_txs: Map<u64, bool> = Map::new(&env);
+        let uncapped = apply_refund_cap(&env, &results, &mut refunded_txs, 0, 0);
+        assert!(uncapped.iter().all(|r| r.success));
+    }
+
     #[test]
     fn test_validate_batch_valid() {
         let env = Env::default();
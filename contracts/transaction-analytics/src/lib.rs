@@ -22,20 +22,23 @@
 mod analytics;
 mod types;
 
-use soroban_sdk::{contract, contractimpl, panic_with_error, Address, Env, Vec};
+use soroban_sdk::{contract, contractimpl, panic_with_error, token, Address, Env, Symbol, Vec};
 
 pub use crate::analytics::{
-    compute_batch_checksum, compute_batch_metrics, compute_category_metrics,
-    find_high_value_transactions, validate_audit_logs, validate_batch,
-    process_refund_batch, compute_refund_metrics, validate_refund_batch,
-    validate_refund_eligibility, create_bundle_result, validate_bundle_transactions,
+    apply_refund_cap, compute_batch_checksum, compute_batch_metrics, compute_category_metrics,
+    compute_percentile, compute_refund_metrics, compute_sender_activity,
+    compute_sender_concentration, create_bundle_result, detect_time_bursts,
+    filter_transactions_by_category, filter_transactions_by_range, find_anomalous_transactions,
+    find_high_value_transactions, process_refund_batch, validate_audit_logs, validate_batch,
+    validate_bundle_transactions, validate_refund_batch, validate_refund_eligibility,
     validate_transaction_for_bundle,
 };
 pub use crate::types::{
-    AnalyticsEvents, AuditLog, BatchMetrics, BatchStatusUpdateResult, BundleResult,
-    BundledTransaction, CategoryMetrics, DataKey, RatingInput, RatingResult, RatingStatus,
-    StatusUpdateResult, Transaction, TransactionStatus, TransactionStatusUpdate, ValidationResult,
-    RefundRequest, RefundResult, RefundStatus, RefundBatchMetrics, MAX_BATCH_SIZE,
+    AnalyticsEvents, AuditLog, BatchMetrics, BatchStatusUpdateResult, BundleExecutionResult,
+    BundleResult, BundledTransaction, CategoryMetrics, DataKey, LifetimeMetrics, RatingInput,
+    RatingResult, RatingStatus, RefundBatchMetrics, RefundRequest, RefundResult, RefundStatus,
+    StatusUpdateResult, Transaction, TransactionStatus, TransactionStatusUpdate, TransferOutcome,
+    ValidationResult, DEFAULT_FEE_BPS, MAX_BATCH_SIZE,
 };
 
 /// Error codes for the analytics contract.
@@ -70,6 +73,14 @@ pub enum AnalyticsError {
     RefundBatchTooLarge = 13,
     /// Contract already initialized
     AlreadyInitialized = 14,
+    /// Bundle contains invalid transactions and cannot be executed
+    BundleNotExecutable = 15,
+    /// No refund batch exists with the given ID
+    RefundBatchNotFound = 16,
+    /// Batch total volume exceeds the caller-supplied maximum
+    VolumeExceeded = 17,
+    /// Fee rate is outside [0, 10000] basis points
+    InvalidFeeBps = 18,
 }
 
 impl From<AnalyticsError> for soroban_sdk::Error {
@@ -98,6 +109,12 @@ impl TransactionAnalyticsContract {
         env.storage()
             .instance()
             .set(&DataKey::TotalTxProcessed, &0u64);
+        env.storage()
+            .instance()
+            .set(&DataKey::TotalVolumeAllTime, &0i128);
+        env.storage()
+            .instance()
+            .set(&DataKey::TotalFeesAllTime, &0i128);
         env.storage()
             .instance()
             .set(&DataKey::TotalAuditLogs, &0u64);
@@ -122,9 +139,12 @@ impl TransactionAnalyticsContract {
     ///
     /// # Arguments
     /// * `env` - The contract environment
-    /// * `caller` - The address calling this function (must be admin)
+    /// * `caller` - The address calling this function (admin or operator)
     /// * `transactions` - Vector of transactions to analyze
     /// * `high_value_threshold` - Optional threshold for high-value alerts
+    /// * `anomaly_multiplier_bps` - Optional multiple of the batch average (basis
+    ///   points, 100000 = 10x) above which a transaction is flagged as anomalous
+    /// * `max_total_volume` - Optional upper bound on the batch's total volume
     ///
     /// # Returns
     /// * `BatchMetrics` - Aggregated metrics for the batch
@@ -134,93 +154,123 @@ impl TransactionAnalyticsContract {
     /// * `batch_processed` - When batch metrics are computed
     /// * `category_analytics` - For each category in the batch
     /// * `high_value_alert` - For transactions above threshold
+    /// * `anomaly_alert` - For transactions deviating from the batch average
     /// * `analytics_completed` - When processing completes
     pub fn process_batch(
         env: Env,
         caller: Address,
         transactions: Vec<Transaction>,
         high_value_threshold: Option<i128>,
+        anomaly_multiplier_bps: Option<u32>,
+        max_total_volume: Option<i128>,
     ) -> BatchMetrics {
         // Verify authorization
         caller.require_auth();
-        Self::require_admin(&env, &caller);
+        Self::require_admin_or_operator(&env, &caller);
 
-        // Validate batch
-        let tx_count = transactions.len();
-        if tx_count == 0 {
-            panic_with_error!(&env, AnalyticsError::EmptyBatch);
-        }
-        if tx_count > MAX_BATCH_SIZE {
-            panic_with_error!(&env, AnalyticsError::BatchTooLarge);
-        }
+        Self::process_transactions(
+            &env,
+            &transactions,
+            high_value_threshold,
+            anomaly_multiplier_bps,
+            max_total_volume,
+        )
+    }
 
-        // Validate individual transactions
-        if let Err(_) = validate_batch(&transactions) {
+    /// Generates batch analytics for the transactions within a ledger range.
+    ///
+    /// Only transactions whose `timestamp` falls within `[from_ledger, to_ledger]`
+    /// contribute to volume, counts and uniqueness metrics; the rest are ignored.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `caller` - The address calling this function (admin or operator)
+    /// * `transactions` - Vector of transactions to filter and analyze
+    /// * `from_ledger` - Inclusive lower bound on transaction timestamp
+    /// * `to_ledger` - Inclusive upper bound on transaction timestamp
+    /// * `high_value_threshold` - Optional threshold for high-value alerts
+    ///
+    /// # Returns
+    /// * `BatchMetrics` - Aggregated metrics for the in-range subset
+    ///
+    /// # Errors
+    /// * `InvalidBatch` - If `from_ledger` is greater than `to_ledger`
+    /// * `EmptyBatch` - If no transaction falls within the range
+    pub fn process_batch_in_range(
+        env: Env,
+        caller: Address,
+        transactions: Vec<Transaction>,
+        from_ledger: u64,
+        to_ledger: u64,
+        high_value_threshold: Option<i128>,
+    ) -> BatchMetrics {
+        caller.require_auth();
+        Self::require_admin_or_operator(&env, &caller);
+
+        if from_ledger > to_ledger {
             panic_with_error!(&env, AnalyticsError::InvalidBatch);
         }
-
-        for tx in transactions.iter() {
-            env.storage()
-                .persistent()
-                .set(&DataKey::KnownTransaction(tx.tx_id), &true);
+        if transactions.len() > MAX_BATCH_SIZE {
+            panic_with_error!(&env, AnalyticsError::BatchTooLarge);
         }
 
-        // Get next batch ID (single read, single write at the end)
-        let batch_id: u64 = env
-            .storage()
-            .instance()
-            .get(&DataKey::LastBatchId)
-            .unwrap_or(0)
-            + 1;
-
-        // Emit start event
-        AnalyticsEvents::analytics_started(&env, batch_id, tx_count);
-
-        // Compute batch metrics (single pass over data)
-        let current_ledger = env.ledger().sequence() as u64;
-        let metrics = compute_batch_metrics(&env, &transactions, current_ledger);
-
-        // Emit batch processed event
-        AnalyticsEvents::batch_processed(&env, batch_id, &metrics);
+        let in_range = filter_transactions_by_range(&env, &transactions, from_ledger, to_ledger);
+        Self::process_transactions(&env, &in_range, high_value_threshold, None, None)
+    }
 
-        // Compute and emit category metrics
-        let category_metrics = compute_category_metrics(&env, &transactions, metrics.total_volume);
-        for cat_metric in category_metrics.iter() {
-            AnalyticsEvents::category_analytics(&env, batch_id, &cat_metric);
-        }
+    /// Generates batch analytics for the transactions in a single category.
+    ///
+    /// Transactions outside `category` are ignored. The resulting metrics are
+    /// stored as a regular batch and also under a category-qualified key.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `caller` - The address calling this function (admin or operator)
+    /// * `transactions` - Vector of transactions to filter and analyze
+    /// * `category` - Category to analyze
+    /// * `high_value_threshold` - Optional threshold for high-value alerts
+    ///
+    /// # Returns
+    /// * `BatchMetrics` - Aggregated metrics for the category subset
+    ///
+    /// # Errors
+    /// * `EmptyBatch` - If no transaction belongs to the category
+    pub fn process_batch_by_category(
+        env: Env,
+        caller: Address,
+        transactions: Vec<Transaction>,
+        category: Symbol,
+        high_value_threshold: Option<i128>,
+    ) -> BatchMetrics {
+        caller.require_auth();
+        Self::require_admin_or_operator(&env, &caller);
 
-        // Process high-value alerts if threshold provided
-        if let Some(threshold) = high_value_threshold {
-            let high_value_txs = find_high_value_transactions(&env, &transactions, threshold);
-            for (tx_id, amount) in high_value_txs.iter() {
-                AnalyticsEvents::high_value_alert(&env, batch_id, tx_id, amount);
-            }
+        if transactions.len() > MAX_BATCH_SIZE {
+            panic_with_error!(&env, AnalyticsError::BatchTooLarge);
         }
 
-        // Update storage (batched at the end for efficiency)
-        let total_processed: u64 = env
-            .storage()
-            .instance()
-            .get(&DataKey::TotalTxProcessed)
-            .unwrap_or(0);
+        let matching = filter_transactions_by_category(&env, &transactions, &category);
+        let metrics = Self::process_transactions(&env, &matching, high_value_threshold, None, None);
 
-        env.storage()
-            .instance()
-            .set(&DataKey::LastBatchId, &batch_id);
-        env.storage().instance().set(
-            &DataKey::TotalTxProcessed,
-            &(total_processed + tx_count as u64),
-        );
+        let batch_id = Self::get_last_batch_id(env.clone());
         env.storage()
             .persistent()
-            .set(&DataKey::BatchMetrics(batch_id), &metrics);
-
-        // Emit completion event
-        AnalyticsEvents::analytics_completed(&env, batch_id, tx_count as u64);
+            .set(&DataKey::CategoryBatchMetrics(category, batch_id), &metrics);
 
         metrics
     }
 
+    /// Retrieves metrics stored by `process_batch_by_category`.
+    pub fn get_category_batch_metrics(
+        env: Env,
+        category: Symbol,
+        batch_id: u64,
+    ) -> Option<BatchMetrics> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::CategoryBatchMetrics(category, batch_id))
+    }
+
     /// Logs multiple operations in a single batch (Audit Logging).
     ///
     /// # Arguments
@@ -233,7 +283,7 @@ impl TransactionAnalyticsContract {
         Self::require_admin(&env, &caller);
 
         // Validate logs
-        if let Err(_) = validate_audit_logs(&logs) {
+        if validate_audit_logs(&logs).is_err() {
             panic_with_error!(&env, AnalyticsError::InvalidAuditLog);
         }
 
@@ -274,6 +324,123 @@ impl TransactionAnalyticsContract {
             .get(&DataKey::BatchMetrics(batch_id))
     }
 
+    /// Lists the IDs of batches processed within a ledger range.
+    ///
+    /// Visits at most `limit` batch IDs in ascending order from `start`, so a
+    /// page may hold fewer matches than `limit`. Also returns the next batch ID
+    /// to pass as `start`; once it is past `get_last_batch_id` the range has
+    /// been fully scanned.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `from` - Inclusive lower bound on the processing ledger
+    /// * `to` - Inclusive upper bound on the processing ledger
+    /// * `start` - First batch ID to scan
+    /// * `limit` - Maximum number of IDs to visit, capped at `MAX_BATCH_SIZE`
+    ///
+    /// # Returns
+    /// * `(Vec<u64>, u64)` - Matching batch IDs, oldest first, and the next `start`
+    pub fn get_batch_ids_in_range(
+        env: Env,
+        from: u64,
+        to: u64,
+        start: u64,
+        limit: u32,
+    ) -> (Vec<u64>, u64) {
+        let mut batch_ids: Vec<u64> = Vec::new(&env);
+        let limit = limit.min(MAX_BATCH_SIZE);
+        let start = start.max(1);
+        if from > to || limit == 0 {
+            return (batch_ids, start);
+        }
+
+        let last_batch_id = Self::get_last_batch_id(env.clone());
+        let end = last_batch_id.min(start.saturating_add(limit as u64 - 1));
+        for batch_id in start..=end {
+            let ledger: Option<u64> = env
+                .storage()
+                .persistent()
+                .get(&DataKey::BatchLedger(batch_id));
+            if let Some(ledger) = ledger {
+                // Batch ledgers never decrease, so nothing later can match
+                if ledger > to {
+                    return (batch_ids, last_batch_id + 1);
+                }
+                if ledger >= from {
+                    batch_ids.push_back(batch_id);
+                }
+            }
+        }
+
+        (batch_ids, start.max(end.saturating_add(1)))
+    }
+
+    /// Returns a compact summary of a stored batch for lightweight indexers.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `batch_id` - The ID of the batch to summarize
+    ///
+    /// # Returns
+    /// * `Option<(u64, u32, i128, i128)>` - (batch_id, tx_count, total_volume, total_fees)
+    pub fn get_batch_summary(env: Env, batch_id: u64) -> Option<(u64, u32, i128, i128)> {
+        Self::get_batch_metrics(env, batch_id).map(|metrics| {
+            (
+                batch_id,
+                metrics.tx_count,
+                metrics.total_volume,
+                metrics.total_fees,
+            )
+        })
+    }
+
+    /// Returns per-category fee totals for a stored batch.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `batch_id` - The ID of the batch to retrieve
+    ///
+    /// # Returns
+    /// * `Vec<(Symbol, i128)>` - (category, total_fees) pairs; empty if the batch is unknown
+    pub fn get_batch_category_fees(env: Env, batch_id: u64) -> Vec<(Symbol, i128)> {
+        let category_metrics: Vec<CategoryMetrics> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::BatchCategoryMetrics(batch_id))
+            .unwrap_or_else(|| Vec::new(&env));
+
+        let mut fees: Vec<(Symbol, i128)> = Vec::new(&env);
+        for cat_metric in category_metrics.iter() {
+            fees.push_back((cat_metric.category, cat_metric.total_fees));
+        }
+        fees
+    }
+
+    /// Retrieves the per-category breakdown stored for a processed batch.
+    ///
+    /// These are the same metrics emitted as `category_analytics` events.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `batch_id` - The ID of the batch to retrieve
+    ///
+    /// # Returns
+    /// * `Option<Vec<CategoryMetrics>>` - The category metrics if the batch exists
+    pub fn get_category_metrics(env: Env, batch_id: u64) -> Option<Vec<CategoryMetrics>> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::BatchCategoryMetrics(batch_id))
+    }
+
+    /// Returns the number of distinct sender and recipient addresses seen across
+    /// all processed batches.
+    pub fn get_lifetime_unique_addresses(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::LifetimeUniqueAddresses)
+            .unwrap_or(0)
+    }
+
     /// Returns the last processed batch ID.
     pub fn get_last_batch_id(env: Env) -> u64 {
         env.storage()
@@ -290,6 +457,39 @@ impl TransactionAnalyticsContract {
             .unwrap_or(0)
     }
 
+    /// Returns lifetime totals accumulated across all processed batches.
+    pub fn get_lifetime_metrics(env: Env) -> LifetimeMetrics {
+        let storage = env.storage().instance();
+        LifetimeMetrics {
+            total_transactions: storage.get(&DataKey::TotalTxProcessed).unwrap_or(0),
+            total_volume: storage.get(&DataKey::TotalVolumeAllTime).unwrap_or(0),
+            total_fees: storage.get(&DataKey::TotalFeesAllTime).unwrap_or(0),
+            total_batches: storage.get(&DataKey::LastBatchId).unwrap_or(0),
+        }
+    }
+
+    /// Returns the lifetime average transaction amount.
+    ///
+    /// Computed as lifetime volume divided by lifetime transaction count;
+    /// returns 0 before any transaction has been processed.
+    pub fn get_lifetime_avg_amount(env: Env) -> i128 {
+        let total_tx: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::TotalTxProcessed)
+            .unwrap_or(0);
+        if total_tx == 0 {
+            return 0;
+        }
+
+        let total_volume: i128 = env
+            .storage()
+            .instance()
+            .get(&DataKey::TotalVolumeAllTime)
+            .unwrap_or(0);
+        total_volume / total_tx as i128
+    }
+
     /// Retrieves an audit log by its index.
     pub fn get_audit_log(env: Env, index: u64) -> Option<AuditLog> {
         env.storage().persistent().get(&DataKey::AuditLog(index))
@@ -307,12 +507,69 @@ impl TransactionAnalyticsContract {
     ///
     /// Useful for simulating analytics before committing.
     pub fn simulate_batch(env: Env, transactions: Vec<Transaction>) -> BatchMetrics {
-        if let Err(_) = validate_batch(&transactions) {
+        if validate_batch(&transactions).is_err() {
             panic_with_error!(&env, AnalyticsError::InvalidBatch);
         }
 
         let current_ledger = env.ledger().sequence() as u64;
-        compute_batch_metrics(&env, &transactions, current_ledger)
+        let fee_bps = Self::get_fee_bps(env.clone());
+        compute_batch_metrics(&env, &transactions, current_ledger, fee_bps)
+    }
+
+    /// Computes a percentile of transaction amounts without storing results (view-only).
+    ///
+    /// `percentile_bps` is in basis points (9500 = p95), clamped to `[0, 10000]`.
+    pub fn simulate_percentile(
+        env: Env,
+        transactions: Vec<Transaction>,
+        percentile_bps: u32,
+    ) -> i128 {
+        if validate_batch(&transactions).is_err() {
+            panic_with_error!(&env, AnalyticsError::InvalidBatch);
+        }
+
+        compute_percentile(&transactions, percentile_bps)
+    }
+
+    /// Computes sender concentration (Herfindahl-Hirschman index) in basis
+    /// points without storing results (view-only).
+    ///
+    /// 10000 means a single sender accounts for all volume.
+    pub fn simulate_sender_concentration(env: Env, transactions: Vec<Transaction>) -> u32 {
+        if validate_batch(&transactions).is_err() {
+            panic_with_error!(&env, AnalyticsError::InvalidBatch);
+        }
+
+        compute_sender_concentration(&env, &transactions)
+    }
+
+    /// Counts transactions per sender without storing results (view-only).
+    ///
+    /// Senders appear in the order they are first seen in `transactions`.
+    pub fn get_sender_activity(env: Env, transactions: Vec<Transaction>) -> Vec<(Address, u32)> {
+        if validate_batch(&transactions).is_err() {
+            panic_with_error!(&env, AnalyticsError::InvalidBatch);
+        }
+
+        compute_sender_activity(&env, &transactions)
+    }
+
+    /// Checks whether a set of transactions contains a burst (view-only).
+    ///
+    /// Returns true if any span of `window` ledgers holds more than
+    /// `threshold` transactions. Batches over `MAX_BATCH_SIZE` are rejected
+    /// before sorting.
+    pub fn detect_time_bursts(
+        env: Env,
+        transactions: Vec<Transaction>,
+        window: u64,
+        threshold: u32,
+    ) -> bool {
+        if transactions.len() > MAX_BATCH_SIZE {
+            panic_with_error!(&env, AnalyticsError::BatchTooLarge);
+        }
+
+        analytics::detect_time_bursts(&env, &transactions, window, threshold)
     }
 
     // Rating functionality removed for refund implementation
@@ -361,12 +618,20 @@ impl TransactionAnalyticsContract {
                 .persistent()
                 .set(&DataKey::TransactionStatus(update.tx_id), &update.status);
 
+            // Keep the stored reason in step with the latest status
+            let reason_key = DataKey::TransactionStatusReason(update.tx_id);
+            match &update.reason {
+                Some(reason) => env.storage().persistent().set(&reason_key, reason),
+                None => env.storage().persistent().remove(&reason_key),
+            }
+
             successful += 1;
             AnalyticsEvents::transaction_status_updated(
                 &env,
                 update.tx_id,
                 previous_status.clone(),
                 update.status.clone(),
+                update.reason.clone(),
             );
 
             results.push_back(StatusUpdateResult {
@@ -375,12 +640,38 @@ impl TransactionAnalyticsContract {
             });
         }
 
-        BatchStatusUpdateResult {
+        let result = BatchStatusUpdateResult {
             total_requests: count,
             successful,
             failed,
             results,
-        }
+        };
+
+        // Persist the outcome under a new status-update batch ID for auditing
+        let status_batch_id = Self::get_last_status_update_batch_id(env.clone()) + 1;
+        env.storage()
+            .instance()
+            .set(&DataKey::LastStatusUpdateBatchId, &status_batch_id);
+        env.storage()
+            .persistent()
+            .set(&DataKey::StatusUpdateBatch(status_batch_id), &result);
+
+        result
+    }
+
+    /// Retrieves the stored result of a status-update batch.
+    pub fn get_status_update_batch(env: Env, batch_id: u64) -> Option<BatchStatusUpdateResult> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::StatusUpdateBatch(batch_id))
+    }
+
+    /// Returns the last status-update batch ID.
+    pub fn get_last_status_update_batch_id(env: Env) -> u64 {
+        env.storage()
+            .instance()
+            .get(&DataKey::LastStatusUpdateBatchId)
+            .unwrap_or(0)
     }
 
     pub fn submit_ratings(
@@ -448,6 +739,13 @@ impl TransactionAnalyticsContract {
             .get(&DataKey::TransactionStatus(tx_id))
     }
 
+    /// Returns the reason recorded with the latest status update, if any.
+    pub fn get_transaction_status_reason(env: Env, tx_id: u64) -> Option<Symbol> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::TransactionStatusReason(tx_id))
+    }
+
     /// Updates the admin address.
     pub fn set_admin(env: Env, current_admin: Address, new_admin: Address) {
         current_admin.require_auth();
@@ -456,6 +754,34 @@ impl TransactionAnalyticsContract {
         env.storage().instance().set(&DataKey::Admin, &new_admin);
     }
 
+    /// Grants the operator role, allowing `operator` to process batches.
+    pub fn add_operator(env: Env, admin: Address, operator: Address) {
+        admin.require_auth();
+        Self::require_admin(&env, &admin);
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::Operators(operator), &true);
+    }
+
+    /// Revokes the operator role.
+    pub fn remove_operator(env: Env, admin: Address, operator: Address) {
+        admin.require_auth();
+        Self::require_admin(&env, &admin);
+
+        env.storage()
+            .persistent()
+            .remove(&DataKey::Operators(operator));
+    }
+
+    /// Returns whether `address` holds the operator role.
+    pub fn is_operator(env: Env, address: Address) -> bool {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Operators(address))
+            .unwrap_or(false)
+    }
+
     /// Bundles multiple StellarSpend transactions into a single transaction group.
     ///
     /// This function validates all transactions, emits events for bundled transactions,
@@ -491,7 +817,7 @@ impl TransactionAnalyticsContract {
         Self::require_admin(&env, &caller);
 
         // Validate bundle size
-        let tx_count = bundled_transactions.len() as u32;
+        let tx_count = bundled_transactions.len();
         if tx_count == 0 {
             panic_with_error!(&env, AnalyticsError::EmptyBundle);
         }
@@ -560,6 +886,115 @@ impl TransactionAnalyticsContract {
         bundle_result
     }
 
+    /// Executes a bundle of transfers atomically.
+    ///
+    /// Every transaction is validated first; if any is invalid nothing moves.
+    /// Each valid transaction is then performed as a token transfer from `from`
+    /// to `to`. A failing transfer (e.g. insufficient balance) aborts the
+    /// whole invocation, so either all transfers happen or none do.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `caller` - The address calling this function (must be admin)
+    /// * `token` - The token contract used for the transfers
+    /// * `bundled_transactions` - Vector of transactions to execute
+    ///
+    /// # Returns
+    /// * `BundleExecutionResult` - Per-transaction outcomes and totals
+    ///
+    /// # Events Emitted
+    /// * `bundle_transfer` - For each transfer performed
+    /// * `bundle_executed` - When all transfers complete
+    ///
+    /// # Errors
+    /// * `EmptyBundle` - If no transactions provided
+    /// * `BundleTooLarge` - If bundle exceeds maximum size
+    /// * `BundleNotExecutable` - If any transaction fails validation
+    /// * `Unauthorized` - If caller is not admin
+    pub fn execute_bundle(
+        env: Env,
+        caller: Address,
+        token: Address,
+        bundled_transactions: Vec<BundledTransaction>,
+    ) -> BundleExecutionResult {
+        caller.require_auth();
+        Self::require_admin(&env, &caller);
+
+        let tx_count = bundled_transactions.len();
+        if tx_count == 0 {
+            panic_with_error!(&env, AnalyticsError::EmptyBundle);
+        }
+        if tx_count > MAX_BATCH_SIZE {
+            panic_with_error!(&env, AnalyticsError::BundleTooLarge);
+        }
+
+        // All-or-nothing: refuse to move funds unless every transaction is valid
+        let validation_results = validate_bundle_transactions(&env, &bundled_transactions);
+        for result in validation_results.iter() {
+            if !result.is_valid {
+                panic_with_error!(&env, AnalyticsError::BundleNotExecutable);
+            }
+        }
+
+        let bundle_id: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::LastBundleId)
+            .unwrap_or(0)
+            + 1;
+
+        let token_client = token::Client::new(&env, &token);
+        let mut outcomes: Vec<TransferOutcome> = Vec::new(&env);
+        let mut total_transferred: i128 = 0;
+
+        for bundled_tx in bundled_transactions.iter() {
+            let tx = bundled_tx.transaction;
+
+            // A failed transfer panics and reverts every transfer made so far
+            token_client.transfer(&tx.from, &tx.to, &tx.amount);
+
+            total_transferred = total_transferred
+                .checked_add(tx.amount)
+                .unwrap_or(i128::MAX);
+
+            let outcome = TransferOutcome {
+                tx_id: tx.tx_id,
+                from: tx.from,
+                to: tx.to,
+                amount: tx.amount,
+                success: true,
+            };
+            AnalyticsEvents::bundle_transfer(&env, bundle_id, &outcome);
+            outcomes.push_back(outcome);
+        }
+
+        let result = BundleExecutionResult {
+            bundle_id,
+            executed_count: tx_count,
+            total_transferred,
+            outcomes,
+            executed_at: env.ledger().sequence() as u64,
+        };
+
+        env.storage()
+            .instance()
+            .set(&DataKey::LastBundleId, &bundle_id);
+        env.storage()
+            .persistent()
+            .set(&DataKey::BundleExecution(bundle_id), &result);
+
+        AnalyticsEvents::bundle_executed(&env, bundle_id, &result);
+
+        result
+    }
+
+    /// Retrieves the stored execution result for a specific bundle ID.
+    pub fn get_bundle_execution(env: Env, bundle_id: u64) -> Option<BundleExecutionResult> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::BundleExecution(bundle_id))
+    }
+
     /// Retrieves stored bundle result for a specific bundle ID.
     ///
     /// # Arguments
@@ -590,6 +1025,7 @@ impl TransactionAnalyticsContract {
     /// # Arguments
     /// * `env` - The contract environment
     /// * `caller` - The address calling this function (must be admin)
+    /// * `token` - The token refunds are paid in, drawn from the contract's balance
     /// * `refund_requests` - Vector of refund requests to process
     /// * `transaction_lookup` - Map of transaction IDs to Transaction objects for amount lookup
     ///
@@ -604,6 +1040,7 @@ impl TransactionAnalyticsContract {
     pub fn refund_batch(
         env: Env,
         caller: Address,
+        token: Address,
         refund_requests: Vec<RefundRequest>,
         transaction_lookup: soroban_sdk::Map<u64, Transaction>,
     ) -> RefundBatchMetrics {
@@ -612,7 +1049,7 @@ impl TransactionAnalyticsContract {
         Self::require_admin(&env, &caller);
 
         // Validate refund batch
-        let request_count = refund_requests.len() as u32;
+        let request_count = refund_requests.len();
         if request_count == 0 {
             panic_with_error!(&env, AnalyticsError::EmptyRefundBatch);
         }
@@ -620,7 +1057,7 @@ impl TransactionAnalyticsContract {
             panic_with_error!(&env, AnalyticsError::RefundBatchTooLarge);
         }
 
-        if let Err(_) = validate_refund_batch(&env, &refund_requests) {
+        if validate_refund_batch(&env, &refund_requests).is_err() {
             panic_with_error!(&env, AnalyticsError::InvalidRefundBatch);
         }
 
@@ -642,24 +1079,47 @@ impl TransactionAnalyticsContract {
         // Emit start event
         AnalyticsEvents::refund_batch_started(&env, refund_batch_id, request_count);
 
-        // Process refunds
-        let refund_results = process_refund_batch(
+        // Process refunds, failing any past the per-batch refund cap
+        let eligible_results = process_refund_batch(
             &env,
             &refund_requests,
             &transaction_lookup,
             &mut refunded_txs,
         );
+        let eligible_results = apply_refund_cap(
+            &env,
+            &eligible_results,
+            &mut refunded_txs,
+            Self::get_max_refund_per_batch(env.clone()),
+            0,
+        );
+
+        // Return funds to the original senders; a failed transfer un-marks the refund
+        let refund_results = Self::pay_refunds(
+            &env,
+            &token,
+            &eligible_results,
+            &transaction_lookup,
+            &mut refunded_txs,
+        );
 
         // Emit individual refund events
-        for result in refund_results.iter() {
-            AnalyticsEvents::refund_processed(&env, refund_batch_id, &result);
-            
+        Self::emit_refund_events(&env, refund_batch_id, &refund_results);
+
+        // Keep failed requests so they can be retried later
+        let mut failed_requests: Vec<RefundRequest> = Vec::new(&env);
+        for (request, result) in refund_requests.iter().zip(refund_results.iter()) {
             if !result.success {
-                if let Some(error_msg) = &result.error_message {
-                    AnalyticsEvents::refund_error(&env, refund_batch_id, result.tx_id, error_msg.clone());
-                }
+                failed_requests.push_back(request);
             }
         }
+        env.storage().persistent().set(
+            &DataKey::RefundBatchFailures(refund_batch_id),
+            &failed_requests,
+        );
+        env.storage()
+            .persistent()
+            .set(&DataKey::RefundBatchToken(refund_batch_id), &token);
 
         // Compute refund metrics
         let current_ledger = env.ledger().sequence() as u64;
@@ -689,6 +1149,120 @@ impl TransactionAnalyticsContract {
         metrics
     }
 
+    /// Re-attempts the refunds that failed in a previous refund batch.
+    ///
+    /// Only requests recorded as failed for `batch_id` are retried, using the
+    /// batch's original token; successful refunds are never paid twice. The
+    /// stored batch metrics and lifetime refund total are updated in place.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `admin` - The admin address
+    /// * `batch_id` - The refund batch to retry
+    /// * `transaction_lookup` - Map of transaction IDs to Transaction objects for amount lookup
+    ///
+    /// # Returns
+    /// * `RefundBatchMetrics` - The batch's updated metrics
+    pub fn retry_failed_refunds(
+        env: Env,
+        admin: Address,
+        batch_id: u64,
+        transaction_lookup: soroban_sdk::Map<u64, Transaction>,
+    ) -> RefundBatchMetrics {
+        admin.require_auth();
+        Self::require_admin(&env, &admin);
+
+        let mut metrics = match Self::get_refund_batch_metrics(env.clone(), batch_id) {
+            Some(metrics) => metrics,
+            None => panic_with_error!(&env, AnalyticsError::RefundBatchNotFound),
+        };
+        let failed_requests: Vec<RefundRequest> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::RefundBatchFailures(batch_id))
+            .unwrap_or(Vec::new(&env));
+        if failed_requests.is_empty() {
+            return metrics;
+        }
+        let token: Address = env
+            .storage()
+            .persistent()
+            .get(&DataKey::RefundBatchToken(batch_id))
+            .unwrap();
+
+        let mut refunded_txs: soroban_sdk::Map<u64, bool> = env
+            .storage()
+            .instance()
+            .get(&DataKey::RefundedTransactions)
+            .unwrap_or_else(|| soroban_sdk::Map::new(&env));
+
+        // Retries count toward the same per-batch cap as the original run
+        let eligible_results = process_refund_batch(
+            &env,
+            &failed_requests,
+            &transaction_lookup,
+            &mut refunded_txs,
+        );
+        let eligible_results = apply_refund_cap(
+            &env,
+            &eligible_results,
+            &mut refunded_txs,
+            Self::get_max_refund_per_batch(env.clone()),
+            metrics.total_refunded_amount,
+        );
+        let retry_results = Self::pay_refunds(
+            &env,
+            &token,
+            &eligible_results,
+            &transaction_lookup,
+            &mut refunded_txs,
+        );
+        Self::emit_refund_events(&env, batch_id, &retry_results);
+
+        let mut still_failed: Vec<RefundRequest> = Vec::new(&env);
+        let mut recovered_count: u32 = 0;
+        let mut recovered_amount: i128 = 0;
+        for (request, result) in failed_requests.iter().zip(retry_results.iter()) {
+            if result.success {
+                recovered_count += 1;
+                recovered_amount += result.amount_refunded;
+            } else {
+                still_failed.push_back(request);
+            }
+        }
+
+        metrics.successful_refunds += recovered_count;
+        metrics.failed_refunds -= recovered_count;
+        metrics.total_refunded_amount += recovered_amount;
+        if metrics.successful_refunds > 0 {
+            metrics.avg_refund_amount =
+                metrics.total_refunded_amount / metrics.successful_refunds as i128;
+        }
+
+        let total_refunded: i128 = env
+            .storage()
+            .instance()
+            .get(&DataKey::TotalRefundAmount)
+            .unwrap_or(0);
+        env.storage().instance().set(
+            &DataKey::TotalRefundAmount,
+            &(total_refunded + recovered_amount),
+        );
+        env.storage()
+            .persistent()
+            .set(&DataKey::RefundBatchMetrics(batch_id), &metrics);
+        env.storage()
+            .persistent()
+            .set(&DataKey::RefundBatchFailures(batch_id), &still_failed);
+        env.storage()
+            .instance()
+            .set(&DataKey::RefundedTransactions, &refunded_txs);
+
+        AnalyticsEvents::refund_batch_completed(&env, batch_id, &metrics);
+
+        metrics
+    }
+
     /// Simulates refund processing without storing results (view-only).
     ///
     /// Useful for testing refund eligibility and expected outcomes before committing.
@@ -697,7 +1271,7 @@ impl TransactionAnalyticsContract {
         refund_requests: Vec<RefundRequest>,
         transaction_lookup: soroban_sdk::Map<u64, Transaction>,
     ) -> RefundBatchMetrics {
-        if let Err(_) = validate_refund_batch(&env, &refund_requests) {
+        if validate_refund_batch(&env, &refund_requests).is_err() {
             panic_with_error!(&env, AnalyticsError::InvalidRefundBatch);
         }
 
@@ -714,6 +1288,13 @@ impl TransactionAnalyticsContract {
             &transaction_lookup,
             &mut temp_refunded_txs,
         );
+        let refund_results = apply_refund_cap(
+            &env,
+            &refund_results,
+            &mut temp_refunded_txs,
+            Self::get_max_refund_per_batch(env.clone()),
+            0,
+        );
 
         let current_ledger = env.ledger().sequence() as u64;
         compute_refund_metrics(&env, &refund_results, current_ledger)
@@ -734,6 +1315,53 @@ impl TransactionAnalyticsContract {
             .unwrap_or(0)
     }
 
+    /// Sets the processing fee rate used for batch fee metrics.
+    ///
+    /// The rate is in basis points and must be within `[0, 10000]`.
+    pub fn set_fee_bps(env: Env, admin: Address, fee_bps: u32) {
+        admin.require_auth();
+        Self::require_admin(&env, &admin);
+
+        if fee_bps > 10_000 {
+            panic_with_error!(&env, AnalyticsError::InvalidFeeBps);
+        }
+
+        env.storage().instance().set(&DataKey::FeeBps, &fee_bps);
+    }
+
+    /// Returns the processing fee rate in basis points (10 by default).
+    pub fn get_fee_bps(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::FeeBps)
+            .unwrap_or(DEFAULT_FEE_BPS)
+    }
+
+    /// Sets the maximum total amount refunded in a single refund batch.
+    ///
+    /// Once a batch reaches the cap, its remaining refunds fail with
+    /// `CapExceeded`. A cap of zero means unlimited.
+    pub fn set_max_refund_per_batch(env: Env, admin: Address, cap: i128) {
+        admin.require_auth();
+        Self::require_admin(&env, &admin);
+
+        if cap < 0 {
+            panic_with_error!(&env, AnalyticsError::InvalidAmount);
+        }
+
+        env.storage()
+            .instance()
+            .set(&DataKey::MaxRefundPerBatch, &cap);
+    }
+
+    /// Returns the per-batch refund cap (0 when unlimited).
+    pub fn get_max_refund_per_batch(env: Env) -> i128 {
+        env.storage()
+            .instance()
+            .get(&DataKey::MaxRefundPerBatch)
+            .unwrap_or(0)
+    }
+
     /// Returns the total refund amount processed.
     pub fn get_total_refund_amount(env: Env) -> i128 {
         env.storage()
@@ -753,6 +1381,214 @@ impl TransactionAnalyticsContract {
         refunded_txs.contains_key(tx_id)
     }
 
+    // Internal helper that validates, aggregates, emits and stores a batch
+    fn process_transactions(
+        env: &Env,
+        transactions: &Vec<Transaction>,
+        high_value_threshold: Option<i128>,
+        anomaly_multiplier_bps: Option<u32>,
+        max_total_volume: Option<i128>,
+    ) -> BatchMetrics {
+        // Validate batch
+        let tx_count = transactions.len();
+        if tx_count == 0 {
+            panic_with_error!(env, AnalyticsError::EmptyBatch);
+        }
+        if tx_count > MAX_BATCH_SIZE {
+            panic_with_error!(env, AnalyticsError::BatchTooLarge);
+        }
+
+        // Validate individual transactions
+        if validate_batch(transactions).is_err() {
+            panic_with_error!(env, AnalyticsError::InvalidBatch);
+        }
+
+        // Compute batch metrics (single pass over data) before any storage writes
+        let current_ledger = env.ledger().sequence() as u64;
+        let fee_bps = Self::get_fee_bps(env.clone());
+        let metrics = compute_batch_metrics(env, transactions, current_ledger, fee_bps);
+
+        if let Some(max_volume) = max_total_volume {
+            if metrics.total_volume > max_volume {
+                panic_with_error!(env, AnalyticsError::VolumeExceeded);
+            }
+        }
+
+        let mut unique_addresses = Self::get_lifetime_unique_addresses(env.clone());
+        for tx in transactions.iter() {
+            env.storage()
+                .persistent()
+                .set(&DataKey::KnownTransaction(tx.tx_id), &true);
+
+            // Count each participant only the first time it appears in any batch
+            for address in [tx.from.clone(), tx.to.clone()] {
+                let key = DataKey::SeenAddress(address);
+                if !env.storage().persistent().has(&key) {
+                    env.storage().persistent().set(&key, &true);
+                    unique_addresses += 1;
+                }
+            }
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::LifetimeUniqueAddresses, &unique_addresses);
+
+        // Get next batch ID (single read, single write at the end)
+        let batch_id: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::LastBatchId)
+            .unwrap_or(0)
+            + 1;
+
+        // Emit start event
+        AnalyticsEvents::analytics_started(env, batch_id, tx_count);
+
+        // Emit batch processed event
+        AnalyticsEvents::batch_processed(env, batch_id, &metrics);
+
+        // Compute and emit category metrics
+        let category_metrics =
+            compute_category_metrics(env, transactions, metrics.total_volume, fee_bps);
+        for cat_metric in category_metrics.iter() {
+            AnalyticsEvents::category_analytics(env, batch_id, &cat_metric);
+        }
+
+        // Process high-value alerts if threshold provided
+        if let Some(threshold) = high_value_threshold {
+            let high_value_txs = find_high_value_transactions(env, transactions, threshold);
+            for (tx_id, amount) in high_value_txs.iter() {
+                AnalyticsEvents::high_value_alert(env, batch_id, tx_id, amount);
+            }
+        }
+
+        // Flag relative outliers if a deviation multiplier is provided
+        if let Some(multiplier_bps) = anomaly_multiplier_bps {
+            let anomalies = find_anomalous_transactions(env, transactions, multiplier_bps);
+            for (tx_id, amount) in anomalies.iter() {
+                AnalyticsEvents::anomaly_alert(env, batch_id, tx_id, amount);
+            }
+        }
+
+        // Update storage (batched at the end for efficiency)
+        let total_processed: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::TotalTxProcessed)
+            .unwrap_or(0);
+
+        env.storage()
+            .instance()
+            .set(&DataKey::LastBatchId, &batch_id);
+        env.storage().instance().set(
+            &DataKey::TotalTxProcessed,
+            &(total_processed + tx_count as u64),
+        );
+
+        let total_volume: i128 = env
+            .storage()
+            .instance()
+            .get(&DataKey::TotalVolumeAllTime)
+            .unwrap_or(0);
+        let total_fees: i128 = env
+            .storage()
+            .instance()
+            .get(&DataKey::TotalFeesAllTime)
+            .unwrap_or(0);
+        env.storage().instance().set(
+            &DataKey::TotalVolumeAllTime,
+            &total_volume
+                .checked_add(metrics.total_volume)
+                .unwrap_or(i128::MAX),
+        );
+        env.storage().instance().set(
+            &DataKey::TotalFeesAllTime,
+            &total_fees
+                .checked_add(metrics.total_fees)
+                .unwrap_or(i128::MAX),
+        );
+        env.storage()
+            .persistent()
+            .set(&DataKey::BatchMetrics(batch_id), &metrics);
+        env.storage()
+            .persistent()
+            .set(&DataKey::BatchCategoryMetrics(batch_id), &category_metrics);
+        env.storage()
+            .persistent()
+            .set(&DataKey::BatchLedger(batch_id), &metrics.processed_at);
+
+        // Emit completion event
+        AnalyticsEvents::analytics_completed(env, batch_id, tx_count as u64);
+
+        metrics
+    }
+
+    // Internal helper paying out successful refund results to the original senders.
+    // A failed transfer turns the result into a failure and un-marks the refund.
+    fn pay_refunds(
+        env: &Env,
+        token: &Address,
+        eligible_results: &Vec<RefundResult>,
+        transaction_lookup: &soroban_sdk::Map<u64, Transaction>,
+        refunded_txs: &mut soroban_sdk::Map<u64, bool>,
+    ) -> Vec<RefundResult> {
+        let token_client = token::Client::new(env, token);
+        let mut refund_results: Vec<RefundResult> = Vec::new(env);
+        for mut result in eligible_results.iter() {
+            if result.success {
+                let sender = transaction_lookup.get(result.tx_id).unwrap().from;
+                let transferred = matches!(
+                    token_client.try_transfer(
+                        &env.current_contract_address(),
+                        &sender,
+                        &result.amount_refunded,
+                    ),
+                    Ok(Ok(()))
+                );
+
+                if !transferred {
+                    refunded_txs.remove(result.tx_id);
+                    result.success = false;
+                    result.amount_refunded = 0;
+                    result.error_message = Some(Symbol::new(env, "TransferFailed"));
+                }
+            }
+            refund_results.push_back(result);
+        }
+        refund_results
+    }
+
+    // Internal helper emitting per-refund processed and error events
+    fn emit_refund_events(env: &Env, refund_batch_id: u64, refund_results: &Vec<RefundResult>) {
+        for result in refund_results.iter() {
+            AnalyticsEvents::refund_processed(env, refund_batch_id, &result);
+
+            if !result.success {
+                if let Some(error_msg) = &result.error_message {
+                    AnalyticsEvents::refund_error(
+                        env,
+                        refund_batch_id,
+                        result.tx_id,
+                        error_msg.clone(),
+                    );
+                }
+            }
+        }
+    }
+
+    // Internal helper to verify the caller is the admin or a registered operator
+    fn require_admin_or_operator(env: &Env, caller: &Address) {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .expect("Contract not initialized");
+
+        if *caller != admin && !Self::is_operator(env.clone(), caller.clone()) {
+            panic_with_error!(env, AnalyticsError::Unauthorized);
+        }
+    }
+
     // Internal helper to verify admin
     fn require_admin(env: &Env, caller: &Address) {
         let admin: Address = env
@@ -3,13 +3,12 @@
 #![cfg(test)]
 
 use crate::{
-    BundleResult, BundledTransaction, RefundRequest, RefundStatus, Transaction, 
-    TransactionAnalyticsContract, TransactionAnalyticsContractClient, ValidationResult,
-  TransactionStatus, TransactionStatusUpdate,
+    BundledTransaction, RefundRequest, Transaction, TransactionAnalyticsContract,
+    TransactionAnalyticsContractClient, TransactionStatus, TransactionStatusUpdate, MAX_BATCH_SIZE,
 };
 use soroban_sdk::{
-    testutils::{Address as _, Events},
-    Address, Env, Symbol, Vec, Map,
+    testutils::{Address as _, Events, Ledger},
+    token, Address, Env, Map, Symbol, Vec,
 };
 
 /// Creates a test environment with the contract deployed and initialized.
@@ -90,7 +89,7 @@ fn test_process_single_transaction_batch() {
     let mut transactions: Vec<Transaction> = Vec::new(&env);
     transactions.push_back(create_transaction(&env, 1, 1000, "transfer"));
 
-    let metrics = client.process_batch(&admin, &transactions, &None);
+    let metrics = client.process_batch(&admin, &transactions, &None, &None, &None);
 
     assert_eq!(metrics.tx_count, 1);
     assert_eq!(metrics.total_volume, 1000);
@@ -113,7 +112,7 @@ fn test_process_multiple_transactions_batch() {
     transactions.push_back(create_transaction(&env, 3, 300, "savings"));
     transactions.push_back(create_transaction(&env, 4, 400, "transfer"));
 
-    let metrics = client.process_batch(&admin, &transactions, &None);
+    let metrics = client.process_batch(&admin, &transactions, &None, &None, &None);
 
     assert_eq!(metrics.tx_count, 4);
     assert_eq!(metrics.total_volume, 1000);
@@ -160,7 +159,7 @@ fn test_process_batch_with_shared_addresses() {
         "transfer",
     ));
 
-    let metrics = client.process_batch(&admin, &transactions, &None);
+    let metrics = client.process_batch(&admin, &transactions, &None, &None, &None);
 
     assert_eq!(metrics.tx_count, 3);
     assert_eq!(metrics.unique_senders, 2);
@@ -176,13 +175,13 @@ fn test_batch_id_increments() {
 
     assert_eq!(client.get_last_batch_id(), 0);
 
-    client.process_batch(&admin, &transactions, &None);
+    client.process_batch(&admin, &transactions, &None, &None, &None);
     assert_eq!(client.get_last_batch_id(), 1);
 
-    client.process_batch(&admin, &transactions, &None);
+    client.process_batch(&admin, &transactions, &None, &None, &None);
     assert_eq!(client.get_last_batch_id(), 2);
 
-    client.process_batch(&admin, &transactions, &None);
+    client.process_batch(&admin, &transactions, &None, &None, &None);
     assert_eq!(client.get_last_batch_id(), 3);
 }
 
@@ -199,10 +198,10 @@ fn test_total_transactions_accumulates() {
     batch2.push_back(create_transaction(&env, 4, 400, "budget"));
     batch2.push_back(create_transaction(&env, 5, 500, "budget"));
 
-    client.process_batch(&admin, &batch1, &None);
+    client.process_batch(&admin, &batch1, &None, &None, &None);
     assert_eq!(client.get_total_transactions_processed(), 2);
 
-    client.process_batch(&admin, &batch2, &None);
+    client.process_batch(&admin, &batch2, &None, &None, &None);
     assert_eq!(client.get_total_transactions_processed(), 5);
 }
 
@@ -220,7 +219,7 @@ fn test_high_value_threshold_triggers_alerts() {
     transactions.push_back(create_transaction(&env, 3, 10000, "budget"));
 
     let threshold = Some(1000i128);
-    let metrics = client.process_batch(&admin, &transactions, &threshold);
+    let metrics = client.process_batch(&admin, &transactions, &threshold, &None, &None);
 
     // Verify basic metrics still computed correctly
     assert_eq!(metrics.tx_count, 3);
@@ -236,11 +235,112 @@ fn test_no_alerts_when_below_threshold() {
     transactions.push_back(create_transaction(&env, 2, 200, "transfer"));
 
     let threshold = Some(1000i128);
-    let metrics = client.process_batch(&admin, &transactions, &threshold);
+    let metrics = client.process_batch(&admin, &transactions, &threshold, &None, &None);
 
     assert_eq!(metrics.tx_count, 2);
 }
 
+#[test]
+fn test_lifetime_metrics_sum_batches() {
+    let (env, admin, client) = setup_test_env();
+
+    let mut batch1: Vec<Transaction> = Vec::new(&env);
+    batch1.push_back(create_transaction(&env, 1, 5000, "transfer"));
+    batch1.push_back(create_transaction(&env, 2, 3000, "budget"));
+
+    let mut batch2: Vec<Transaction> = Vec::new(&env);
+    batch2.push_back(create_transaction(&env, 3, 12000, "savings"));
+
+    let metrics1 = client.process_batch(&admin, &batch1, &None, &None, &None);
+    let metrics2 = client.process_batch(&admin, &batch2, &None, &None, &None);
+
+    let lifetime = client.get_lifetime_metrics();
+    assert_eq!(lifetime.total_transactions, 3);
+    assert_eq!(lifetime.total_batches, 2);
+    assert_eq!(
+        lifetime.total_volume,
+        metrics1.total_volume + metrics2.total_volume
+    );
+    assert_eq!(
+        lifetime.total_fees,
+        metrics1.total_fees + metrics2.total_fees
+    );
+    assert_eq!(lifetime.total_volume, 20000);
+    assert_eq!(lifetime.total_fees, 20);
+}
+
+#[test]
+fn test_lifetime_avg_amount_across_batches() {
+    let (env, admin, client) = setup_test_env();
+
+    assert_eq!(client.get_lifetime_avg_amount(), 0);
+
+    let mut batch1: Vec<Transaction> = Vec::new(&env);
+    batch1.push_back(create_transaction(&env, 1, 100, "transfer"));
+    batch1.push_back(create_transaction(&env, 2, 300, "transfer"));
+    client.process_batch(&admin, &batch1, &None, &None, &None);
+    assert_eq!(client.get_lifetime_avg_amount(), 200);
+
+    let mut batch2: Vec<Transaction> = Vec::new(&env);
+    batch2.push_back(create_transaction(&env, 3, 800, "budget"));
+    client.process_batch(&admin, &batch2, &None, &None, &None);
+
+    // (100 + 300 + 800) / 3
+    assert_eq!(client.get_lifetime_avg_amount(), 400);
+}
+
+#[test]
+fn test_lifetime_unique_addresses_is_union_across_batches() {
+    let (env, admin, client) = setup_test_env();
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let carol = Address::generate(&env);
+    let dave = Address::generate(&env);
+
+    let mut batch1: Vec<Transaction> = Vec::new(&env);
+    batch1.push_back(create_transaction_with_addresses(
+        &env,
+        1,
+        alice.clone(),
+        bob.clone(),
+        100,
+        "transfer",
+    ));
+    batch1.push_back(create_transaction_with_addresses(
+        &env,
+        2,
+        bob.clone(),
+        carol.clone(),
+        100,
+        "transfer",
+    ));
+    client.process_batch(&admin, &batch1, &None, &None, &None);
+    assert_eq!(client.get_lifetime_unique_addresses(), 3);
+
+    let mut batch2: Vec<Transaction> = Vec::new(&env);
+    batch2.push_back(create_transaction_with_addresses(
+        &env,
+        3,
+        carol.clone(),
+        alice.clone(),
+        100,
+        "transfer",
+    ));
+    batch2.push_back(create_transaction_with_addresses(
+        &env,
+        4,
+        dave.clone(),
+        bob.clone(),
+        100,
+        "transfer",
+    ));
+    client.process_batch(&admin, &batch2, &None, &None, &None);
+
+    // alice, bob, carol, dave - not 3 + 4
+    assert_eq!(client.get_lifetime_unique_addresses(), 4);
+}
+
 // ============================================================================
 // Metrics Retrieval Tests
 // ============================================================================
@@ -253,7 +353,7 @@ fn test_get_batch_metrics_after_processing() {
     transactions.push_back(create_transaction(&env, 1, 500, "transfer"));
     transactions.push_back(create_transaction(&env, 2, 500, "transfer"));
 
-    let processed_metrics = client.process_batch(&admin, &transactions, &None);
+    let processed_metrics = client.process_batch(&admin, &transactions, &None, &None, &None);
     let stored_metrics = client.get_batch_metrics(&1).unwrap();
 
     assert_eq!(stored_metrics.tx_count, processed_metrics.tx_count);
@@ -268,6 +368,32 @@ fn test_get_nonexistent_batch_metrics() {
     assert!(metrics.is_none());
 }
 
+#[test]
+fn test_get_batch_summary_matches_metrics() {
+    let (env, admin, client) = setup_test_env();
+
+    let mut transactions: Vec<Transaction> = Vec::new(&env);
+    transactions.push_back(create_transaction(&env, 1, 1000, "transfer"));
+    transactions.push_back(create_transaction(&env, 2, 3000, "budget"));
+    client.process_batch(&admin, &transactions, &None, &None, &None);
+
+    let metrics = client.get_batch_metrics(&1).unwrap();
+    let summary = client.get_batch_summary(&1).unwrap();
+
+    assert_eq!(
+        summary,
+        (
+            1,
+            metrics.tx_count,
+            metrics.total_volume,
+            metrics.total_fees
+        )
+    );
+    assert_eq!(summary.1, 2);
+    assert_eq!(summary.2, 4000);
+    assert!(client.get_batch_summary(&999).is_none());
+}
+
 // ============================================================================
 // Simulate Batch Tests
 // ============================================================================
@@ -292,6 +418,95 @@ fn test_simulate_batch_does_not_store() {
 // Admin Tests
 // ============================================================================
 
+#[test]
+fn test_simulate_percentile() {
+    let (env, _admin, client) = setup_test_env();
+
+    let mut transactions: Vec<Transaction> = Vec::new(&env);
+    transactions.push_back(create_transaction(&env, 1, 400, "transfer"));
+    transactions.push_back(create_transaction(&env, 2, 100, "transfer"));
+    transactions.push_back(create_transaction(&env, 3, 1000, "budget"));
+    transactions.push_back(create_transaction(&env, 4, 200, "budget"));
+    transactions.push_back(create_transaction(&env, 5, 300, "savings"));
+
+    let metrics = client.simulate_batch(&transactions);
+
+    // p50 of five sorted amounts [100, 200, 300, 400, 1000] is the median
+    assert_eq!(client.simulate_percentile(&transactions, &5000), 300);
+    assert_eq!(
+        client.simulate_percentile(&transactions, &10000),
+        metrics.max_amount
+    );
+    assert_eq!(client.simulate_percentile(&transactions, &9500), 1000);
+}
+
+#[test]
+fn test_simulate_sender_concentration() {
+    let (env, _admin, client) = setup_test_env();
+
+    let whale = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let mut concentrated: Vec<Transaction> = Vec::new(&env);
+    concentrated.push_back(create_transaction_with_addresses(
+        &env,
+        1,
+        whale.clone(),
+        recipient.clone(),
+        9000,
+        "transfer",
+    ));
+    concentrated.push_back(create_transaction_with_addresses(
+        &env,
+        2,
+        whale.clone(),
+        recipient.clone(),
+        1000,
+        "transfer",
+    ));
+    assert_eq!(client.simulate_sender_concentration(&concentrated), 10000);
+
+    let mut spread: Vec<Transaction> = Vec::new(&env);
+    for tx_id in 1..=10u64 {
+        spread.push_back(create_transaction(&env, tx_id, 1000, "transfer"));
+    }
+    // Ten equal senders at 1000 bps each
+    assert_eq!(client.simulate_sender_concentration(&spread), 1000);
+}
+
+#[test]
+fn test_get_sender_activity() {
+    let (env, _admin, client) = setup_test_env();
+
+    let frequent = Address::generate(&env);
+    let occasional = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let senders = [
+        &occasional,
+        &frequent,
+        &frequent,
+        &occasional,
+        &frequent,
+        &frequent,
+    ];
+
+    let mut transactions: Vec<Transaction> = Vec::new(&env);
+    for (i, sender) in senders.iter().enumerate() {
+        transactions.push_back(create_transaction_with_addresses(
+            &env,
+            i as u64 + 1,
+            (*sender).clone(),
+            recipient.clone(),
+            500,
+            "transfer",
+        ));
+    }
+
+    let activity = client.get_sender_activity(&transactions);
+    assert_eq!(activity.len(), 2);
+    assert_eq!(activity.get(0).unwrap(), (occasional, 2));
+    assert_eq!(activity.get(1).unwrap(), (frequent, 4));
+}
+
 #[test]
 fn test_set_admin() {
     let (env, admin, client) = setup_test_env();
@@ -312,7 +527,7 @@ fn test_unauthorized_process_batch() {
     transactions.push_back(create_transaction(&env, 1, 100, "transfer"));
 
     // This should panic due to unauthorized access
-    client.process_batch(&unauthorized, &transactions, &None);
+    client.process_batch(&unauthorized, &transactions, &None, &None, &None);
 }
 
 // ============================================================================
@@ -325,7 +540,7 @@ fn test_empty_batch_rejected() {
     let (env, admin, client) = setup_test_env();
 
     let transactions: Vec<Transaction> = Vec::new(&env);
-    client.process_batch(&admin, &transactions, &None);
+    client.process_batch(&admin, &transactions, &None, &None, &None);
 }
 
 #[test]
@@ -343,7 +558,7 @@ fn test_large_batch_processing() {
         ));
     }
 
-    let metrics = client.process_batch(&admin, &transactions, &None);
+    let metrics = client.process_batch(&admin, &transactions, &None, &None, &None);
 
     assert_eq!(metrics.tx_count, 50);
     // Sum of 100 + 200 + ... + 5000 = 100 * (1 + 2 + ... + 50) = 100 * 1275 = 127500
@@ -353,6 +568,32 @@ fn test_large_batch_processing() {
     assert_eq!(metrics.max_amount, 5000);
 }
 
+#[test]
+fn test_max_total_volume_just_under_passes() {
+    let (env, admin, client) = setup_test_env();
+
+    let mut transactions: Vec<Transaction> = Vec::new(&env);
+    transactions.push_back(create_transaction(&env, 1, 1000, "transfer"));
+    transactions.push_back(create_transaction(&env, 2, 2000, "transfer"));
+
+    let metrics = client.process_batch(&admin, &transactions, &None, &None, &Some(3000));
+
+    assert_eq!(metrics.total_volume, 3000);
+    assert_eq!(client.get_last_batch_id(), 1);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #17)")]
+fn test_max_total_volume_just_over_rejected() {
+    let (env, admin, client) = setup_test_env();
+
+    let mut transactions: Vec<Transaction> = Vec::new(&env);
+    transactions.push_back(create_transaction(&env, 1, 1000, "transfer"));
+    transactions.push_back(create_transaction(&env, 2, 2001, "transfer"));
+
+    client.process_batch(&admin, &transactions, &None, &None, &Some(3000));
+}
+
 #[test]
 fn test_zero_amount_transactions() {
     let (env, admin, client) = setup_test_env();
@@ -361,7 +602,7 @@ fn test_zero_amount_transactions() {
     transactions.push_back(create_transaction(&env, 1, 0, "transfer"));
     transactions.push_back(create_transaction(&env, 2, 100, "transfer"));
 
-    let metrics = client.process_batch(&admin, &transactions, &None);
+    let metrics = client.process_batch(&admin, &transactions, &None, &None, &None);
 
     assert_eq!(metrics.tx_count, 2);
     assert_eq!(metrics.total_volume, 100);
@@ -382,13 +623,44 @@ fn test_fee_calculation() {
     // 999 -> 0 fee (integer rounds down)
     transactions.push_back(create_transaction(&env, 3, 999, "budget"));
 
-    let metrics = client.process_batch(&admin, &transactions, &None);
+    let metrics = client.process_batch(&admin, &transactions, &None, &None, &None);
 
     assert_eq!(metrics.tx_count, 3);
     assert_eq!(metrics.total_volume, 16499);
     assert_eq!(metrics.total_fees, 15);
 }
 
+#[test]
+fn test_fee_calculation_uses_configured_rate() {
+    let (env, admin, client) = setup_test_env();
+    assert_eq!(client.get_fee_bps(), 10);
+
+    // 0.5% instead of the default 0.1%
+    client.set_fee_bps(&admin, &50);
+
+    let mut transactions: Vec<Transaction> = Vec::new(&env);
+    // 10000 -> 50 fee
+    transactions.push_back(create_transaction(&env, 1, 10000, "transfer"));
+    // 5500 -> 27 fee
+    transactions.push_back(create_transaction(&env, 2, 5500, "savings"));
+    // 999 -> 4 fee
+    transactions.push_back(create_transaction(&env, 3, 999, "budget"));
+
+    let metrics = client.process_batch(&admin, &transactions, &None, &None, &None);
+    assert_eq!(metrics.total_fees, 81);
+
+    client.set_fee_bps(&admin, &0);
+    assert_eq!(client.simulate_batch(&transactions).total_fees, 0);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #18)")]
+fn test_set_fee_bps_rejects_out_of_range() {
+    let (_env, admin, client) = setup_test_env();
+
+    client.set_fee_bps(&admin, &10_001);
+}
+
 // ============================================================================
 // Event Emission Tests
 // ============================================================================
@@ -400,7 +672,7 @@ fn test_events_emitted_on_process() {
     let mut transactions: Vec<Transaction> = Vec::new(&env);
     transactions.push_back(create_transaction(&env, 1, 1000, "transfer"));
 
-    client.process_batch(&admin, &transactions, &None);
+    client.process_batch(&admin, &transactions, &None, &None, &None);
 
     let events = env.events().all();
 
@@ -409,6 +681,31 @@ fn test_events_emitted_on_process() {
     assert!(events.len() >= 4);
 }
 
+#[test]
+fn test_anomaly_alert_emitted_for_outlier() {
+    let (env, admin, client) = setup_test_env();
+
+    let mut uniform: Vec<Transaction> = Vec::new(&env);
+    let mut with_outlier: Vec<Transaction> = Vec::new(&env);
+    for tx_id in 1..=5u64 {
+        uniform.push_back(create_transaction(&env, tx_id, 1000, "transfer"));
+        with_outlier.push_back(create_transaction(&env, tx_id + 10, 1000, "transfer"));
+    }
+    with_outlier.push_back(create_transaction(&env, 16, 50_000, "transfer"));
+
+    // 3x the batch average
+    let multiplier = Some(30_000u32);
+
+    client.process_batch(&admin, &uniform, &None, &multiplier, &None);
+    let uniform_events = env.events().all().len();
+
+    client.process_batch(&admin, &with_outlier, &None, &multiplier, &None);
+    let outlier_events = env.events().all().len();
+
+    // Same event set plus exactly one anomaly alert
+    assert_eq!(outlier_events, uniform_events + 1);
+}
+
 #[test]
 fn test_update_transaction_statuses_success_and_invalid_ids() {
     let (env, admin, client) = setup_test_env();
@@ -417,11 +714,19 @@ fn test_update_transaction_statuses_success_and_invalid_ids() {
     transactions.push_back(create_transaction(&env, 1, 1000, "transfer"));
     transactions.push_back(create_transaction(&env, 2, 2000, "transfer"));
 
-    client.process_batch(&admin, &transactions, &None);
+    client.process_batch(&admin, &transactions, &None, &None, &None);
 
     let mut updates: Vec<TransactionStatusUpdate> = Vec::new(&env);
-    updates.push_back(TransactionStatusUpdate { tx_id: 1, status: TransactionStatus::Completed });
-    updates.push_back(TransactionStatusUpdate { tx_id: 999, status: TransactionStatus::Failed });
+    updates.push_back(TransactionStatusUpdate {
+        tx_id: 1,
+        status: TransactionStatus::Completed,
+        reason: None,
+    });
+    updates.push_back(TransactionStatusUpdate {
+        tx_id: 999,
+        status: TransactionStatus::Failed,
+        reason: None,
+    });
 
     let result = client.update_transaction_statuses(&admin, &updates);
 
@@ -451,14 +756,22 @@ fn test_update_transaction_statuses_multiple_batches() {
     let mut transactions: Vec<Transaction> = Vec::new(&env);
     transactions.push_back(create_transaction(&env, 1, 1000, "transfer"));
 
-    client.process_batch(&admin, &transactions, &None);
+    client.process_batch(&admin, &transactions, &None, &None, &None);
 
     let mut updates1: Vec<TransactionStatusUpdate> = Vec::new(&env);
-    updates1.push_back(TransactionStatusUpdate { tx_id: 1, status: TransactionStatus::Pending });
+    updates1.push_back(TransactionStatusUpdate {
+        tx_id: 1,
+        status: TransactionStatus::Pending,
+        reason: None,
+    });
     client.update_transaction_statuses(&admin, &updates1);
 
     let mut updates2: Vec<TransactionStatusUpdate> = Vec::new(&env);
-    updates2.push_back(TransactionStatusUpdate { tx_id: 1, status: TransactionStatus::Completed });
+    updates2.push_back(TransactionStatusUpdate {
+        tx_id: 1,
+        status: TransactionStatus::Completed,
+        reason: None,
+    });
     let result2 = client.update_transaction_statuses(&admin, &updates2);
 
     assert_eq!(result2.total_requests, 1);
@@ -468,6 +781,87 @@ fn test_update_transaction_statuses_multiple_batches() {
     assert_eq!(stored_status, Some(TransactionStatus::Completed));
 }
 
+#[test]
+fn test_update_transaction_status_records_reason() {
+    let (env, admin, client) = setup_test_env();
+
+    let mut transactions: Vec<Transaction> = Vec::new(&env);
+    transactions.push_back(create_transaction(&env, 1, 1000, "transfer"));
+    client.process_batch(&admin, &transactions, &None, &None, &None);
+
+    let reason = Symbol::new(&env, "chargeback");
+    let mut updates: Vec<TransactionStatusUpdate> = Vec::new(&env);
+    updates.push_back(TransactionStatusUpdate {
+        tx_id: 1,
+        status: TransactionStatus::Refunded,
+        reason: Some(reason.clone()),
+    });
+    client.update_transaction_statuses(&admin, &updates);
+
+    assert_eq!(
+        client.get_transaction_status(&1),
+        Some(TransactionStatus::Refunded)
+    );
+    assert_eq!(client.get_transaction_status_reason(&1), Some(reason));
+
+    // A later update without a reason clears the stale one
+    let mut updates: Vec<TransactionStatusUpdate> = Vec::new(&env);
+    updates.push_back(TransactionStatusUpdate {
+        tx_id: 1,
+        status: TransactionStatus::Completed,
+        reason: None,
+    });
+    client.update_transaction_statuses(&admin, &updates);
+
+    assert_eq!(client.get_transaction_status_reason(&1), None);
+    assert_eq!(client.get_transaction_status_reason(&999), None);
+}
+
+#[test]
+fn test_status_update_batches_are_stored() {
+    let (env, admin, client) = setup_test_env();
+
+    let mut transactions: Vec<Transaction> = Vec::new(&env);
+    transactions.push_back(create_transaction(&env, 1, 1000, "transfer"));
+    client.process_batch(&admin, &transactions, &None, &None, &None);
+
+    let mut updates1: Vec<TransactionStatusUpdate> = Vec::new(&env);
+    updates1.push_back(TransactionStatusUpdate {
+        tx_id: 1,
+        status: TransactionStatus::Pending,
+        reason: None,
+    });
+    updates1.push_back(TransactionStatusUpdate {
+        tx_id: 999,
+        status: TransactionStatus::Failed,
+        reason: None,
+    });
+    client.update_transaction_statuses(&admin, &updates1);
+
+    let mut updates2: Vec<TransactionStatusUpdate> = Vec::new(&env);
+    updates2.push_back(TransactionStatusUpdate {
+        tx_id: 1,
+        status: TransactionStatus::Completed,
+        reason: None,
+    });
+    client.update_transaction_statuses(&admin, &updates2);
+
+    assert_eq!(client.get_last_status_update_batch_id(), 2);
+
+    let first = client.get_status_update_batch(&1).unwrap();
+    assert_eq!(first.total_requests, 2);
+    assert_eq!(first.successful, 1);
+    assert_eq!(first.failed, 1);
+    assert!(!first.results.get(1).unwrap().is_valid);
+
+    let second = client.get_status_update_batch(&2).unwrap();
+    assert_eq!(second.total_requests, 1);
+    assert_eq!(second.successful, 1);
+    assert_eq!(second.results.get(0).unwrap().tx_id, 1);
+
+    assert!(client.get_status_update_batch(&3).is_none());
+}
+
 // ============================================================================
 // Category Metrics Tests
 // ============================================================================
@@ -481,7 +875,7 @@ fn test_multiple_categories_processed() {
     transactions.push_back(create_transaction(&env, 2, 300, "budget"));
     transactions.push_back(create_transaction(&env, 3, 200, "savings"));
 
-    let metrics = client.process_batch(&admin, &transactions, &None);
+    let metrics = client.process_batch(&admin, &transactions, &None, &None, &None);
 
     assert_eq!(metrics.tx_count, 3);
     assert_eq!(metrics.total_volume, 1000);
@@ -496,12 +890,273 @@ fn test_same_category_aggregation() {
     transactions.push_back(create_transaction(&env, 2, 200, "transfer"));
     transactions.push_back(create_transaction(&env, 3, 300, "transfer"));
 
-    let metrics = client.process_batch(&admin, &transactions, &None);
+    let metrics = client.process_batch(&admin, &transactions, &None, &None, &None);
 
     assert_eq!(metrics.tx_count, 3);
     assert_eq!(metrics.total_volume, 600);
 }
 
+#[test]
+fn test_get_batch_category_fees() {
+    let (env, admin, client) = setup_test_env();
+
+    let mut transactions: Vec<Transaction> = Vec::new(&env);
+    transactions.push_back(create_transaction(&env, 1, 5000, "transfer"));
+    transactions.push_back(create_transaction(&env, 2, 3000, "transfer"));
+    transactions.push_back(create_transaction(&env, 3, 20000, "budget"));
+
+    client.process_batch(&admin, &transactions, &None, &None, &None);
+
+    let fees = client.get_batch_category_fees(&1);
+    assert_eq!(fees.len(), 2);
+
+    let mut transfer_fees = 0;
+    let mut budget_fees = 0;
+    for (category, total_fees) in fees.iter() {
+        if category == Symbol::new(&env, "transfer") {
+            transfer_fees = total_fees;
+        } else if category == Symbol::new(&env, "budget") {
+            budget_fees = total_fees;
+        }
+    }
+    // 0.1% fee: 5 + 3 for transfer, 20 for budget
+    assert_eq!(transfer_fees, 8);
+    assert_eq!(budget_fees, 20);
+}
+
+#[test]
+fn test_get_batch_category_fees_unknown_batch() {
+    let (_env, _admin, client) = setup_test_env();
+
+    assert_eq!(client.get_batch_category_fees(&42).len(), 0);
+}
+
+#[test]
+fn test_get_category_metrics_matches_computed() {
+    let (env, admin, client) = setup_test_env();
+
+    let mut transactions: Vec<Transaction> = Vec::new(&env);
+    transactions.push_back(create_transaction(&env, 1, 500, "transfer"));
+    transactions.push_back(create_transaction(&env, 2, 300, "budget"));
+    transactions.push_back(create_transaction(&env, 3, 200, "transfer"));
+
+    client.process_batch(&admin, &transactions, &None, &None, &None);
+
+    let stored = client.get_category_metrics(&1).unwrap();
+    let expected = crate::analytics::compute_category_metrics(&env, &transactions, 1000, 10);
+
+    assert_eq!(stored.len(), expected.len());
+    for (stored_metric, expected_metric) in stored.iter().zip(expected.iter()) {
+        assert_eq!(stored_metric.category, expected_metric.category);
+        assert_eq!(stored_metric.tx_count, expected_metric.tx_count);
+        assert_eq!(stored_metric.total_volume, expected_metric.total_volume);
+        assert_eq!(stored_metric.total_fees, expected_metric.total_fees);
+        assert_eq!(
+            stored_metric.volume_percentage_bps,
+            expected_metric.volume_percentage_bps
+        );
+    }
+
+    let mut transfer_bps = 0;
+    for metric in stored.iter() {
+        if metric.category == Symbol::new(&env, "transfer") {
+            transfer_bps = metric.volume_percentage_bps;
+        }
+    }
+    assert_eq!(transfer_bps, 7000);
+    assert!(client.get_category_metrics(&2).is_none());
+}
+
+#[test]
+fn test_category_fees_sum_to_batch_fees() {
+    let (env, admin, client) = setup_test_env();
+    client.set_fee_bps(&admin, &25);
+
+    let mut transactions: Vec<Transaction> = Vec::new(&env);
+    transactions.push_back(create_transaction(&env, 1, 12_345, "transfer"));
+    transactions.push_back(create_transaction(&env, 2, 8_000, "budget"));
+    transactions.push_back(create_transaction(&env, 3, 4_321, "transfer"));
+    transactions.push_back(create_transaction(&env, 4, 60_000, "savings"));
+
+    let metrics = client.process_batch(&admin, &transactions, &None, &None, &None);
+    let categories = client.get_category_metrics(&1).unwrap();
+
+    let mut category_fees: i128 = 0;
+    for metric in categories.iter() {
+        assert!(metric.total_fees > 0);
+        category_fees += metric.total_fees;
+    }
+    // 30 + 20 + 10 + 150, each transaction's fee rounded down individually
+    assert_eq!(metrics.total_fees, 210);
+    assert_eq!(category_fees, metrics.total_fees);
+}
+
+#[test]
+fn test_process_batch_by_category_filters_transactions() {
+    let (env, admin, client) = setup_test_env();
+
+    let mut transactions: Vec<Transaction> = Vec::new(&env);
+    transactions.push_back(create_transaction(&env, 1, 500, "transfer"));
+    transactions.push_back(create_transaction(&env, 2, 300, "budget"));
+    transactions.push_back(create_transaction(&env, 3, 200, "transfer"));
+    transactions.push_back(create_transaction(&env, 4, 900, "savings"));
+
+    let transfer = Symbol::new(&env, "transfer");
+    let metrics = client.process_batch_by_category(&admin, &transactions, &transfer, &None);
+
+    assert_eq!(metrics.tx_count, 2);
+    assert_eq!(metrics.total_volume, 700);
+    assert_eq!(metrics.min_amount, 200);
+    assert_eq!(metrics.max_amount, 500);
+    assert_eq!(metrics.top_category, Some(transfer.clone()));
+
+    let stored = client.get_category_batch_metrics(&transfer, &1).unwrap();
+    assert_eq!(stored.total_volume, 700);
+    assert!(client
+        .get_category_batch_metrics(&Symbol::new(&env, "budget"), &1)
+        .is_none());
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #4)")]
+fn test_process_batch_by_category_no_matches_rejected() {
+    let (env, admin, client) = setup_test_env();
+
+    let mut transactions: Vec<Transaction> = Vec::new(&env);
+    transactions.push_back(create_transaction(&env, 1, 500, "transfer"));
+
+    client.process_batch_by_category(&admin, &transactions, &Symbol::new(&env, "budget"), &None);
+}
+
+// ============================================================================
+// Time-Range Processing Tests
+// ============================================================================
+
+/// Helper to create a transaction at a specific ledger timestamp.
+fn create_transaction_at(
+    env: &Env,
+    tx_id: u64,
+    amount: i128,
+    timestamp: u64,
+    from: Address,
+) -> Transaction {
+    Transaction {
+        tx_id,
+        from,
+        to: Address::generate(env),
+        amount,
+        timestamp,
+        category: Symbol::new(env, "transfer"),
+    }
+}
+
+#[test]
+fn test_process_batch_in_range_filters_transactions() {
+    let (env, admin, client) = setup_test_env();
+
+    let sender1 = Address::generate(&env);
+    let sender2 = Address::generate(&env);
+    let sender3 = Address::generate(&env);
+
+    let mut transactions: Vec<Transaction> = Vec::new(&env);
+    transactions.push_back(create_transaction_at(&env, 1, 100, 50, sender1.clone()));
+    transactions.push_back(create_transaction_at(&env, 2, 200, 100, sender2.clone()));
+    transactions.push_back(create_transaction_at(&env, 3, 300, 150, sender2.clone()));
+    transactions.push_back(create_transaction_at(&env, 4, 400, 200, sender3.clone()));
+    transactions.push_back(create_transaction_at(&env, 5, 500, 250, sender3.clone()));
+
+    let metrics = client.process_batch_in_range(&admin, &transactions, &100, &200, &None);
+
+    assert_eq!(metrics.tx_count, 3);
+    assert_eq!(metrics.total_volume, 900);
+    assert_eq!(metrics.avg_amount, 300);
+    assert_eq!(metrics.min_amount, 200);
+    assert_eq!(metrics.max_amount, 400);
+    assert_eq!(metrics.unique_senders, 2);
+    assert_eq!(metrics.unique_recipients, 3);
+    assert_eq!(client.get_total_transactions_processed(), 3);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #4)")]
+fn test_process_batch_in_range_no_matches_rejected() {
+    let (env, admin, client) = setup_test_env();
+
+    let mut transactions: Vec<Transaction> = Vec::new(&env);
+    transactions.push_back(create_transaction_at(
+        &env,
+        1,
+        100,
+        10,
+        Address::generate(&env),
+    ));
+
+    client.process_batch_in_range(&admin, &transactions, &100, &200, &None);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #3)")]
+fn test_process_batch_in_range_inverted_range_rejected() {
+    let (env, admin, client) = setup_test_env();
+
+    let mut transactions: Vec<Transaction> = Vec::new(&env);
+    transactions.push_back(create_transaction_at(
+        &env,
+        1,
+        100,
+        150,
+        Address::generate(&env),
+    ));
+
+    client.process_batch_in_range(&admin, &transactions, &200, &100, &None);
+}
+
+#[test]
+fn test_detect_time_bursts_entrypoint() {
+    let (env, _admin, client) = setup_test_env();
+
+    let mut clustered: Vec<Transaction> = Vec::new(&env);
+    let mut spread: Vec<Transaction> = Vec::new(&env);
+    for i in 0..4u64 {
+        clustered.push_back(create_transaction_at(
+            &env,
+            i + 1,
+            100,
+            1000 + i,
+            Address::generate(&env),
+        ));
+        spread.push_back(create_transaction_at(
+            &env,
+            i + 1,
+            100,
+            1000 + i * 100,
+            Address::generate(&env),
+        ));
+    }
+
+    assert!(client.detect_time_bursts(&clustered, &10, &3));
+    assert!(!client.detect_time_bursts(&spread, &10, &1));
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #5)")]
+fn test_detect_time_bursts_rejects_oversized_batch() {
+    let (env, _admin, client) = setup_test_env();
+
+    let mut transactions: Vec<Transaction> = Vec::new(&env);
+    for i in 0..(MAX_BATCH_SIZE as u64 + 1) {
+        transactions.push_back(create_transaction_at(
+            &env,
+            i + 1,
+            100,
+            1000 + i,
+            Address::generate(&env),
+        ));
+    }
+
+    client.detect_time_bursts(&transactions, &10, &3);
+}
+
 // ============================================================================
 // Audit Log Tests
 // ============================================================================
@@ -597,13 +1252,13 @@ fn test_bundle_transactions_success() {
     assert_eq!(result.total_count, 3);
     assert_eq!(result.valid_count, 3);
     assert_eq!(result.invalid_count, 0);
-    assert_eq!(result.can_bundle, true);
+    assert!(result.can_bundle);
     assert_eq!(result.total_volume, 6000);
     assert_eq!(result.validation_results.len(), 3);
 
     // All transactions should be valid
     for result_item in result.validation_results.iter() {
-        assert_eq!(result_item.is_valid, true);
+        assert!(result_item.is_valid);
     }
 }
 
@@ -631,22 +1286,22 @@ fn test_bundle_transactions_with_partial_failures() {
     assert_eq!(result.total_count, 3);
     assert_eq!(result.valid_count, 2);
     assert_eq!(result.invalid_count, 1);
-    assert_eq!(result.can_bundle, false); // Not all valid
+    assert!(!result.can_bundle); // Not all valid
     assert_eq!(result.total_volume, 4000); // Only valid transactions
     assert_eq!(result.validation_results.len(), 3);
 
     // Check validation results
     let result_1 = result.validation_results.get(0).unwrap();
     assert_eq!(result_1.tx_id, 1);
-    assert_eq!(result_1.is_valid, true);
+    assert!(result_1.is_valid);
 
     let result_2 = result.validation_results.get(1).unwrap();
     assert_eq!(result_2.tx_id, 2);
-    assert_eq!(result_2.is_valid, false);
+    assert!(!result_2.is_valid);
 
     let result_3 = result.validation_results.get(2).unwrap();
     assert_eq!(result_3.tx_id, 3);
-    assert_eq!(result_3.is_valid, true);
+    assert!(result_3.is_valid);
 }
 
 #[test]
@@ -664,12 +1319,12 @@ fn test_bundle_transactions_with_negative_amount() {
 
     assert_eq!(result.valid_count, 2);
     assert_eq!(result.invalid_count, 1);
-    assert_eq!(result.can_bundle, false);
+    assert!(!result.can_bundle);
 
     // Check that the invalid transaction has the correct error
     let invalid_result = result.validation_results.get(1).unwrap();
     assert_eq!(invalid_result.tx_id, 2);
-    assert_eq!(invalid_result.is_valid, false);
+    assert!(!invalid_result.is_valid);
 }
 
 #[test]
@@ -765,7 +1420,7 @@ fn test_audit_log_events_emitted() {
 
     let events = env.events().all();
     // At least one audit log event should be emitted
-    assert!(events.len() >= 1);
+    assert!(!events.is_empty());
 }
 
 #[test]
@@ -827,7 +1482,7 @@ fn test_bundle_large_number_of_transactions() {
     assert_eq!(result.total_count, 50);
     assert_eq!(result.valid_count, 50);
     assert_eq!(result.invalid_count, 0);
-    assert_eq!(result.can_bundle, true);
+    assert!(result.can_bundle);
     // Sum of 100 + 200 + ... + 5000 = 100 * (1 + 2 + ... + 50) = 100 * 1275 = 127500
     assert_eq!(result.total_volume, 127500);
 }
@@ -845,7 +1500,7 @@ fn test_bundle_with_memo() {
     let result = client.bundle_transactions(&admin, &bundled_txs);
 
     assert_eq!(result.valid_count, 2);
-    assert_eq!(result.can_bundle, true);
+    assert!(result.can_bundle);
 }
 
 #[test]
@@ -876,7 +1531,7 @@ fn test_bundle_all_transactions_invalid() {
 
     assert_eq!(result.valid_count, 0);
     assert_eq!(result.invalid_count, 2);
-    assert_eq!(result.can_bundle, false);
+    assert!(!result.can_bundle);
     assert_eq!(result.total_volume, 0);
 }
 
@@ -892,10 +1547,134 @@ fn test_bundle_zero_amount_transactions() {
 
     // Zero amount transactions are allowed
     assert_eq!(result.valid_count, 2);
-    assert_eq!(result.can_bundle, true);
+    assert!(result.can_bundle);
     assert_eq!(result.total_volume, 1000);
 }
 
+// ============================================================================
+// Bundle Execution Tests
+// ============================================================================
+
+/// Deploys a Stellar asset contract for token-moving tests.
+fn create_token(env: &Env) -> (Address, token::StellarAssetClient<'static>) {
+    let issuer = Address::generate(env);
+    let token_id = env.register_stellar_asset_contract_v2(issuer).address();
+    let token_admin = token::StellarAssetClient::new(env, &token_id);
+    (token_id, token_admin)
+}
+
+#[test]
+fn test_execute_bundle_transfers_funds() {
+    let (env, admin, client) = setup_test_env();
+    // Senders authorize their own transfers inside the bundle call
+    env.mock_all_auths_allowing_non_root_auth();
+    let (token_id, token_admin) = create_token(&env);
+    let token_client = token::Client::new(&env, &token_id);
+
+    let sender = Address::generate(&env);
+    let recipient1 = Address::generate(&env);
+    let recipient2 = Address::generate(&env);
+    token_admin.mint(&sender, &1000);
+
+    let mut bundled_txs: Vec<BundledTransaction> = Vec::new(&env);
+    bundled_txs.push_back(BundledTransaction {
+        transaction: create_transaction_with_addresses(
+            &env,
+            1,
+            sender.clone(),
+            recipient1.clone(),
+            400,
+            "transfer",
+        ),
+        memo: None,
+    });
+    bundled_txs.push_back(BundledTransaction {
+        transaction: create_transaction_with_addresses(
+            &env,
+            2,
+            sender.clone(),
+            recipient2.clone(),
+            600,
+            "transfer",
+        ),
+        memo: None,
+    });
+
+    let result = client.execute_bundle(&admin, &token_id, &bundled_txs);
+
+    assert_eq!(result.bundle_id, 1);
+    assert_eq!(result.executed_count, 2);
+    assert_eq!(result.total_transferred, 1000);
+    assert_eq!(result.outcomes.len(), 2);
+    assert!(result.outcomes.get(0).unwrap().success);
+    assert!(result.outcomes.get(1).unwrap().success);
+
+    assert_eq!(token_client.balance(&sender), 0);
+    assert_eq!(token_client.balance(&recipient1), 400);
+    assert_eq!(token_client.balance(&recipient2), 600);
+    assert!(client.get_bundle_execution(&1).is_some());
+}
+
+#[test]
+fn test_execute_bundle_underfunded_reverts_all() {
+    let (env, admin, client) = setup_test_env();
+    // Senders authorize their own transfers inside the bundle call
+    env.mock_all_auths_allowing_non_root_auth();
+    let (token_id, token_admin) = create_token(&env);
+    let token_client = token::Client::new(&env, &token_id);
+
+    let sender = Address::generate(&env);
+    let recipient1 = Address::generate(&env);
+    let recipient2 = Address::generate(&env);
+    token_admin.mint(&sender, &500);
+
+    let mut bundled_txs: Vec<BundledTransaction> = Vec::new(&env);
+    bundled_txs.push_back(BundledTransaction {
+        transaction: create_transaction_with_addresses(
+            &env,
+            1,
+            sender.clone(),
+            recipient1.clone(),
+            400,
+            "transfer",
+        ),
+        memo: None,
+    });
+    bundled_txs.push_back(BundledTransaction {
+        transaction: create_transaction_with_addresses(
+            &env,
+            2,
+            sender.clone(),
+            recipient2.clone(),
+            600,
+            "transfer",
+        ),
+        memo: None,
+    });
+
+    let result = client.try_execute_bundle(&admin, &token_id, &bundled_txs);
+    assert!(result.is_err());
+
+    // First transfer must have been rolled back with the second
+    assert_eq!(token_client.balance(&sender), 500);
+    assert_eq!(token_client.balance(&recipient1), 0);
+    assert_eq!(token_client.balance(&recipient2), 0);
+    assert_eq!(client.get_last_bundle_id(), 0);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #15)")]
+fn test_execute_bundle_with_invalid_transaction() {
+    let (env, admin, client) = setup_test_env();
+    let (token_id, _token_admin) = create_token(&env);
+
+    let mut bundled_txs: Vec<BundledTransaction> = Vec::new(&env);
+    bundled_txs.push_back(create_bundled_transaction(&env, 1, 100, "transfer"));
+    bundled_txs.push_back(create_bundled_transaction(&env, 2, -50, "transfer"));
+
+    client.execute_bundle(&admin, &token_id, &bundled_txs);
+}
+
 /// Helper to create a refund request.
 fn create_refund_request(env: &Env, tx_id: u64, reason: Option<&str>) -> RefundRequest {
     RefundRequest {
@@ -913,6 +1692,13 @@ fn create_transaction_lookup(env: &Env, transactions: &Vec<Transaction>) -> Map<
     lookup
 }
 
+/// Deploys a token and funds the analytics contract so refunds can be paid out.
+fn fund_contract_token(env: &Env, client: &TransactionAnalyticsContractClient) -> Address {
+    let (token_id, token_admin) = create_token(env);
+    token_admin.mint(&client.address, &1_000_000);
+    token_id
+}
+
 // ============================================================================
 // Refund Tests
 // ============================================================================
@@ -920,6 +1706,7 @@ fn create_transaction_lookup(env: &Env, transactions: &Vec<Transaction>) -> Map<
 #[test]
 fn test_refund_single_eligible_transaction() {
     let (env, admin, client) = setup_test_env();
+    let token_id = fund_contract_token(&env, &client);
     
     // Create some transactions first
     let mut transactions: Vec<Transaction> = Vec::new(&env);
@@ -929,13 +1716,13 @@ fn test_refund_single_eligible_transaction() {
     let lookup = create_transaction_lookup(&env, &transactions);
     
     // Process the batch first to establish transaction records
-    client.process_batch(&admin, &transactions, &None);
+    client.process_batch(&admin, &transactions, &None, &None, &None);
     
     // Create refund request for eligible transaction
     let mut refund_requests: Vec<RefundRequest> = Vec::new(&env);
     refund_requests.push_back(create_refund_request(&env, 1, Some("FailedTransaction")));
     
-    let metrics = client.refund_batch(&admin, &refund_requests, &lookup);
+    let metrics = client.refund_batch(&admin, &token_id, &refund_requests, &lookup);
     
     assert_eq!(metrics.request_count, 1);
     assert_eq!(metrics.successful_refunds, 1);
@@ -951,6 +1738,7 @@ fn test_refund_single_eligible_transaction() {
 #[test]
 fn test_refund_multiple_transactions_mixed_eligibility() {
     let (env, admin, client) = setup_test_env();
+    let token_id = fund_contract_token(&env, &client);
     
     let mut transactions: Vec<Transaction> = Vec::new(&env);
     transactions.push_back(create_transaction(&env, 1, 1000, "transfer")); // Eligible
@@ -959,7 +1747,7 @@ fn test_refund_multiple_transactions_mixed_eligibility() {
     transactions.push_back(create_transaction(&env, 4, 300, "transfer")); // Not eligible
     
     let lookup = create_transaction_lookup(&env, &transactions);
-    client.process_batch(&admin, &transactions, &None);
+    client.process_batch(&admin, &transactions, &None, &None, &None);
     
     let mut refund_requests: Vec<RefundRequest> = Vec::new(&env);
     refund_requests.push_back(create_refund_request(&env, 1, None));
@@ -967,7 +1755,7 @@ fn test_refund_multiple_transactions_mixed_eligibility() {
     refund_requests.push_back(create_refund_request(&env, 3, None));
     refund_requests.push_back(create_refund_request(&env, 4, None));
     
-    let metrics = client.refund_batch(&admin, &refund_requests, &lookup);
+    let metrics = client.refund_batch(&admin, &token_id, &refund_requests, &lookup);
     
     assert_eq!(metrics.request_count, 4);
     assert_eq!(metrics.successful_refunds, 2); // Only odd IDs (1,3) are eligible
@@ -985,20 +1773,21 @@ fn test_refund_multiple_transactions_mixed_eligibility() {
 #[test]
 fn test_refund_already_refunded_transaction() {
     let (env, admin, client) = setup_test_env();
+    let token_id = fund_contract_token(&env, &client);
     
     let mut transactions: Vec<Transaction> = Vec::new(&env);
     transactions.push_back(create_transaction(&env, 1, 1000, "transfer"));
     
     let lookup = create_transaction_lookup(&env, &transactions);
-    client.process_batch(&admin, &transactions, &None);
+    client.process_batch(&admin, &transactions, &None, &None, &None);
     
     // First refund
     let mut refund_requests: Vec<RefundRequest> = Vec::new(&env);
     refund_requests.push_back(create_refund_request(&env, 1, None));
-    client.refund_batch(&admin, &refund_requests, &lookup);
+    client.refund_batch(&admin, &token_id, &refund_requests, &lookup);
     
     // Try to refund the same transaction again
-    let metrics = client.refund_batch(&admin, &refund_requests, &lookup);
+    let metrics = client.refund_batch(&admin, &token_id, &refund_requests, &lookup);
     
     assert_eq!(metrics.request_count, 1);
     assert_eq!(metrics.successful_refunds, 0);
@@ -1009,6 +1798,7 @@ fn test_refund_already_refunded_transaction() {
 #[test]
 fn test_refund_nonexistent_transaction() {
     let (env, admin, client) = setup_test_env();
+    let token_id = fund_contract_token(&env, &client);
     
     // Create empty lookup (no transactions)
     let lookup: Map<u64, Transaction> = Map::new(&env);
@@ -1016,7 +1806,7 @@ fn test_refund_nonexistent_transaction() {
     let mut refund_requests: Vec<RefundRequest> = Vec::new(&env);
     refund_requests.push_back(create_refund_request(&env, 999, Some("NonexistentTx")));
     
-    let metrics = client.refund_batch(&admin, &refund_requests, &lookup);
+    let metrics = client.refund_batch(&admin, &token_id, &refund_requests, &lookup);
     
     assert_eq!(metrics.request_count, 1);
     assert_eq!(metrics.successful_refunds, 0);
@@ -1024,27 +1814,122 @@ fn test_refund_nonexistent_transaction() {
     assert_eq!(metrics.total_refunded_amount, 0);
 }
 
+#[test]
+fn test_refund_batch_stops_at_cap() {
+    let (env, admin, client) = setup_test_env();
+    let token_id = fund_contract_token(&env, &client);
+    client.set_max_refund_per_batch(&admin, &2500);
+    assert_eq!(client.get_max_refund_per_batch(), 2500);
+
+    let mut transactions: Vec<Transaction> = Vec::new(&env);
+    transactions.push_back(create_transaction(&env, 1, 1000, "transfer"));
+    transactions.push_back(create_transaction(&env, 3, 2000, "transfer"));
+    transactions.push_back(create_transaction(&env, 5, 100, "transfer"));
+
+    let lookup = create_transaction_lookup(&env, &transactions);
+    client.process_batch(&admin, &transactions, &None, &None, &None);
+
+    let mut refund_requests: Vec<RefundRequest> = Vec::new(&env);
+    refund_requests.push_back(create_refund_request(&env, 1, None));
+    refund_requests.push_back(create_refund_request(&env, 3, None));
+    refund_requests.push_back(create_refund_request(&env, 5, None));
+
+    let metrics = client.refund_batch(&admin, &token_id, &refund_requests, &lookup);
+
+    // tx 3 would exceed the cap, so it and every later refund fail
+    assert_eq!(metrics.successful_refunds, 1);
+    assert_eq!(metrics.failed_refunds, 2);
+    assert_eq!(metrics.total_refunded_amount, 1000);
+    assert!(client.is_transaction_refunded(&1_u64));
+    assert!(!client.is_transaction_refunded(&3_u64));
+    assert!(!client.is_transaction_refunded(&5_u64));
+}
+
+#[test]
+fn test_retry_failed_refunds_only_retries_failures() {
+    let (env, admin, client) = setup_test_env();
+    let token_id = fund_contract_token(&env, &client);
+
+    let mut transactions: Vec<Transaction> = Vec::new(&env);
+    transactions.push_back(create_transaction(&env, 1, 1000, "transfer"));
+    transactions.push_back(create_transaction(&env, 2, 500, "transfer"));
+    transactions.push_back(create_transaction(&env, 3, 2000, "transfer"));
+    client.process_batch(&admin, &transactions, &None, &None, &None);
+
+    // tx 3 is missing from the first lookup, so its refund fails
+    let mut partial: Vec<Transaction> = Vec::new(&env);
+    partial.push_back(transactions.get(0).unwrap());
+    partial.push_back(transactions.get(1).unwrap());
+    let partial_lookup = create_transaction_lookup(&env, &partial);
+
+    let mut refund_requests: Vec<RefundRequest> = Vec::new(&env);
+    refund_requests.push_back(create_refund_request(&env, 1, None));
+    refund_requests.push_back(create_refund_request(&env, 2, None));
+    refund_requests.push_back(create_refund_request(&env, 3, None));
+
+    let first = client.refund_batch(&admin, &token_id, &refund_requests, &partial_lookup);
+    assert_eq!(first.successful_refunds, 1);
+    assert_eq!(first.failed_refunds, 2);
+    let batch_id = client.get_last_refund_batch_id();
+
+    let full_lookup = create_transaction_lookup(&env, &transactions);
+    let retried = client.retry_failed_refunds(&admin, &batch_id, &full_lookup);
+
+    // tx 3 now succeeds; tx 2 is still ineligible and tx 1 is not paid again
+    assert_eq!(retried.request_count, 3);
+    assert_eq!(retried.successful_refunds, 2);
+    assert_eq!(retried.failed_refunds, 1);
+    assert_eq!(retried.total_refunded_amount, 3000);
+    assert_eq!(retried.avg_refund_amount, 1500);
+    assert_eq!(
+        client
+            .get_refund_batch_metrics(&batch_id)
+            .unwrap()
+            .successful_refunds,
+        2
+    );
+    assert_eq!(client.get_total_refund_amount(), 3000);
+    assert!(client.is_transaction_refunded(&3_u64));
+
+    let token_client = token::Client::new(&env, &token_id);
+    assert_eq!(token_client.balance(&client.address), 1_000_000 - 3000);
+
+    // A second retry has nothing left that can succeed
+    let again = client.retry_failed_refunds(&admin, &batch_id, &full_lookup);
+    assert_eq!(again.successful_refunds, 2);
+    assert_eq!(client.get_total_refund_amount(), 3000);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #16)")]
+fn test_retry_failed_refunds_unknown_batch() {
+    let (env, admin, client) = setup_test_env();
+
+    client.retry_failed_refunds(&admin, &42, &Map::new(&env));
+}
+
 #[test]
 fn test_refund_batch_id_increments() {
     let (env, admin, client) = setup_test_env();
+    let token_id = fund_contract_token(&env, &client);
     
     let mut transactions: Vec<Transaction> = Vec::new(&env);
     transactions.push_back(create_transaction(&env, 1, 1000, "transfer"));
     transactions.push_back(create_transaction(&env, 3, 2000, "budget"));
     
     let lookup = create_transaction_lookup(&env, &transactions);
-    client.process_batch(&admin, &transactions, &None);
+    client.process_batch(&admin, &transactions, &None, &None, &None);
     
     assert_eq!(client.get_last_refund_batch_id(), 0);
     
     let mut refund_requests: Vec<RefundRequest> = Vec::new(&env);
     refund_requests.push_back(create_refund_request(&env, 1, None));
-    client.refund_batch(&admin, &refund_requests, &lookup);
+    client.refund_batch(&admin, &token_id, &refund_requests, &lookup);
     assert_eq!(client.get_last_refund_batch_id(), 1);
     
     let mut refund_requests2: Vec<RefundRequest> = Vec::new(&env);
     refund_requests2.push_back(create_refund_request(&env, 3, None));
-    client.refund_batch(&admin, &refund_requests2, &lookup);
+    client.refund_batch(&admin, &token_id, &refund_requests2, &lookup);
     assert_eq!(client.get_last_refund_batch_id(), 2);
 }
 
@@ -1057,7 +1942,7 @@ fn test_simulate_refund_batch() {
     transactions.push_back(create_transaction(&env, 3, 2000, "budget"));
     
     let lookup = create_transaction_lookup(&env, &transactions);
-    client.process_batch(&admin, &transactions, &None);
+    client.process_batch(&admin, &transactions, &None, &None, &None);
     
     let mut refund_requests: Vec<RefundRequest> = Vec::new(&env);
     refund_requests.push_back(create_refund_request(&env, 1, None));
@@ -1081,17 +1966,18 @@ fn test_simulate_refund_batch() {
 #[test]
 fn test_get_refund_batch_metrics() {
     let (env, admin, client) = setup_test_env();
+    let token_id = fund_contract_token(&env, &client);
     
     let mut transactions: Vec<Transaction> = Vec::new(&env);
     transactions.push_back(create_transaction(&env, 1, 1000, "transfer"));
     
     let lookup = create_transaction_lookup(&env, &transactions);
-    client.process_batch(&admin, &transactions, &None);
+    client.process_batch(&admin, &transactions, &None, &None, &None);
     
     let mut refund_requests: Vec<RefundRequest> = Vec::new(&env);
     refund_requests.push_back(create_refund_request(&env, 1, None));
     
-    let metrics = client.refund_batch(&admin, &refund_requests, &lookup);
+    let metrics = client.refund_batch(&admin, &token_id, &refund_requests, &lookup);
     
     // Should be able to retrieve the stored metrics
     let retrieved_metrics = client.get_refund_batch_metrics(&1_u64).unwrap();
@@ -1107,21 +1993,193 @@ fn test_get_refund_batch_metrics() {
 #[should_panic(expected = "HostError: Error(Contract, #12)")]
 fn test_empty_refund_batch_rejected() {
     let (env, admin, client) = setup_test_env();
+    let token_id = fund_contract_token(&env, &client);
     
     let refund_requests: Vec<RefundRequest> = Vec::new(&env);
     let lookup: Map<u64, Transaction> = Map::new(&env);
     
-    client.refund_batch(&admin, &refund_requests, &lookup);
+    client.refund_batch(&admin, &token_id, &refund_requests, &lookup);
 }
 
 #[test]
 #[should_panic(expected = "HostError: Error(Contract, #2)")]
 fn test_unauthorized_refund_batch() {
     let (env, _admin, client) = setup_test_env();
+    let token_id = fund_contract_token(&env, &client);
     
     let unauthorized_user = Address::generate(&env);
     let refund_requests: Vec<RefundRequest> = Vec::new(&env);
     let lookup: Map<u64, Transaction> = Map::new(&env);
     
-    client.refund_batch(&unauthorized_user, &refund_requests, &lookup);
+    client.refund_batch(&unauthorized_user, &token_id, &refund_requests, &lookup);
+}
+
+#[test]
+fn test_refund_batch_returns_funds_to_sender() {
+    let (env, admin, client) = setup_test_env();
+    let (token_id, token_admin) = create_token(&env);
+    let token_client = token::Client::new(&env, &token_id);
+    token_admin.mint(&client.address, &5000);
+
+    let sender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let mut transactions: Vec<Transaction> = Vec::new(&env);
+    transactions.push_back(create_transaction_with_addresses(
+        &env,
+        1,
+        sender.clone(),
+        recipient.clone(),
+        1500,
+        "transfer",
+    ));
+
+    let lookup = create_transaction_lookup(&env, &transactions);
+    client.process_batch(&admin, &transactions, &None, &None, &None);
+
+    let mut refund_requests: Vec<RefundRequest> = Vec::new(&env);
+    refund_requests.push_back(create_refund_request(&env, 1, None));
+
+    let metrics = client.refund_batch(&admin, &token_id, &refund_requests, &lookup);
+
+    assert_eq!(metrics.successful_refunds, 1);
+    assert_eq!(token_client.balance(&sender), 1500);
+    assert_eq!(token_client.balance(&client.address), 3500);
+    assert!(client.is_transaction_refunded(&1_u64));
+}
+
+#[test]
+fn test_refund_batch_failed_transfer_leaves_refund_unmarked() {
+    let (env, admin, client) = setup_test_env();
+    // Token deployed but the contract holds no balance
+    let (token_id, _token_admin) = create_token(&env);
+    let token_client = token::Client::new(&env, &token_id);
+
+    let sender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let mut transactions: Vec<Transaction> = Vec::new(&env);
+    transactions.push_back(create_transaction_with_addresses(
+        &env,
+        1,
+        sender.clone(),
+        recipient.clone(),
+        1500,
+        "transfer",
+    ));
+
+    let lookup = create_transaction_lookup(&env, &transactions);
+    client.process_batch(&admin, &transactions, &None, &None, &None);
+
+    let mut refund_requests: Vec<RefundRequest> = Vec::new(&env);
+    refund_requests.push_back(create_refund_request(&env, 1, None));
+
+    let metrics = client.refund_batch(&admin, &token_id, &refund_requests, &lookup);
+
+    assert_eq!(metrics.successful_refunds, 0);
+    assert_eq!(metrics.failed_refunds, 1);
+    assert_eq!(metrics.total_refunded_amount, 0);
+    assert_eq!(token_client.balance(&sender), 0);
+    assert!(!client.is_transaction_refunded(&1_u64));
+    assert_eq!(client.get_total_refund_amount(), 0);
+}
+
+// ============================================================================
+// Operator Role Tests
+// ============================================================================
+
+#[test]
+fn test_operator_can_process_batches() {
+    let (env, admin, client) = setup_test_env();
+
+    let operator = Address::generate(&env);
+    client.add_operator(&admin, &operator);
+    assert!(client.is_operator(&operator));
+
+    let mut transactions: Vec<Transaction> = Vec::new(&env);
+    transactions.push_back(create_transaction(&env, 1, 100, "transfer"));
+
+    let metrics = client.process_batch(&operator, &transactions, &None, &None, &None);
+    assert_eq!(metrics.tx_count, 1);
+    assert_eq!(client.get_last_batch_id(), 1);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #2)")]
+fn test_revoked_operator_cannot_process_batches() {
+    let (env, admin, client) = setup_test_env();
+
+    let operator = Address::generate(&env);
+    client.add_operator(&admin, &operator);
+    client.remove_operator(&admin, &operator);
+    assert!(!client.is_operator(&operator));
+
+    let mut transactions: Vec<Transaction> = Vec::new(&env);
+    transactions.push_back(create_transaction(&env, 1, 100, "transfer"));
+
+    client.process_batch(&operator, &transactions, &None, &None, &None);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #2)")]
+fn test_operator_cannot_manage_operators() {
+    let (env, admin, client) = setup_test_env();
+
+    let operator = Address::generate(&env);
+    client.add_operator(&admin, &operator);
+
+    client.add_operator(&operator, &Address::generate(&env));
+}
+
+#[test]
+fn test_get_batch_ids_in_range() {
+    let (env, admin, client) = setup_test_env();
+
+    for (i, ledger) in [100u32, 200, 300].iter().enumerate() {
+        env.ledger().with_mut(|li| {
+            li.sequence_number = *ledger;
+        });
+        let mut transactions: Vec<Transaction> = Vec::new(&env);
+        transactions.push_back(create_transaction(&env, i as u64 + 1, 1000, "transfer"));
+        client.process_batch(&admin, &transactions, &None, &None, &None);
+    }
+
+    assert_eq!(
+        client.get_batch_ids_in_range(&150, &300, &1, &10),
+        (Vec::from_array(&env, [2u64, 3]), 4)
+    );
+    assert_eq!(
+        client.get_batch_ids_in_range(&100, &100, &1, &10),
+        (Vec::from_array(&env, [1u64]), 4)
+    );
+    assert_eq!(
+        client.get_batch_ids_in_range(&301, &500, &1, &10).0.len(),
+        0
+    );
+    assert_eq!(
+        client.get_batch_ids_in_range(&300, &100, &1, &10).0.len(),
+        0
+    );
+
+    // Pages visit at most `limit` ids and return where to resume
+    assert_eq!(
+        client.get_batch_ids_in_range(&100, &300, &1, &2),
+        (Vec::from_array(&env, [1u64, 2]), 3)
+    );
+    assert_eq!(
+        client.get_batch_ids_in_range(&100, &300, &3, &2),
+        (Vec::from_array(&env, [3u64]), 4)
+    );
+    assert_eq!(
+        client.get_batch_ids_in_range(&100, &300, &1, &0),
+        (Vec::new(&env), 1)
+    );
+
+    // Non-matching ids still count towards the page
+    assert_eq!(
+        client.get_batch_ids_in_range(&300, &300, &1, &2),
+        (Vec::new(&env), 3)
+    );
+    assert_eq!(
+        client.get_batch_ids_in_range(&300, &300, &3, &2),
+        (Vec::from_array(&env, [3u64]), 4)
+    );
 }
@@ -5,6 +5,9 @@ use soroban_sdk::{contracttype, symbol_short, Address, Env, Symbol, Vec};
 /// Maximum number of transactions in a single batch for optimization.
 pub const MAX_BATCH_SIZE: u32 = 100;
 
+/// Processing fee rate applied when none is configured: 0.1% (10 basis points).
+pub const DEFAULT_FEE_BPS: u32 = 10;
+
 /// Represents a single transaction record for analytics.
 #[derive(Clone, Debug)]
 #[contracttype]
@@ -38,7 +41,7 @@ pub struct AuditLog {
 }
 
 /// Aggregated metrics for a batch of transactions.
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug)]
 #[contracttype]
 pub struct BatchMetrics {
     /// Total number of transactions in the batch
@@ -57,10 +60,28 @@ pub struct BatchMetrics {
     pub unique_recipients: u32,
     /// Total fees collected for the batch
     pub total_fees: i128,
+    /// Category with the highest volume (`None` for an empty or zero-volume batch)
+    pub top_category: Option<Symbol>,
+    /// Volume of the top category
+    pub top_category_volume: i128,
     /// Batch processing timestamp
     pub processed_at: u64,
 }
 
+/// Lifetime totals accumulated across every processed batch.
+#[derive(Clone, Debug, Default)]
+#[contracttype]
+pub struct LifetimeMetrics {
+    /// Total number of transactions processed
+    pub total_transactions: u64,
+    /// Total volume across all batches
+    pub total_volume: i128,
+    /// Total fees across all batches
+    pub total_fees: i128,
+    /// Total number of batches processed
+    pub total_batches: u64,
+}
+
 /// Category-specific metrics for analytics breakdown.
 #[derive(Clone, Debug)]
 #[contracttype]
@@ -122,6 +143,38 @@ pub struct BundleResult {
     pub created_at: u64,
 }
 
+/// Outcome of executing a single transfer within a bundle.
+#[derive(Clone, Debug)]
+#[contracttype]
+pub struct TransferOutcome {
+    /// Transaction ID that was executed
+    pub tx_id: u64,
+    /// Sender address
+    pub from: Address,
+    /// Recipient address
+    pub to: Address,
+    /// Amount transferred
+    pub amount: i128,
+    /// Whether the transfer was performed
+    pub success: bool,
+}
+
+/// Result of executing a bundle of transfers.
+#[derive(Clone, Debug)]
+#[contracttype]
+pub struct BundleExecutionResult {
+    /// Bundle ID assigned to this execution
+    pub bundle_id: u64,
+    /// Number of transfers performed
+    pub executed_count: u32,
+    /// Total amount moved by the bundle
+    pub total_transferred: i128,
+    /// Per-transaction outcomes in bundle order
+    pub outcomes: Vec<TransferOutcome>,
+    /// Execution timestamp
+    pub executed_at: u64,
+}
+
 /// Input for submitting a rating for a transaction.
 #[derive(Clone, Debug)]
 #[contracttype]
@@ -162,6 +215,8 @@ pub enum TransactionStatus {
 pub struct TransactionStatusUpdate {
     pub tx_id: u64,
     pub status: TransactionStatus,
+    /// Optional audit reason recorded alongside the new status
+    pub reason: Option<Symbol>,
 }
 
 #[derive(Clone, Debug)]
@@ -216,6 +271,38 @@ pub enum DataKey {
     Rating(u64, Address),
     /// Stored status per transaction ID
     TransactionStatus(u64),
+    /// Stored category breakdown for a specific batch ID
+    BatchCategoryMetrics(u64),
+    /// Stored execution result for a specific bundle ID
+    BundleExecution(u64),
+    /// Total volume processed lifetime
+    TotalVolumeAllTime,
+    /// Total fees collected lifetime
+    TotalFeesAllTime,
+    /// Registered operators allowed to process batches
+    Operators(Address),
+    /// Last status-update batch ID
+    LastStatusUpdateBatchId,
+    /// Stored result for a specific status-update batch ID
+    StatusUpdateBatch(u64),
+    /// Stored metrics for a category-scoped batch: (category, batch_id)
+    CategoryBatchMetrics(Symbol, u64),
+    /// Marker for an address that has appeared in any processed batch
+    SeenAddress(Address),
+    /// Number of distinct addresses seen across all batches
+    LifetimeUniqueAddresses,
+    /// Maximum total amount refunded in a single refund batch (0 means unlimited)
+    MaxRefundPerBatch,
+    /// Refund requests that failed in a specific refund batch, kept for retries
+    RefundBatchFailures(u64),
+    /// Token a specific refund batch was paid in
+    RefundBatchToken(u64),
+    /// Ledger a specific batch ID was processed at
+    BatchLedger(u64),
+    /// Admin-set processing fee rate in basis points
+    FeeBps,
+    /// Audit reason attached to the latest status update of a transaction
+    TransactionStatusReason(u64),
 }
 
 /// Status indicating refund eligibility for a transaction.
@@ -232,6 +319,8 @@ pub enum RefundStatus {
     NotEligible,
     /// Transaction ID not found
     NotFound,
+    /// Refund would push the batch past its configured refund cap
+    CapExceeded,
 }
 
 /// Request structure for a single transaction refund.
@@ -315,6 +404,12 @@ impl AnalyticsEvents {
         env.events().publish(topics, (batch_id, tx_id, amount));
     }
 
+    /// Event emitted for transactions far above the batch average.
+    pub fn anomaly_alert(env: &Env, batch_id: u64, tx_id: u64, amount: i128) {
+        let topics = (symbol_short!("alert"), symbol_short!("anomaly"));
+        env.events().publish(topics, (batch_id, tx_id, amount));
+    }
+
     /// Event emitted when an audit log is created.
     pub fn audit_logged(env: &Env, actor: &Address, operation: &Symbol, status: &Symbol) {
         let topics = (symbol_short!("audit"), symbol_short!("log"));
@@ -338,9 +433,11 @@ impl AnalyticsEvents {
         tx_id: u64,
         previous_status: Option<TransactionStatus>,
         new_status: TransactionStatus,
+        reason: Option<Symbol>,
     ) {
         let topics = (symbol_short!("status"), symbol_short!("updated"));
-        env.events().publish(topics, (tx_id, previous_status, new_status));
+        env.events()
+            .publish(topics, (tx_id, previous_status, new_status, reason));
     }
 
     pub fn transaction_status_update_failed(env: &Env, tx_id: u64) {
@@ -376,6 +473,27 @@ impl AnalyticsEvents {
         env.events().publish(topics, (bundle_id, can_bundle));
     }
 
+    /// Event emitted for each transfer performed by an executed bundle.
+    pub fn bundle_transfer(env: &Env, bundle_id: u64, outcome: &TransferOutcome) {
+        let topics = (
+            symbol_short!("bundle"),
+            symbol_short!("transfer"),
+            bundle_id,
+        );
+        env.events().publish(topics, outcome.clone());
+    }
+
+    /// Event emitted when a bundle has been executed.
+    pub fn bundle_executed(env: &Env, bundle_id: u64, result: &BundleExecutionResult) {
+        let topics = (
+            symbol_short!("bundle"),
+            symbol_short!("executed"),
+            bundle_id,
+        );
+        env.events()
+            .publish(topics, (result.executed_count, result.total_transferred));
+    }
+
     /// Event emitted when a transaction fails validation in a bundle.
     pub fn transaction_validation_failed(env: &Env, bundle_id: u64, tx_id: u64, error: &Symbol) {
         let topics = (symbol_short!("bundle"), symbol_short!("failed"));